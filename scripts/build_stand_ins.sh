@@ -0,0 +1,9 @@
+# Builds the stand-in canisters the PocketIC harness installs next to the proxy
+set -e
+
+mkdir -p wasm/stand-ins
+
+for name in profile group event boost notification report topic friend_request global history transaction_handler ledger; do
+  cargo build -p stand_ins --release --target wasm32-unknown-unknown --features "$name"
+  gzip -c target/wasm32-unknown-unknown/release/stand_ins.wasm > "wasm/stand-ins/$name.wasm.gz"
+done
@@ -0,0 +1,59 @@
+use candid::Principal;
+use catalyze_shared::CanisterResult;
+use ic_cdk::{query, update};
+
+use crate::{
+    helpers::guards::{is_developer, is_developer_or_controller},
+    logic::admin_logic::AdminCalls,
+    models::{AdminAuditEntry, AdminRole, AdminRoles},
+};
+
+/// Returns all principals in the admin registry with their roles - [`[query]`](query)
+/// # Returns
+/// * `Vec<(Principal, AdminRoles)>` - The admins
+/// # Note
+/// This function is guarded by the [`is_developer`](is_developer) function.
+#[query(guard = "is_developer")]
+fn _dev_get_admins() -> Vec<(Principal, AdminRoles)> {
+    AdminCalls::get_admins()
+}
+
+/// Returns every change made to the admin registry - [`[query]`](query)
+/// # Returns
+/// * `Vec<(u64, AdminAuditEntry)>` - The changes, oldest first
+/// # Note
+/// This function is guarded by the [`is_developer`](is_developer) function.
+#[query(guard = "is_developer")]
+fn _dev_get_admin_audit_log() -> Vec<(u64, AdminAuditEntry)> {
+    AdminCalls::get_audit_log()
+}
+
+/// Gives a principal an admin role - [`[update]`](update)
+/// # Arguments
+/// * `principal` - The principal to give the role
+/// * `role` - The role to give
+/// # Returns
+/// * `AdminRoles` - The roles of the principal
+/// # Errors
+/// * `ApiError` - If the principal already has the role
+/// # Note
+/// This function is guarded by the [`is_developer_or_controller`](is_developer_or_controller) function.
+#[update(guard = "is_developer_or_controller")]
+fn _dev_add_admin_role(principal: Principal, role: AdminRole) -> CanisterResult<AdminRoles> {
+    AdminCalls::add_role(principal, role)
+}
+
+/// Removes an admin role from a principal - [`[update]`](update)
+/// # Arguments
+/// * `principal` - The principal to remove the role from
+/// * `role` - The role to remove
+/// # Returns
+/// * `AdminRoles` - The remaining roles of the principal
+/// # Errors
+/// * `ApiError` - If the principal does not have the role
+/// # Note
+/// This function is guarded by the [`is_developer_or_controller`](is_developer_or_controller) function.
+#[update(guard = "is_developer_or_controller")]
+fn _dev_remove_admin_role(principal: Principal, role: AdminRole) -> CanisterResult<AdminRoles> {
+    AdminCalls::remove_role(principal, role)
+}
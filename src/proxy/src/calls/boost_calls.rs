@@ -1,11 +1,16 @@
 use crate::{
-    helpers::guards::has_access,
-    logic::{boost_logic::BoostCalls, event_logic::EventCalls, group_logic::GroupCalls},
-    E8S_PER_DAY_BOOST_COST,
+    helpers::{
+        guards::{has_access, is_not_anonymous},
+        metrics::metered,
+    },
+    logic::{
+        boost_logic::BoostCalls, config_logic::ConfigCalls, event_logic::EventCalls,
+        group_logic::GroupCalls,
+    },
 };
 use catalyze_shared::{
     api_error::ApiError, event_with_attendees::EventResponse, group_with_members::GroupResponse,
-    guards::is_not_anonymous, subject::Subject, CanisterResult,
+    subject::Subject, CanisterResult,
 };
 use ic_cdk::{query, update};
 
@@ -30,7 +35,7 @@ async fn get_boosted_events() -> CanisterResult<Vec<EventResponse>> {
 /// * `u64` - the cost of boosting per day
 #[query]
 fn get_e8s_per_day_boost_cost() -> u64 {
-    E8S_PER_DAY_BOOST_COST
+    ConfigCalls::get_platform_config().e8s_per_day_boost_cost
 }
 
 /// Boosts a group or event
@@ -46,16 +51,19 @@ fn get_e8s_per_day_boost_cost() -> u64 {
 /// The identifier is used to determine if the group or event should be boosted.
 #[update(guard = "is_not_anonymous")]
 async fn boost(boost_subject: Subject, blockheight: u64) -> CanisterResult<u64> {
-    has_access().await?;
+    metered("boost", async {
+        has_access().await?;
 
-    use Subject::*;
-    let subject = match boost_subject {
-        Group(id) => Subject::Group(id),
-        Event(id) => Subject::Event(id),
-        _ => return Err(ApiError::bad_request().add_message("Invalid identifier")),
-    };
+        use Subject::*;
+        let subject = match boost_subject {
+            Group(id) => Subject::Group(id),
+            Event(id) => Subject::Event(id),
+            _ => return Err(ApiError::bad_request().add_message("Invalid identifier")),
+        };
 
-    BoostCalls::boost(subject, blockheight).await
+        BoostCalls::boost(subject, blockheight).await
+    })
+    .await
 }
 
 /// Returns the remaining boost time in seconds for a group or event
@@ -1,13 +1,14 @@
 use candid::Principal;
 use catalyze_shared::{CanisterResult, CellStorage};
-use ic_cdk::{id, query, update};
+use ic_cdk::{query, update};
 
 use crate::{
     helpers::guards::is_developer,
+    models::CanisterConfig,
     storage::{
-        boost_canister, event_canister, friend_request_canister, group_canister, history_canister,
-        notification_canister, profile_canister, report_canister, topic_canister,
-        transaction_handler_canister,
+        boost_canister, event_canister, friend_request_canister, get_canister_config,
+        global_canister, group_canister, history_canister, notification_canister, profile_canister,
+        report_canister, set_canister_config, topic_canister, transaction_handler_canister,
     },
 };
 
@@ -121,28 +122,24 @@ fn _dev_set_friend_request_canister(principal: Principal) -> CanisterResult<Prin
     friend_request_canister().set(principal)
 }
 
+// Global canister
+#[query(guard = "is_developer")]
+fn _dev_get_global_canister() -> CanisterResult<Principal> {
+    global_canister().get()
+}
+
+#[update(guard = "is_developer")]
+fn _dev_set_global_canister(principal: Principal) -> CanisterResult<Principal> {
+    global_canister().set(principal)
+}
+
+// All canisters
+#[query(guard = "is_developer")]
+fn _dev_get_canister_config() -> CanisterConfig {
+    get_canister_config()
+}
+
 #[update(guard = "is_developer")]
-pub fn _dev_canisters_init() {
-    // not enviroment specific
-    let _ = transaction_handler_canister()
-        .set(Principal::from_text("r4bli7-7iaaa-aaaap-ahd4a-cai").unwrap());
-
-    // development
-    if id().to_string().as_str() == "puwkw-6qaaa-aaaap-ahmvq-cai" {
-        let _ =
-            profile_canister().set(Principal::from_text("qj423-uyaaa-aaaap-aho4a-cai").unwrap());
-        let _ = notification_canister()
-            .set(Principal::from_text("xz5up-6qaaa-aaaap-ahwxq-cai").unwrap());
-        let _ =
-            history_canister().set(Principal::from_text("vkcxp-wiaaa-aaaap-ahwza-cai").unwrap());
-        let _ = report_canister().set(Principal::from_text("iha5a-maaaa-aaaap-ahtda-cai").unwrap());
-        let _ = group_canister().set(Principal::from_text("ivgkz-aqaaa-aaaap-ahtaa-cai").unwrap());
-        let _ = event_canister().set(Principal::from_text("i4fbf-wyaaa-aaaap-ahtbq-cai").unwrap());
-        let _ = topic_canister().set(Principal::from_text("v7fgc-xaaaa-aaaap-ahw2q-cai").unwrap());
-        let _ = boost_canister().set(Principal::from_text("vyeaw-2yaaa-aaaap-ahw2a-cai").unwrap());
-        let _ = friend_request_canister()
-            .set(Principal::from_text("vwgn6-biaaa-aaaap-ahw3a-cai").unwrap());
-    }
-
-    // TODO: Add staging and production canisters for correct initialization
+fn _dev_set_canister_config(config: CanisterConfig) -> CanisterResult<CanisterConfig> {
+    set_canister_config(config)
 }
@@ -0,0 +1,30 @@
+use catalyze_shared::CanisterResult;
+use ic_cdk::{query, update};
+
+use crate::{
+    helpers::guards::is_developer,
+    logic::config_logic::ConfigCalls,
+    models::{PlatformConfig, UpdatePlatformConfig},
+};
+
+/// Returns the platform parameters - [`[query]`](query)
+/// # Returns
+/// * `PlatformConfig` - The current platform config
+#[query]
+fn get_platform_config() -> PlatformConfig {
+    ConfigCalls::get_platform_config()
+}
+
+/// Updates the platform parameters - [`[update]`](update)
+/// # Arguments
+/// * `update` - The parameters to change, `None` fields are left unchanged
+/// # Returns
+/// * `PlatformConfig` - The updated platform config
+/// # Errors
+/// * `ApiError` - If the resulting config is invalid
+/// # Note
+/// This function is guarded by the [`is_developer`](is_developer) function.
+#[update(guard = "is_developer")]
+fn _dev_update_platform_config(update: UpdatePlatformConfig) -> CanisterResult<PlatformConfig> {
+    ConfigCalls::update_platform_config(update)
+}
@@ -1,7 +1,7 @@
 use crate::{
     helpers::{
-        group_permission::{can_delete, can_edit},
-        guards::has_access,
+        guards::{has_access, is_not_anonymous},
+        metrics::metered,
     },
     logic::event_logic::EventCalls,
 };
@@ -15,18 +15,16 @@ use crate::{
 ///
 use candid::Principal;
 use catalyze_shared::{
-    api_error::ApiError,
     attendee::{InviteAttendeeResponse, JoinedAttendeeResponse},
     event_with_attendees::{
         Attendee, EventFilter, EventResponse, EventSort, EventsCount, PostEvent, UpdateEvent,
     },
-    guards::is_not_anonymous,
     paged_response::PagedResponse,
-    permission::PermissionType,
     profile_with_refs::ProfileResponse,
     CanisterResult,
 };
 use ic_cdk::{query, update};
+use proxy_macros::group_permission;
 
 /// Add an event - [`[update]`](update)
 /// # Arguments
@@ -37,16 +35,14 @@ use ic_cdk::{query, update};
 /// * `ApiError` - If something went wrong while adding the event
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
+#[group_permission(Event, Edit, group_id = post_event.group_id)]
 #[update(guard = "is_not_anonymous")]
 pub async fn add_event(post_event: PostEvent) -> CanisterResult<EventResponse> {
-    has_access().await?;
-
-    let group_id = post_event
-        .group_id
-        .ok_or_else(|| ApiError::bad_request().add_message("Group id is required"))?;
-
-    can_edit(group_id, PermissionType::Event(None)).await?;
-    EventCalls::add_event(post_event).await
+    metered("add_event", async {
+        has_access().await?;
+        EventCalls::add_event(post_event).await
+    })
+    .await
 }
 
 /// Get an event - [`[query]`](query)
@@ -107,15 +103,18 @@ async fn get_event_count(
 /// * `ApiError` - If something went wrong while updating the event
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
+#[group_permission(Event, Edit)]
 #[update(guard = "is_not_anonymous")]
 pub async fn edit_event(
     event_id: u64,
     group_id: u64,
     update_event: UpdateEvent,
 ) -> CanisterResult<EventResponse> {
-    has_access().await?;
-    can_edit(group_id, PermissionType::Event(None)).await?;
-    EventCalls::edit_event(event_id, update_event, group_id).await
+    metered("edit_event", async {
+        has_access().await?;
+        EventCalls::edit_event(event_id, update_event, group_id).await
+    })
+    .await
 }
 
 /// Delete an event - [`[update]`](update)
@@ -128,11 +127,14 @@ pub async fn edit_event(
 /// * `ApiError` - If something went wrong while deleting the event
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
+#[group_permission(Event, Delete)]
 #[update(guard = "is_not_anonymous")]
 pub async fn delete_event(event_id: u64, group_id: u64) -> CanisterResult<()> {
-    has_access().await?;
-    can_delete(group_id, PermissionType::Event(None)).await?;
-    EventCalls::delete_event(event_id, group_id).await
+    metered("delete_event", async {
+        has_access().await?;
+        EventCalls::delete_event(event_id, group_id).await
+    })
+    .await
 }
 
 /// Cancel an event - [`[update]`](update)
@@ -146,11 +148,14 @@ pub async fn delete_event(event_id: u64, group_id: u64) -> CanisterResult<()> {
 /// * `ApiError` - If something went wrong while cancelling the event
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
+#[group_permission(Event, Edit)]
 #[update(guard = "is_not_anonymous")]
 pub async fn cancel_event(event_id: u64, group_id: u64, reason: String) -> CanisterResult<()> {
-    has_access().await?;
-    can_edit(group_id, PermissionType::Event(None)).await?;
-    EventCalls::cancel_event(event_id, reason, group_id).await
+    metered("cancel_event", async {
+        has_access().await?;
+        EventCalls::cancel_event(event_id, reason, group_id).await
+    })
+    .await
 }
 
 // Attendee methods
@@ -167,8 +172,11 @@ pub async fn cancel_event(event_id: u64, group_id: u64, reason: String) -> Canis
 /// This function is guarded by the [`has_access`](has_access) function.
 #[update(guard = "is_not_anonymous")]
 pub async fn join_event(event_id: u64) -> CanisterResult<JoinedAttendeeResponse> {
-    has_access().await?;
-    EventCalls::join_event(event_id).await
+    metered("join_event", async {
+        has_access().await?;
+        EventCalls::join_event(event_id).await
+    })
+    .await
 }
 
 /// Invite a user to an event - [`[update]`](update)
@@ -183,15 +191,18 @@ pub async fn join_event(event_id: u64) -> CanisterResult<JoinedAttendeeResponse>
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
 /// TODO: This action is guarded by group role based authorization
+#[group_permission(Event, Edit)]
 #[update(guard = "is_not_anonymous")]
 pub async fn invite_to_event(
     event_id: u64,
     group_id: u64,
     attendee_principal: Principal,
 ) -> CanisterResult<InviteAttendeeResponse> {
-    has_access().await?;
-    can_edit(group_id, PermissionType::Event(None)).await?;
-    EventCalls::invite_to_event(event_id, attendee_principal, group_id).await
+    metered("invite_to_event", async {
+        has_access().await?;
+        EventCalls::invite_to_event(event_id, attendee_principal, group_id).await
+    })
+    .await
 }
 
 /// Accept an user invite to an event as a admin - [`[update]`](update)
@@ -206,20 +217,23 @@ pub async fn invite_to_event(
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
 /// TODO: This action is guarded by group role based authorization
+#[group_permission(Event, Edit)]
 #[update(guard = "is_not_anonymous")]
 pub async fn accept_user_request_event_invite(
     event_id: u64,
     group_id: u64,
     attendee_principal: Principal,
 ) -> CanisterResult<JoinedAttendeeResponse> {
-    has_access().await?;
-    can_edit(group_id, PermissionType::Event(None)).await?;
-    EventCalls::accept_or_decline_user_request_event_invite(
-        event_id,
-        attendee_principal,
-        group_id,
-        true,
-    )
+    metered("accept_user_request_event_invite", async {
+        has_access().await?;
+        EventCalls::accept_or_decline_user_request_event_invite(
+            event_id,
+            attendee_principal,
+            group_id,
+            true,
+        )
+        .await
+    })
     .await
 }
 
@@ -235,20 +249,23 @@ pub async fn accept_user_request_event_invite(
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
 /// TODO: This action is guarded by group role based authorization
+#[group_permission(Event, Edit)]
 #[update(guard = "is_not_anonymous")]
 pub async fn decline_user_request_event_invite(
     event_id: u64,
     group_id: u64,
     attendee_principal: Principal,
 ) -> CanisterResult<JoinedAttendeeResponse> {
-    has_access().await?;
-    can_edit(group_id, PermissionType::Event(None)).await?;
-    EventCalls::accept_or_decline_user_request_event_invite(
-        event_id,
-        attendee_principal,
-        group_id,
-        false,
-    )
+    metered("decline_user_request_event_invite", async {
+        has_access().await?;
+        EventCalls::accept_or_decline_user_request_event_invite(
+            event_id,
+            attendee_principal,
+            group_id,
+            false,
+        )
+        .await
+    })
     .await
 }
 
@@ -263,8 +280,11 @@ pub async fn decline_user_request_event_invite(
 /// This function is guarded by the [`has_access`](has_access) function.
 #[update(guard = "is_not_anonymous")]
 pub async fn accept_owner_request_event_invite(event_id: u64) -> CanisterResult<Attendee> {
-    has_access().await?;
-    EventCalls::accept_or_decline_owner_request_event_invite(event_id, true).await
+    metered("accept_owner_request_event_invite", async {
+        has_access().await?;
+        EventCalls::accept_or_decline_owner_request_event_invite(event_id, true).await
+    })
+    .await
 }
 
 /// Decline an owner invite to an event as a user - [`[update]`](update)
@@ -278,8 +298,11 @@ pub async fn accept_owner_request_event_invite(event_id: u64) -> CanisterResult<
 /// This function is guarded by the [`has_access`](has_access) function.
 #[update(guard = "is_not_anonymous")]
 pub async fn decline_owner_request_event_invite(event_id: u64) -> CanisterResult<Attendee> {
-    has_access().await?;
-    EventCalls::accept_or_decline_owner_request_event_invite(event_id, false).await
+    metered("decline_owner_request_event_invite", async {
+        has_access().await?;
+        EventCalls::accept_or_decline_owner_request_event_invite(event_id, false).await
+    })
+    .await
 }
 
 /// Get the attendees for an event - [`[query]`](query)
@@ -386,8 +409,11 @@ pub async fn get_attending_from_principal(
 /// This function is guarded by the [`has_access`](has_access) function.
 #[update(guard = "is_not_anonymous")]
 pub async fn leave_event(event_id: u64) -> CanisterResult<()> {
-    has_access().await?;
-    EventCalls::leave_event(event_id).await
+    metered("leave_event", async {
+        has_access().await?;
+        EventCalls::leave_event(event_id).await
+    })
+    .await
 }
 
 /// Remove an event invite as a user - [`[update]`](update)
@@ -403,8 +429,11 @@ pub async fn leave_event(event_id: u64) -> CanisterResult<()> {
 /// This function is guarded by the [`has_access`](has_access) function.
 #[update(guard = "is_not_anonymous")]
 pub async fn remove_event_invite(event_id: u64) -> CanisterResult<()> {
-    has_access().await?;
-    EventCalls::remove_event_invite(event_id).await
+    metered("remove_event_invite", async {
+        has_access().await?;
+        EventCalls::remove_event_invite(event_id).await
+    })
+    .await
 }
 
 /// Remove an event attendee as a admin - [`[update]`](update)
@@ -418,15 +447,18 @@ pub async fn remove_event_invite(event_id: u64) -> CanisterResult<()> {
 /// * `ApiError` - If something went wrong while removing the event
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
+#[group_permission(Event, Edit)]
 #[update(guard = "is_not_anonymous")]
 pub async fn remove_attendee_from_event(
     event_id: u64,
     group_id: u64,
     attendee_principal: Principal,
 ) -> CanisterResult<()> {
-    has_access().await?;
-    can_edit(group_id, PermissionType::Event(None)).await?;
-    EventCalls::remove_attendee_from_event(attendee_principal, event_id).await
+    metered("remove_attendee_from_event", async {
+        has_access().await?;
+        EventCalls::remove_attendee_from_event(attendee_principal, event_id).await
+    })
+    .await
 }
 
 /// Remove an event invite as a admin - [`[update]`](update)
@@ -440,15 +472,18 @@ pub async fn remove_attendee_from_event(
 /// * `ApiError` - If something went wrong while removing the event
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
+#[group_permission(Event, Edit)]
 #[update(guard = "is_not_anonymous")]
 pub async fn remove_attendee_invite_from_event(
     event_id: u64,
     group_id: u64,
     attendee_principal: Principal,
 ) -> CanisterResult<()> {
-    has_access().await?;
-    can_edit(group_id, PermissionType::Event(None)).await?;
-    EventCalls::remove_attendee_invite_from_event(attendee_principal, event_id).await
+    metered("remove_attendee_invite_from_event", async {
+        has_access().await?;
+        EventCalls::remove_attendee_invite_from_event(attendee_principal, event_id).await
+    })
+    .await
 }
 
 /// Get the invites for an event - [`[query]`](query)
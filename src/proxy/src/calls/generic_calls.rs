@@ -1,4 +1,12 @@
-use crate::{helpers::guards::is_prod_developer, logic::websocket_logic::Websocket};
+use crate::{
+    helpers::guards::is_prod_developer,
+    logic::{
+        admin_logic::AdminCalls, group_logic::GroupCalls, metrics_logic::MetricsCalls,
+        websocket_logic::Websocket,
+    },
+    models::CanisterConfig,
+    storage::set_canister_config,
+};
 use candid::Principal;
 use catalyze_shared::http_types::{HttpRequest, HttpResponse};
 use ic_cdk::{
@@ -10,7 +18,13 @@ use ic_cdk::{
 };
 
 #[post_upgrade]
-pub async fn post_upgrade() {
+pub async fn post_upgrade(config: Option<CanisterConfig>) {
+    apply_canister_config(config);
+    AdminCalls::seed_legacy_admins();
+    MetricsCalls::start_store_sizes_timer();
+    GroupCalls::schedule_archived_group_purges();
+    GroupCalls::schedule_join_request_expiries();
+    GroupCalls::start_gated_revalidation_timer();
     Websocket::init();
 }
 
@@ -18,10 +32,24 @@ pub async fn post_upgrade() {
 pub fn pre_upgrade() {}
 
 #[init]
-pub fn init() {
+pub fn init(config: Option<CanisterConfig>) {
+    apply_canister_config(config);
+    AdminCalls::seed_legacy_admins();
+    MetricsCalls::start_store_sizes_timer();
+    GroupCalls::start_gated_revalidation_timer();
     Websocket::init();
 }
 
+/// Stores the storage canister topology passed on install or upgrade, when given.
+/// Traps on an incomplete topology so the install or upgrade is rolled back.
+fn apply_canister_config(config: Option<CanisterConfig>) {
+    if let Some(config) = config {
+        if let Err(err) = set_canister_config(config) {
+            ic_cdk::trap(&err.to_string());
+        }
+    }
+}
+
 #[query]
 fn icts_name() -> String {
     env!("CARGO_PKG_NAME").to_string()
@@ -81,6 +109,14 @@ fn http_request(req: HttpRequest) -> HttpResponse {
                 headers: vec![],
                 body: include_bytes!("../../../../CHANGELOG.md").to_vec(),
             },
+            "metrics" => HttpResponse {
+                status_code: 200,
+                headers: vec![(
+                    "Content-Type".to_string(),
+                    "text/plain; version=0.0.4".to_string(),
+                )],
+                body: MetricsCalls::render().into_bytes(),
+            },
             _ => permission_denied,
         },
         _ => permission_denied,
@@ -1,9 +1,14 @@
 use crate::{
     helpers::{
-        group_permission::{can_delete, can_edit, can_read},
-        guards::has_access,
+        guards::{has_access, is_not_anonymous},
+        metrics::metered,
     },
     logic::group_logic::GroupCalls,
+    models::{
+        ArchivedGroup, GatedGracePeriod, GatedRemovalReport, GatingRule, GroupEligibility,
+        GroupMemberFilter, GroupMemberSort, InviteCode, JoinGroupResult, OwnershipTransfer,
+        PostInviteCode, WaitlistEntry,
+    },
 };
 
 /// # Group methods
@@ -19,21 +24,22 @@ use catalyze_shared::{
     group_with_members::{
         GroupFilter, GroupResponse, GroupSort, GroupsCount, PostGroup, UpdateGroup,
     },
-    guards::is_not_anonymous,
     old_member::{InviteMemberResponse, JoinedMemberResponse},
     paged_response::PagedResponse,
-    permission::{PermissionType, PostPermission},
+    permission::PostPermission,
     profile_with_refs::ProfileResponse,
     relation_type::RelationType,
     role::Role,
     CanisterResult,
 };
-use ic_cdk::{query, update};
+use ic_cdk::{caller, query, update};
+use proxy_macros::group_permission;
 
 /// Add a group to the canister  - [`[update]`](update)
 /// # Arguments
 /// * `post_group` - The group to add
 /// * `account_identifier` - Optional account identifier needed in case when the group is Gated
+/// * `gating_rule` - Optional gating rule of a gated group, replaces the rule of its gated type
 /// # Returns
 /// * `GroupResponse` - The added group
 /// # Errors
@@ -44,9 +50,13 @@ use ic_cdk::{query, update};
 pub async fn add_group(
     post_group: PostGroup,
     account_identifier: Option<String>,
+    gating_rule: Option<GatingRule>,
 ) -> CanisterResult<GroupResponse> {
-    has_access().await?;
-    GroupCalls::add_group(post_group, account_identifier).await
+    metered("add_group", async {
+        has_access().await?;
+        GroupCalls::add_group(post_group, account_identifier, gating_rule).await
+    })
+    .await
 }
 
 /// Get a group - [`[query]`](query)
@@ -115,11 +125,14 @@ pub async fn get_groups_count(query: Option<String>) -> CanisterResult<GroupsCou
 /// * `ApiError` - If something went wrong while updating the group
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
+#[group_permission(Group, Edit)]
 #[update(guard = "is_not_anonymous")]
 pub async fn edit_group(group_id: u64, update_group: UpdateGroup) -> CanisterResult<GroupResponse> {
-    has_access().await?;
-    can_edit(group_id, PermissionType::Group(None)).await?;
-    GroupCalls::edit_group(group_id, update_group).await
+    metered("edit_group", async {
+        has_access().await?;
+        GroupCalls::edit_group(group_id, update_group).await
+    })
+    .await
 }
 
 /// Get groups by their identifiers - [`[query]`](query)
@@ -139,16 +152,47 @@ pub async fn get_groups_by_id(group_ids: Vec<u64>) -> CanisterResult<Vec<GroupRe
 /// # Arguments
 /// * `group_id` - The identifier of the group
 /// # Returns
-/// * `GroupResponse` - The deleted group
+/// * `bool` - If the group is archived
 /// # Errors
 /// * `ApiError` - If something went wrong while deleting the group
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
+/// The group is hidden and read-only until the owner restores it, or it is purged with its events
+/// once the `group_restore_window` of the platform config has passed.
+#[group_permission(Group, Delete)]
 #[update(guard = "is_not_anonymous")]
 pub async fn delete_group(group_id: u64) -> CanisterResult<bool> {
-    has_access().await?;
-    can_delete(group_id, PermissionType::Group(None)).await?;
-    GroupCalls::delete_group(group_id).await
+    metered("delete_group", async {
+        has_access().await?;
+        GroupCalls::delete_group(group_id).await
+    })
+    .await
+}
+
+/// Restore a soft deleted group - [`[update]`](update)
+/// # Arguments
+/// * `group_id` - The identifier of the group
+/// # Returns
+/// * `GroupResponse` - The restored group
+/// # Errors
+/// * `ApiError` - If the group is not archived, the caller is not the owner or the restore window has passed
+/// # Note
+/// This function is guarded by the [`has_access`](has_access) function.
+#[update(guard = "is_not_anonymous")]
+pub async fn restore_group(group_id: u64) -> CanisterResult<GroupResponse> {
+    metered("restore_group", async {
+        has_access().await?;
+        GroupCalls::restore_group(group_id).await
+    })
+    .await
+}
+
+/// Get the soft deleted groups of the caller that are waiting to be purged - [`[query]`](query)
+/// # Returns
+/// * `Vec<ArchivedGroup>` - The archived groups owned by the caller
+#[query(guard = "is_not_anonymous")]
+pub fn get_self_archived_groups() -> Vec<ArchivedGroup> {
+    GroupCalls::get_archived_groups_for(caller())
 }
 
 /// Add a wallet reference to the group - [`[update]`](update)
@@ -164,15 +208,18 @@ pub async fn delete_group(group_id: u64) -> CanisterResult<bool> {
 /// * `ApiError` - If something went wrong while adding the wallet
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
+#[group_permission(Group, Edit)]
 #[update(guard = "is_not_anonymous")]
 pub async fn add_wallet_to_group(
     group_id: u64,
     wallet_canister: Principal,
     description: String,
 ) -> CanisterResult<GroupResponse> {
-    has_access().await?;
-    can_edit(group_id, PermissionType::Group(None)).await?;
-    GroupCalls::add_wallet_to_group(group_id, wallet_canister, description).await
+    metered("add_wallet_to_group", async {
+        has_access().await?;
+        GroupCalls::add_wallet_to_group(group_id, wallet_canister, description).await
+    })
+    .await
 }
 
 /// Remove a wallet reference from the group - [`[update]`](update)
@@ -187,14 +234,17 @@ pub async fn add_wallet_to_group(
 /// * `ApiError` - If something went wrong while removing the wallet
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
+#[group_permission(Group, Edit)]
 #[update(guard = "is_not_anonymous")]
 pub async fn remove_wallet_from_group(
     group_id: u64,
     wallet_canister: Principal,
 ) -> CanisterResult<GroupResponse> {
-    has_access().await?;
-    can_edit(group_id, PermissionType::Group(None)).await?;
-    GroupCalls::remove_wallet_from_group(group_id, wallet_canister).await
+    metered("remove_wallet_from_group", async {
+        has_access().await?;
+        GroupCalls::remove_wallet_from_group(group_id, wallet_canister).await
+    })
+    .await
 }
 
 /// Add a role to the group - [`[update]`](update)
@@ -203,23 +253,29 @@ pub async fn remove_wallet_from_group(
 /// * `role_name` - The name of the role
 /// * `color` - The color of the role
 /// * `index` - The index of the role
+/// * `rank` - Optional rank of the role, between the `member` (0) and `owner` (400) roles, defaults to 100
 /// # Returns
 /// * `Role` - The added role
 /// # Errors
 /// * `ApiError` - If something went wrong while adding the role
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
+/// The `moderator` role ranks 200 and the `admin` role 300, the caller has to outrank the role.
 /// Was `add_role`
+#[group_permission(Group, Edit)]
 #[update(guard = "is_not_anonymous")]
 pub async fn add_role_to_group(
     group_id: u64,
     role_name: String,
     color: String,
     index: u64,
+    rank: Option<u64>,
 ) -> CanisterResult<Role> {
-    has_access().await?;
-    can_edit(group_id, PermissionType::Group(None)).await?;
-    GroupCalls::add_role_to_group(group_id, role_name, color, index).await
+    metered("add_role_to_group", async {
+        has_access().await?;
+        GroupCalls::add_role_to_group(group_id, role_name, color, index, rank).await
+    })
+    .await
 }
 
 /// Remove a role from the group - [`[update]`](update)
@@ -234,11 +290,14 @@ pub async fn add_role_to_group(
 /// * `ApiError` - If something went wrong while removing the role
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
+#[group_permission(Group, Edit)]
 #[update(guard = "is_not_anonymous")]
 pub async fn remove_group_role(group_id: u64, role_name: String) -> CanisterResult<bool> {
-    has_access().await?;
-    can_edit(group_id, PermissionType::Group(None)).await?;
-    GroupCalls::remove_group_role(group_id, role_name).await
+    metered("remove_group_role", async {
+        has_access().await?;
+        GroupCalls::remove_group_role(group_id, role_name).await
+    })
+    .await
 }
 
 /// Get the roles of the group - [`[query]`](query)
@@ -249,10 +308,10 @@ pub async fn remove_group_role(group_id: u64, role_name: String) -> CanisterResu
 /// # Note
 /// Default unmutable roles are always returned on top of the custom group specific roles.
 /// This function is guarded by the [`has_access`](has_access) function.
+#[group_permission(Group, Read)]
 #[query(composite = true, guard = "is_not_anonymous")]
 pub async fn get_group_roles(group_id: u64) -> CanisterResult<Vec<Role>> {
     has_access().await?;
-    can_read(group_id, PermissionType::Group(None)).await?;
     GroupCalls::get_group_roles(group_id).await
 }
 
@@ -267,15 +326,18 @@ pub async fn get_group_roles(group_id: u64) -> CanisterResult<Vec<Role>> {
 /// * `ApiError` - If something went wrong while updating the permissions
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
+#[group_permission(Group, Edit)]
 #[update(guard = "is_not_anonymous")]
 pub async fn edit_role_permissions(
     group_id: u64,
     role_name: String,
     post_permissions: Vec<PostPermission>,
 ) -> CanisterResult<bool> {
-    has_access().await?;
-    can_edit(group_id, PermissionType::Group(None)).await?;
-    GroupCalls::edit_role_permissions(group_id, role_name, post_permissions).await
+    metered("edit_role_permissions", async {
+        has_access().await?;
+        GroupCalls::edit_role_permissions(group_id, role_name, post_permissions).await
+    })
+    .await
 }
 
 /// Join a group - [`[update]`](update)
@@ -283,7 +345,8 @@ pub async fn edit_role_permissions(
 /// * `group_id` - The identifier of the group to join
 /// * `account_identifier` - Optional account identifier of the user in case the group is Gated
 /// # Returns
-/// * `JoinedMemberResponse` - The joined member details
+/// * `JoinGroupResult` - `Joined` with the member details, `Requested` when a join request was sent
+/// to a private group or `Waitlisted` when the member cap of the group is reached
 /// # Errors
 /// * `ApiError` - If something went wrong while joining the group
 /// # Note
@@ -292,9 +355,12 @@ pub async fn edit_role_permissions(
 pub async fn join_group(
     group_id: u64,
     account_identifier: Option<String>,
-) -> CanisterResult<JoinedMemberResponse> {
-    has_access().await?;
-    GroupCalls::join_group(group_id, account_identifier).await
+) -> CanisterResult<JoinGroupResult> {
+    metered("join_group", async {
+        has_access().await?;
+        GroupCalls::join_group(group_id, account_identifier).await
+    })
+    .await
 }
 
 /// Invite a user to a group - [`[update]`](update)
@@ -308,11 +374,38 @@ pub async fn join_group(
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
 /// TODO: This action is guarded by group role based authorization
+#[group_permission(Invite, Edit)]
 #[update(guard = "is_not_anonymous")]
 pub async fn invite_to_group(group_id: u64, member_principal: Principal) -> CanisterResult<()> {
-    has_access().await?;
-    can_edit(group_id, PermissionType::Invite(None)).await?;
-    GroupCalls::invite_to_group(member_principal, group_id).await
+    metered("invite_to_group", async {
+        has_access().await?;
+        GroupCalls::invite_to_group(member_principal, group_id).await
+    })
+    .await
+}
+
+/// Invite multiple users to a group - [`[update]`](update)
+/// # Arguments
+/// * `group_id` - The identifier of the group to invite the users to
+/// * `member_principals` - The principals of the users to invite
+/// # Returns
+/// * `Vec<(Principal, CanisterResult<()>)>` - The result per user
+/// # Errors
+/// * `ApiError` - If the caller is not allowed to invite or something went wrong while storing the invites
+/// # Note
+/// This function is guarded by the [`has_access`](has_access) function.
+/// At most `MEMBER_BATCH_LIMIT` principals can be passed at once.
+#[group_permission(Invite, Edit)]
+#[update(guard = "is_not_anonymous")]
+pub async fn invite_many_to_group(
+    group_id: u64,
+    member_principals: Vec<Principal>,
+) -> CanisterResult<Vec<(Principal, CanisterResult<()>)>> {
+    metered("invite_many_to_group", async {
+        has_access().await?;
+        GroupCalls::invite_many_to_group(member_principals, group_id).await
+    })
+    .await
 }
 
 /// Accept an invite to a group as a admin - [`[update]`](update)
@@ -325,14 +418,18 @@ pub async fn invite_to_group(group_id: u64, member_principal: Principal) -> Cani
 /// * `ApiError` - If something went wrong while accepting the invite
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
+#[group_permission(Invite, Edit)]
 #[update(guard = "is_not_anonymous")]
 pub async fn accept_user_request_group_invite(
     group_id: u64,
     member_principal: Principal,
 ) -> CanisterResult<()> {
-    has_access().await?;
-    can_edit(group_id, PermissionType::Invite(None)).await?;
-    GroupCalls::accept_or_decline_user_request_group_invite(member_principal, group_id, true).await
+    metered("accept_user_request_group_invite", async {
+        has_access().await?;
+        GroupCalls::accept_or_decline_user_request_group_invite(member_principal, group_id, true)
+            .await
+    })
+    .await
 }
 
 /// Decline an invite to a group as a admin - [`[update]`](update)
@@ -345,14 +442,18 @@ pub async fn accept_user_request_group_invite(
 /// * `ApiError` - If something went wrong while declining the invite
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
+#[group_permission(Invite, Edit)]
 #[update(guard = "is_not_anonymous")]
 pub async fn decline_user_request_group_invite(
     group_id: u64,
     member_principal: Principal,
 ) -> CanisterResult<()> {
-    has_access().await?;
-    can_edit(group_id, PermissionType::Invite(None)).await?;
-    GroupCalls::accept_or_decline_user_request_group_invite(member_principal, group_id, false).await
+    metered("decline_user_request_group_invite", async {
+        has_access().await?;
+        GroupCalls::accept_or_decline_user_request_group_invite(member_principal, group_id, false)
+            .await
+    })
+    .await
 }
 
 /// Accept an invite from a group as a user - [`[update]`](update)
@@ -364,8 +465,11 @@ pub async fn decline_user_request_group_invite(
 /// * `ApiError` - If something went wrong while accepting the invite
 #[update(guard = "is_not_anonymous")]
 pub async fn accept_owner_request_group_invite(group_id: u64) -> CanisterResult<()> {
-    has_access().await?;
-    GroupCalls::accept_or_decline_owner_request_group_invite(group_id, true).await
+    metered("accept_owner_request_group_invite", async {
+        has_access().await?;
+        GroupCalls::accept_or_decline_owner_request_group_invite(group_id, true).await
+    })
+    .await
 }
 
 /// Decline an invite from a group as a user - [`[update]`](update)
@@ -377,8 +481,11 @@ pub async fn accept_owner_request_group_invite(group_id: u64) -> CanisterResult<
 /// * `ApiError` - If something went wrong while declining the invite
 #[update(guard = "is_not_anonymous")]
 pub async fn decline_owner_request_group_invite(group_id: u64) -> CanisterResult<()> {
-    has_access().await?;
-    GroupCalls::accept_or_decline_owner_request_group_invite(group_id, false).await
+    metered("decline_owner_request_group_invite", async {
+        has_access().await?;
+        GroupCalls::accept_or_decline_owner_request_group_invite(group_id, false).await
+    })
+    .await
 }
 
 /// Assign a role to a specific group member - [`[update]`](update)
@@ -392,15 +499,44 @@ pub async fn decline_owner_request_group_invite(group_id: u64) -> CanisterResult
 /// * `ApiError` - If something went wrong while assigning the role
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
+#[group_permission(Group, Edit)]
 #[update(guard = "is_not_anonymous")]
 pub async fn assign_role(
     group_id: u64,
     role: String,
     member_principal: Principal,
 ) -> CanisterResult<()> {
-    has_access().await?;
-    can_edit(group_id, PermissionType::Group(None)).await?;
-    GroupCalls::add_group_role_to_member(role, member_principal, group_id).await
+    metered("assign_role", async {
+        has_access().await?;
+        GroupCalls::add_group_role_to_member(role, member_principal, group_id).await
+    })
+    .await
+}
+
+/// Assign a role to multiple group members - [`[update]`](update)
+/// # Arguments
+/// * `group_id` - The identifier of the group
+/// * `role` - The role to assign
+/// * `member_principals` - The principals of the group members
+/// # Returns
+/// * `Vec<(Principal, CanisterResult<()>)>` - The result per member
+/// # Errors
+/// * `ApiError` - If the caller is not allowed to assign roles or the role does not exist
+/// # Note
+/// This function is guarded by the [`has_access`](has_access) function.
+/// At most `MEMBER_BATCH_LIMIT` principals can be passed at once.
+#[group_permission(Group, Edit)]
+#[update(guard = "is_not_anonymous")]
+pub async fn assign_role_to_many(
+    group_id: u64,
+    role: String,
+    member_principals: Vec<Principal>,
+) -> CanisterResult<Vec<(Principal, CanisterResult<()>)>> {
+    metered("assign_role_to_many", async {
+        has_access().await?;
+        GroupCalls::add_group_role_to_many_members(role, member_principals, group_id).await
+    })
+    .await
 }
 
 /// Remove a role from a specific group member - [`[update]`](update)
@@ -417,15 +553,18 @@ pub async fn assign_role(
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
 /// TODO: This action is guarded by group role based authorization
+#[group_permission(Group, Edit)]
 #[update(guard = "is_not_anonymous")]
 pub async fn remove_member_role(
     group_id: u64,
     role: String,
     member_principal: Principal,
 ) -> CanisterResult<()> {
-    has_access().await?;
-    can_edit(group_id, PermissionType::Group(None)).await?;
-    GroupCalls::remove_group_role_from_member(role, member_principal, group_id).await
+    metered("remove_member_role", async {
+        has_access().await?;
+        GroupCalls::remove_group_role_from_member(role, member_principal, group_id).await
+    })
+    .await
 }
 
 /// Get the member entry of a specific group member - [`[query]`](query)
@@ -477,6 +616,31 @@ pub async fn get_group_members(group_id: u64) -> CanisterResult<Vec<JoinedMember
     GroupCalls::get_group_members(group_id).await
 }
 
+/// Get a page of the group members for a specific group - [`[query]`](query)
+/// # Arguments
+/// * `group_id` - The identifier of the group
+/// * `limit` - The maximum number of members to return per page
+/// * `page` - The page number
+/// * `filters` - The filters to apply, a member has to match all of them
+/// * `sort` - The sort field and direction
+/// # Returns
+/// * `PagedResponse<JoinedMemberResponse>` - The group members
+/// # Errors
+/// * `ApiError` - If something went wrong while getting the group members
+/// # Note
+/// This function is guarded by the [`has_access`](has_access) function.
+#[query(composite = true, guard = "is_not_anonymous")]
+pub async fn get_group_members_paged(
+    group_id: u64,
+    limit: usize,
+    page: usize,
+    filters: Vec<GroupMemberFilter>,
+    sort: GroupMemberSort,
+) -> CanisterResult<PagedResponse<JoinedMemberResponse>> {
+    has_access().await?;
+    GroupCalls::get_group_members_paged(group_id, limit, page, filters, sort).await
+}
+
 /// Get the group members for a specific group - [`[query]`](query)
 /// # Arguments
 /// * `group_id` - The identifier of the group
@@ -537,8 +701,11 @@ pub async fn get_member_roles(
 /// This function is guarded by the [`has_access`](has_access) function.
 #[update(guard = "is_not_anonymous")]
 pub async fn leave_group(group_id: u64) -> CanisterResult<()> {
-    has_access().await?;
-    GroupCalls::leave_group(group_id).await
+    metered("leave_group", async {
+        has_access().await?;
+        GroupCalls::leave_group(group_id).await
+    })
+    .await
 }
 
 /// Remove an invite for a group as a user
@@ -552,8 +719,11 @@ pub async fn leave_group(group_id: u64) -> CanisterResult<()> {
 /// This function is guarded by the [`has_access`](has_access) function.
 #[update(guard = "is_not_anonymous")]
 pub async fn remove_invite(group_id: u64) -> CanisterResult<()> {
-    has_access().await?;
-    GroupCalls::remove_invite(group_id).await
+    metered("remove_invite", async {
+        has_access().await?;
+        GroupCalls::remove_invite(group_id).await
+    })
+    .await
 }
 
 /// Remove a member from a group
@@ -567,11 +737,38 @@ pub async fn remove_invite(group_id: u64) -> CanisterResult<()> {
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
 /// TODO: This action is guarded by group role based authorization
+#[group_permission(Member, Delete)]
 #[update(guard = "is_not_anonymous")]
 pub async fn remove_member_from_group(group_id: u64, principal: Principal) -> CanisterResult<()> {
-    has_access().await?;
-    can_delete(group_id, PermissionType::Member(None)).await?;
-    GroupCalls::remove_member_from_group(principal, group_id).await
+    metered("remove_member_from_group", async {
+        has_access().await?;
+        GroupCalls::remove_member_from_group(principal, group_id).await
+    })
+    .await
+}
+
+/// Remove multiple members from a group - [`[update]`](update)
+/// # Arguments
+/// * `group_id` - The identifier of the group to remove the members from
+/// * `principals` - The principals of the members to remove
+/// # Returns
+/// * `Vec<(Principal, CanisterResult<()>)>` - The result per member
+/// # Errors
+/// * `ApiError` - If the caller is not allowed to remove members or something went wrong while storing the group
+/// # Note
+/// This function is guarded by the [`has_access`](has_access) function.
+/// At most `MEMBER_BATCH_LIMIT` principals can be passed at once, the owner can not be removed.
+#[group_permission(Member, Delete)]
+#[update(guard = "is_not_anonymous")]
+pub async fn remove_many_members_from_group(
+    group_id: u64,
+    principals: Vec<Principal>,
+) -> CanisterResult<Vec<(Principal, CanisterResult<()>)>> {
+    metered("remove_many_members_from_group", async {
+        has_access().await?;
+        GroupCalls::remove_many_members_from_group(principals, group_id).await
+    })
+    .await
 }
 
 /// Remove a member invite from a group as an admin
@@ -584,14 +781,17 @@ pub async fn remove_member_from_group(group_id: u64, principal: Principal) -> Ca
 /// * `ApiError` - If something went wrong while removing the invite
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
+#[group_permission(Invite, Delete)]
 #[update(guard = "is_not_anonymous")]
 pub async fn remove_member_invite_from_group(
     group_id: u64,
     principal: Principal,
 ) -> CanisterResult<()> {
-    has_access().await?;
-    can_delete(group_id, PermissionType::Invite(None)).await?;
-    GroupCalls::remove_member_invite_from_group(principal, group_id).await
+    metered("remove_member_invite_from_group", async {
+        has_access().await?;
+        GroupCalls::remove_member_invite_from_group(principal, group_id).await
+    })
+    .await
 }
 
 /// Get the group invites for a specific group - [`[query]`](query)
@@ -604,10 +804,10 @@ pub async fn remove_member_invite_from_group(
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
 /// TODO: This action is guarded by group role based authorization
+#[group_permission(Invite, Read)]
 #[query(composite = true, guard = "is_not_anonymous")]
 pub async fn get_group_invites(group_id: u64) -> CanisterResult<Vec<InviteMemberResponse>> {
     has_access().await?;
-    can_read(group_id, PermissionType::Invite(None)).await?;
     GroupCalls::get_group_invites(group_id).await
 }
 
@@ -621,36 +821,357 @@ pub async fn get_group_invites(group_id: u64) -> CanisterResult<Vec<InviteMember
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
 /// TODO: This action is guarded by group role based authorization
+#[group_permission(Invite, Read)]
 #[query(composite = true, guard = "is_not_anonymous")]
 pub async fn get_group_invites_with_profiles(
     group_id: u64,
 ) -> CanisterResult<Vec<(InviteMemberResponse, ProfileResponse)>> {
     has_access().await?;
-    can_read(group_id, PermissionType::Invite(None)).await?;
     GroupCalls::get_group_invites_with_profiles(group_id).await
 }
 
+#[group_permission(Member, Edit)]
 #[query(composite = true, guard = "is_not_anonymous")]
 pub async fn get_banned_group_members(group_id: u64) -> CanisterResult<Vec<Principal>> {
     has_access().await?;
-    can_edit(group_id, PermissionType::Member(None)).await?;
     Ok(GroupCalls::get_banned_group_members(group_id).await)
 }
 
+#[group_permission(Member, Edit)]
 #[update(guard = "is_not_anonymous")]
 pub async fn ban_group_member(group_id: u64, member_principal: Principal) -> CanisterResult<()> {
-    has_access().await?;
-    can_edit(group_id, PermissionType::Member(None)).await?;
-    GroupCalls::remove_member_from_group(member_principal, group_id).await?;
-    GroupCalls::add_special_member_to_group(group_id, member_principal, RelationType::Blocked).await
+    metered("ban_group_member", async {
+        has_access().await?;
+        GroupCalls::remove_member_from_group(member_principal, group_id).await?;
+        GroupCalls::add_special_member_to_group(group_id, member_principal, RelationType::Blocked)
+            .await
+    })
+    .await
 }
 
+#[group_permission(Member, Edit)]
 #[update(guard = "is_not_anonymous")]
 pub async fn remove_ban_from_group_member(
     group_id: u64,
     member_principal: Principal,
 ) -> CanisterResult<()> {
+    metered("remove_ban_from_group_member", async {
+        has_access().await?;
+        GroupCalls::remove_special_member_from_group(group_id, member_principal).await
+    })
+    .await
+}
+
+/// Propose a member as the new owner of a group - [`[update]`](update)
+/// # Arguments
+/// * `group_id` - The identifier of the group to transfer
+/// * `new_owner` - The principal of the member that should become the owner
+/// # Returns
+/// * `OwnershipTransfer` - The pending transfer
+/// # Errors
+/// * `ApiError` - If the caller is not the owner or the new owner is not a member of the group
+/// # Note
+/// This function is guarded by the [`has_access`](has_access) function.
+/// The ownership only changes when the new owner accepts the transfer, a new proposal replaces the pending one.
+#[update(guard = "is_not_anonymous")]
+pub async fn transfer_group_ownership(
+    group_id: u64,
+    new_owner: Principal,
+) -> CanisterResult<OwnershipTransfer> {
+    metered("transfer_group_ownership", async {
+        has_access().await?;
+        GroupCalls::transfer_group_ownership(group_id, new_owner).await
+    })
+    .await
+}
+
+/// Accept a pending ownership transfer of a group - [`[update]`](update)
+/// # Arguments
+/// * `group_id` - The identifier of the group to accept the ownership of
+/// # Returns
+/// * `GroupResponse` - The group with the new owner
+/// # Errors
+/// * `ApiError` - If there is no transfer for the caller or the caller would exceed the group limit
+/// # Note
+/// This function is guarded by the [`has_access`](has_access) function.
+/// The previous owner keeps access to the group with the admin role.
+#[update(guard = "is_not_anonymous")]
+pub async fn accept_group_ownership_transfer(group_id: u64) -> CanisterResult<GroupResponse> {
+    metered("accept_group_ownership_transfer", async {
+        has_access().await?;
+        GroupCalls::accept_group_ownership_transfer(group_id).await
+    })
+    .await
+}
+
+/// Decline or cancel a pending ownership transfer of a group - [`[update]`](update)
+/// # Arguments
+/// * `group_id` - The identifier of the group to decline the transfer for
+/// # Returns
+/// * `()` - Empty tuple
+/// # Errors
+/// * `ApiError` - If there is no transfer or the caller is not part of it
+/// # Note
+/// This function is guarded by the [`has_access`](has_access) function.
+#[update(guard = "is_not_anonymous")]
+pub async fn decline_group_ownership_transfer(group_id: u64) -> CanisterResult<()> {
+    metered("decline_group_ownership_transfer", async {
+        has_access().await?;
+        GroupCalls::decline_group_ownership_transfer(group_id)
+    })
+    .await
+}
+
+/// Get the pending ownership transfer of a group - [`[query]`](query)
+/// # Arguments
+/// * `group_id` - The identifier of the group
+/// # Returns
+/// * `OwnershipTransfer` - The pending transfer
+/// # Errors
+/// * `ApiError` - If there is no pending transfer for the group
+#[query(guard = "is_not_anonymous")]
+pub fn get_group_ownership_transfer(group_id: u64) -> CanisterResult<OwnershipTransfer> {
+    GroupCalls::get_group_ownership_transfer(group_id)
+}
+
+/// Get the ownership transfers that are waiting for the caller to accept them - [`[query]`](query)
+/// # Returns
+/// * `Vec<OwnershipTransfer>` - The pending transfers addressed to the caller
+#[query(guard = "is_not_anonymous")]
+pub fn get_self_ownership_transfers() -> Vec<OwnershipTransfer> {
+    GroupCalls::get_ownership_transfers_for(caller())
+}
+
+/// Create a shareable invite code for a group - [`[update]`](update)
+/// # Arguments
+/// * `group_id` - The identifier of the group
+/// * `post_invite_code` - The expiry, maximum number of uses and role of the code
+/// # Returns
+/// * `InviteCode` - The created invite code
+/// # Errors
+/// * `ApiError` - If the role does not exist or is the owner role
+/// # Note
+/// This function is guarded by the [`has_access`](has_access) function.
+#[group_permission(Invite, Edit)]
+#[update(guard = "is_not_anonymous")]
+pub async fn create_invite_code(
+    group_id: u64,
+    post_invite_code: PostInviteCode,
+) -> CanisterResult<InviteCode> {
+    metered("create_invite_code", async {
+        has_access().await?;
+        GroupCalls::create_invite_code(group_id, post_invite_code).await
+    })
+    .await
+}
+
+/// Get the invite codes of a group - [`[query]`](query)
+/// # Arguments
+/// * `group_id` - The identifier of the group
+/// # Returns
+/// * `Vec<InviteCode>` - The invite codes of the group, including used up and expired codes
+/// # Errors
+/// * `ApiError` - If the caller is not allowed to invite members to the group
+/// # Note
+/// This function is guarded by the [`has_access`](has_access) function.
+#[group_permission(Invite, Edit)]
+#[query(composite = true, guard = "is_not_anonymous")]
+pub async fn get_invite_codes(group_id: u64) -> CanisterResult<Vec<InviteCode>> {
     has_access().await?;
-    can_edit(group_id, PermissionType::Member(None)).await?;
-    GroupCalls::remove_special_member_from_group(group_id, member_principal).await
+    Ok(GroupCalls::get_invite_codes(group_id))
+}
+
+/// Revoke an invite code of a group - [`[update]`](update)
+/// # Arguments
+/// * `group_id` - The identifier of the group
+/// * `code` - The invite code to revoke
+/// # Returns
+/// * `()` - If the code was revoked
+/// # Errors
+/// * `ApiError` - If the code does not belong to the group
+/// # Note
+/// This function is guarded by the [`has_access`](has_access) function.
+#[group_permission(Invite, Edit)]
+#[update(guard = "is_not_anonymous")]
+pub async fn revoke_invite_code(group_id: u64, code: String) -> CanisterResult<()> {
+    metered("revoke_invite_code", async {
+        has_access().await?;
+        GroupCalls::revoke_invite_code(group_id, code)
+    })
+    .await
+}
+
+/// Join a group with an invite code - [`[update]`](update)
+/// # Arguments
+/// * `code` - The invite code
+/// # Returns
+/// * `JoinGroupResult` - `Joined` with the member entry with the role of the code,
+/// `Waitlisted` when the group is full and the caller is put on its waitlist
+/// # Errors
+/// * `ApiError` - If the code is expired, used up or revoked
+/// # Note
+/// This function is guarded by the [`has_access`](has_access) function.
+/// The privacy of the group is not checked, the code is the invite.
+#[update(guard = "is_not_anonymous")]
+pub async fn join_group_with_code(code: String) -> CanisterResult<JoinGroupResult> {
+    metered("join_group_with_code", async {
+        has_access().await?;
+        GroupCalls::join_group_with_code(code).await
+    })
+    .await
+}
+
+/// Get the members a gated group lost because they no longer met the gate - [`[query]`](query)
+/// # Arguments
+/// * `group_id` - The identifier of the group
+/// # Returns
+/// * `Vec<GatedRemovalReport>` - The removed members per re-validation run
+/// # Errors
+/// * `ApiError` - If the caller is not allowed to manage the members of the group
+/// # Note
+/// This function is guarded by the [`has_access`](has_access) function.
+/// EXT tokens are only checked on join, the re-validation has no account identifier of the member
+/// and counts them as held, so members are never removed for an EXT token they no longer hold.
+#[group_permission(Member, Edit)]
+#[query(composite = true, guard = "is_not_anonymous")]
+pub async fn get_gated_removal_reports(group_id: u64) -> CanisterResult<Vec<GatedRemovalReport>> {
+    has_access().await?;
+    Ok(GroupCalls::get_gated_removal_reports(group_id))
+}
+
+/// Get the gated groups the caller no longer meets the gate of - [`[query]`](query)
+/// # Returns
+/// * `Vec<GatedGracePeriod>` - The grace periods after which the caller is removed
+#[query(guard = "is_not_anonymous")]
+pub fn get_self_gated_grace_periods() -> Vec<GatedGracePeriod> {
+    GroupCalls::get_gated_grace_periods_for(caller())
+}
+
+/// Get the gating rule a caller has to meet to join a gated group - [`[query]`](query)
+/// # Arguments
+/// * `group_id` - The identifier of the group
+/// # Returns
+/// * `Option<GatingRule>` - The gating rule, `None` when the group is not gated
+/// # Errors
+/// * `ApiError` - If the group does not exist
+#[query(composite = true)]
+pub async fn get_group_gating_rule(group_id: u64) -> CanisterResult<Option<GatingRule>> {
+    GroupCalls::get_group_gating_rule(group_id).await
+}
+
+/// Set the gating rule of a gated group - [`[update]`](update)
+/// # Arguments
+/// * `group_id` - The identifier of the group
+/// * `gating_rule` - The rule, `None` to require `privacy_gated_type_amount` of the canisters of the gated type
+/// # Returns
+/// * `Option<GatingRule>` - The gating rule of the group
+/// # Errors
+/// * `ApiError` - If the group is not gated or the rule is empty or too large
+/// # Note
+/// This function is guarded by the [`has_access`](has_access) function.
+/// Members that don't meet a stricter rule are removed by the gated re-validation after a grace period.
+/// EXT tokens are only checked on join, the re-validation counts them as held.
+#[group_permission(Group, Edit)]
+#[update(guard = "is_not_anonymous")]
+pub async fn set_group_gating_rule(
+    group_id: u64,
+    gating_rule: Option<GatingRule>,
+) -> CanisterResult<Option<GatingRule>> {
+    metered("set_group_gating_rule", async {
+        has_access().await?;
+        GroupCalls::set_group_gating_rule(group_id, gating_rule).await
+    })
+    .await
+}
+
+/// Check if the caller meets the gating rule of a gated group without joining it - [`[update]`](update)
+/// # Arguments
+/// * `group_id` - The identifier of the group
+/// * `account_identifier` - Optional account identifier needed for EXT tokens
+/// # Returns
+/// * `GroupEligibility` - The required and observed amount of every token and neuron condition
+/// # Errors
+/// * `ApiError` - If the group is not gated
+/// # Note
+/// This function is guarded by the [`has_access`](has_access) function.
+/// This is an update call as the token and governance canisters can live on other subnets,
+/// which composite queries can't call.
+#[update(guard = "is_not_anonymous")]
+pub async fn check_group_eligibility(
+    group_id: u64,
+    account_identifier: Option<String>,
+) -> CanisterResult<GroupEligibility> {
+    metered("check_group_eligibility", async {
+        has_access().await?;
+        GroupCalls::check_group_eligibility(group_id, account_identifier).await
+    })
+    .await
+}
+
+/// Set the maximum number of members of a group - [`[update]`](update)
+/// # Arguments
+/// * `group_id` - The identifier of the group
+/// * `max_members` - The member cap, `None` to remove it
+/// # Returns
+/// * `Option<u64>` - The member cap of the group
+/// # Errors
+/// * `ApiError` - If the cap is 0 or the caller is not allowed to edit the group
+/// # Note
+/// This function is guarded by the [`has_access`](has_access) function.
+/// Existing members are kept when the cap is lowered, a raised or removed cap admits the waitlist.
+#[group_permission(Group, Edit)]
+#[update(guard = "is_not_anonymous")]
+pub async fn set_group_member_cap(
+    group_id: u64,
+    max_members: Option<u64>,
+) -> CanisterResult<Option<u64>> {
+    metered("set_group_member_cap", async {
+        has_access().await?;
+        GroupCalls::set_group_member_cap(group_id, max_members).await
+    })
+    .await
+}
+
+/// Get the maximum number of members of a group - [`[query]`](query)
+/// # Arguments
+/// * `group_id` - The identifier of the group
+/// # Returns
+/// * `Option<u64>` - The member cap, `None` when the group has no cap
+#[query]
+pub fn get_group_member_cap(group_id: u64) -> Option<u64> {
+    GroupCalls::get_group_member_cap(group_id)
+}
+
+/// Get the waitlist of a full group in the order it is admitted - [`[query]`](query)
+/// # Arguments
+/// * `group_id` - The identifier of the group
+/// # Returns
+/// * `Vec<WaitlistEntry>` - The principals waiting for a spot
+/// # Errors
+/// * `ApiError` - If the caller is not allowed to read the members of the group
+/// # Note
+/// This function is guarded by the [`has_access`](has_access) function.
+#[group_permission(Member, Read)]
+#[query(composite = true, guard = "is_not_anonymous")]
+pub async fn get_group_waitlist(group_id: u64) -> CanisterResult<Vec<WaitlistEntry>> {
+    has_access().await?;
+    Ok(GroupCalls::get_group_waitlist(group_id))
+}
+
+/// Leave the waitlist of a group as a caller - [`[update]`](update)
+/// # Arguments
+/// * `group_id` - The identifier of the group
+/// # Returns
+/// * `()` - Empty tuple
+/// # Errors
+/// * `ApiError` - If the caller is not on the waitlist
+/// # Note
+/// This function is guarded by the [`has_access`](has_access) function.
+#[update(guard = "is_not_anonymous")]
+pub async fn leave_group_waitlist(group_id: u64) -> CanisterResult<()> {
+    metered("leave_group_waitlist", async {
+        has_access().await?;
+        GroupCalls::leave_group_waitlist(group_id)
+    })
+    .await
 }
@@ -0,0 +1,18 @@
+use ic_cdk::update;
+
+use crate::{
+    helpers::guards::is_developer, logic::health_logic::HealthCalls, models::HealthReport,
+};
+
+/// Checks the proxy and every canister in its topology - [`[update]`](update)
+/// # Returns
+/// * `HealthReport` - Per canister if it is configured and reachable, its `icts_version`,
+/// latency and item count, together with the cycle balance and version of the proxy
+/// # Note
+/// This function is guarded by the [`is_developer`](is_developer) function.
+/// This is an update call as the canister time does not advance within a composite query,
+/// the latency is measured in rounds of the subnet so it is a rough indication.
+#[update(guard = "is_developer")]
+async fn _dev_health() -> HealthReport {
+    HealthCalls::get_health().await
+}
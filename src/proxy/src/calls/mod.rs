@@ -1,13 +1,17 @@
+pub mod admin_calls;
 pub mod boost_calls;
 pub mod canister_calls;
+pub mod config_calls;
 pub mod event_calls;
 pub mod generic_calls;
 pub mod group_calls;
+pub mod health_calls;
 pub mod history_calls;
 pub mod notification_calls;
 pub mod profile_calls;
 pub mod report_calls;
 pub mod reward_calls;
+pub mod saga_calls;
 pub mod stats_calls;
 pub mod topic_calls;
 pub mod websocket_calls;
@@ -7,7 +7,11 @@ use catalyze_shared::{
 };
 use ic_cdk::{caller, query, update};
 
-use crate::{logic::notification_logic::NotificationCalls, storage::transaction_handler_canister};
+use crate::{
+    helpers::metrics::{metered, metered_by},
+    logic::notification_logic::NotificationCalls,
+    storage::transaction_handler_canister,
+};
 
 #[query(composite = true)]
 async fn get_notifications() -> Vec<NotificationResponse> {
@@ -24,43 +28,70 @@ async fn mark_notifications_as_read(
     ids: Vec<u64>,
     is_read: bool,
 ) -> CanisterResult<Vec<(u64, UserNotificationData)>> {
-    NotificationCalls::mark_notifications_as_read(caller(), ids, is_read).await
+    metered("mark_notifications_as_read", async {
+        NotificationCalls::mark_notifications_as_read(caller(), ids, is_read).await
+    })
+    .await
 }
 
 #[update]
 async fn remove_notifications(ids: Vec<u64>) -> Vec<(u64, UserNotificationData)> {
-    NotificationCalls::remove_user_notifications(caller(), ids).await
+    metered_by(
+        "remove_notifications",
+        NotificationCalls::remove_user_notifications(caller(), ids),
+        |_| false,
+    )
+    .await
 }
 
 #[update]
 async fn remove_all_notifications() -> Vec<(u64, UserNotificationData)> {
-    NotificationCalls::remove_all_user_notifications(caller()).await
+    metered_by(
+        "remove_all_notifications",
+        NotificationCalls::remove_all_user_notifications(caller()),
+        |_| false,
+    )
+    .await
 }
 
 #[update]
 async fn add_transaction_notification(transaction: TransactionData) -> bool {
-    if let Ok(principal) = transaction_handler_canister().get() {
-        if caller() != principal {
-            return false;
-        }
+    metered_by(
+        "add_transaction_notification",
+        async {
+            if let Ok(principal) = transaction_handler_canister().get() {
+                if caller() != principal {
+                    return false;
+                }
 
-        return NotificationCalls::notification_add_transaction(transaction).await;
-    }
+                return NotificationCalls::notification_add_transaction(transaction).await;
+            }
 
-    false
+            false
+        },
+        |sent| !sent,
+    )
+    .await
 }
 
 #[update]
 async fn add_transactions_complete_notification(data: TransactionCompleteData) -> bool {
-    if let Ok(principal) = transaction_handler_canister().get() {
-        if caller() != principal {
-            return false;
-        }
+    metered_by(
+        "add_transactions_complete_notification",
+        async {
+            if let Ok(principal) = transaction_handler_canister().get() {
+                if caller() != principal {
+                    return false;
+                }
 
-        return NotificationCalls::notification_add_complete_transaction(data).await;
-    }
+                return NotificationCalls::notification_add_complete_transaction(data).await;
+            }
 
-    false
+            false
+        },
+        |sent| !sent,
+    )
+    .await
 }
 
 #[update]
@@ -69,9 +100,16 @@ pub async fn multisig_whitelist_notice_notification(
     multisig_wallet_canister: Principal,
     group_id: u64,
 ) -> bool {
-    NotificationCalls::notification_add_multisig(
-        receivers,
-        MultisigNotificationType::WhitelistNotice((multisig_wallet_canister, group_id)),
+    metered_by(
+        "multisig_whitelist_notice_notification",
+        async {
+            NotificationCalls::notification_add_multisig(
+                receivers,
+                MultisigNotificationType::WhitelistNotice((multisig_wallet_canister, group_id)),
+            )
+            .await
+        },
+        |sent| !sent,
     )
     .await
 }
@@ -83,9 +121,20 @@ pub async fn multisig_proposal_accept_notification(
     proposal_id: u64,
     group_id: u64,
 ) -> bool {
-    NotificationCalls::notification_add_multisig(
-        receivers,
-        MultisigNotificationType::ProposalAccept((multisig_wallet_canister, proposal_id, group_id)),
+    metered_by(
+        "multisig_proposal_accept_notification",
+        async {
+            NotificationCalls::notification_add_multisig(
+                receivers,
+                MultisigNotificationType::ProposalAccept((
+                    multisig_wallet_canister,
+                    proposal_id,
+                    group_id,
+                )),
+            )
+            .await
+        },
+        |sent| !sent,
     )
     .await
 }
@@ -97,13 +146,20 @@ pub async fn multisig_proposal_decline_notification(
     proposal_id: u64,
     group_id: u64,
 ) -> bool {
-    NotificationCalls::notification_add_multisig(
-        receivers,
-        MultisigNotificationType::ProposalDecline((
-            multisig_wallet_canister,
-            proposal_id,
-            group_id,
-        )),
+    metered_by(
+        "multisig_proposal_decline_notification",
+        async {
+            NotificationCalls::notification_add_multisig(
+                receivers,
+                MultisigNotificationType::ProposalDecline((
+                    multisig_wallet_canister,
+                    proposal_id,
+                    group_id,
+                )),
+            )
+            .await
+        },
+        |sent| !sent,
     )
     .await
 }
@@ -114,13 +170,20 @@ pub async fn multisig_proposal_status_update_notification(
     proposal_id: u64,
     group_id: u64,
 ) -> bool {
-    NotificationCalls::notification_add_multisig_silent(
-        receivers,
-        MultisigNotificationType::ProposalStatusUpdate((
-            multisig_wallet_canister,
-            proposal_id,
-            group_id,
-        )),
+    metered_by(
+        "multisig_proposal_status_update_notification",
+        async {
+            NotificationCalls::notification_add_multisig_silent(
+                receivers,
+                MultisigNotificationType::ProposalStatusUpdate((
+                    multisig_wallet_canister,
+                    proposal_id,
+                    group_id,
+                )),
+            )
+            .await
+        },
+        |sent| !sent,
     )
     .await
 }
@@ -131,9 +194,20 @@ pub async fn multisig_new_proposal_notification(
     proposal_id: u64,
     group_id: u64,
 ) -> bool {
-    NotificationCalls::notification_add_multisig(
-        receivers,
-        MultisigNotificationType::NewProposal((multisig_wallet_canister, proposal_id, group_id)),
+    metered_by(
+        "multisig_new_proposal_notification",
+        async {
+            NotificationCalls::notification_add_multisig(
+                receivers,
+                MultisigNotificationType::NewProposal((
+                    multisig_wallet_canister,
+                    proposal_id,
+                    group_id,
+                )),
+            )
+            .await
+        },
+        |sent| !sent,
     )
     .await
 }
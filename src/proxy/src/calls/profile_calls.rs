@@ -1,5 +1,8 @@
 use crate::{
-    helpers::guards::has_access,
+    helpers::{
+        guards::{has_access, is_not_anonymous},
+        metrics::metered,
+    },
     logic::{friend_request_logic::FriendRequestCalls, profile_logic::ProfileCalls},
 };
 /// # Profile methods
@@ -13,7 +16,6 @@ use crate::{
 use candid::Principal;
 use catalyze_shared::{
     friend_request::FriendRequestResponse,
-    helpers::guards::is_not_anonymous,
     profile_with_refs::{PostProfile, ProfileResponse, UpdateProfile},
     relation_type::RelationType,
     subject::{Subject, SubjectResponse, SubjectType},
@@ -33,7 +35,10 @@ use ic_cdk::{caller, query, update};
 /// This function is guarded by the [`is_not_anonymous`](is_not_anonymous) function.
 #[update(guard = "is_not_anonymous")]
 pub async fn add_profile(post_profile: PostProfile) -> CanisterResult<ProfileResponse> {
-    ProfileCalls::add_profile(post_profile).await
+    metered("add_profile", async {
+        ProfileCalls::add_profile(post_profile).await
+    })
+    .await
 }
 
 /// Gets a profile by the given user principal - [`[query]`](query)
@@ -77,8 +82,11 @@ pub async fn get_profiles(principals: Vec<Principal>) -> CanisterResult<Vec<Prof
 /// This function is guarded by the [`has_access`](has_access) function.
 #[update(guard = "is_not_anonymous")]
 pub async fn edit_profile(update_profile: UpdateProfile) -> CanisterResult<ProfileResponse> {
-    has_access().await?;
-    ProfileCalls::update_profile(update_profile).await
+    metered("edit_profile", async {
+        has_access().await?;
+        ProfileCalls::update_profile(update_profile).await
+    })
+    .await
 }
 
 /// Adds a wallet to the caller his profile - [`[update]`](update)
@@ -94,8 +102,11 @@ pub async fn edit_profile(update_profile: UpdateProfile) -> CanisterResult<Profi
 /// This function is guarded by the [`has_access`](has_access) function.
 #[update(guard = "is_not_anonymous")]
 pub async fn add_wallet_to_profile(wallet: PostWallet) -> CanisterResult<ProfileResponse> {
-    has_access().await?;
-    ProfileCalls::add_wallet_to_profile(wallet).await
+    metered("add_wallet_to_profile", async {
+        has_access().await?;
+        ProfileCalls::add_wallet_to_profile(wallet).await
+    })
+    .await
 }
 
 /// Sets a wallet as the primary wallet of the caller his profile - [`[update]`](update)
@@ -109,8 +120,43 @@ pub async fn add_wallet_to_profile(wallet: PostWallet) -> CanisterResult<Profile
 /// This function is guarded by the [`has_access`](has_access) function.
 #[update(guard = "is_not_anonymous")]
 pub async fn set_wallet_as_primary(wallet_principal: Principal) -> CanisterResult<ProfileResponse> {
+    metered("set_wallet_as_primary", async {
+        has_access().await?;
+        ProfileCalls::set_wallet_as_primary(wallet_principal).await
+    })
+    .await
+}
+
+/// Sets the ICRC subaccounts of the caller that count towards token gated groups - [`[update]`](update)
+/// # Arguments
+/// * `subaccounts` - The 32 byte subaccounts, replaces the registered ones and an empty list removes them
+/// # Returns
+/// * `Vec<Vec<u8>>` - The registered subaccounts
+/// # Errors
+/// * `ApiError` - If there are too many subaccounts or one is not 32 bytes
+/// # Note
+/// This function is guarded by the [`has_access`](has_access) function.
+/// ICRC-1 balances and ICRC-7 NFTs are counted on these subaccounts of the caller next to its default subaccount
+#[update(guard = "is_not_anonymous")]
+pub async fn set_token_subaccounts(subaccounts: Vec<Vec<u8>>) -> CanisterResult<Vec<Vec<u8>>> {
+    metered("set_token_subaccounts", async {
+        has_access().await?;
+        ProfileCalls::set_token_subaccounts(subaccounts)
+    })
+    .await
+}
+
+/// Gets the ICRC subaccounts of the caller that count towards token gated groups - [`[query]`](query)
+/// # Returns
+/// * `Vec<Vec<u8>>` - The registered subaccounts
+/// # Errors
+/// * `ApiError` - If something went wrong while getting the subaccounts
+/// # Note
+/// This function is guarded by the [`has_access`](has_access) function.
+#[query(composite = true, guard = "is_not_anonymous")]
+pub async fn get_self_token_subaccounts() -> CanisterResult<Vec<Vec<u8>>> {
     has_access().await?;
-    ProfileCalls::set_wallet_as_primary(wallet_principal).await
+    Ok(ProfileCalls::get_token_subaccounts(caller()))
 }
 
 /// Removes a wallet from the caller his profile - [`[update]`](update)
@@ -128,8 +174,11 @@ pub async fn set_wallet_as_primary(wallet_principal: Principal) -> CanisterResul
 pub async fn remove_wallet_from_profile(
     wallet_principal: Principal,
 ) -> CanisterResult<ProfileResponse> {
-    has_access().await?;
-    ProfileCalls::remove_wallet_from_profile(wallet_principal).await
+    metered("remove_wallet_from_profile", async {
+        has_access().await?;
+        ProfileCalls::remove_wallet_from_profile(wallet_principal).await
+    })
+    .await
 }
 
 /// Adds a starred subject to the caller his profile - [`[update]`](update)
@@ -143,8 +192,11 @@ pub async fn remove_wallet_from_profile(
 /// This function is guarded by the [`has_access`](has_access) function.
 #[update(guard = "is_not_anonymous")]
 pub async fn add_starred(subject: Subject) -> CanisterResult<ProfileResponse> {
-    has_access().await?;
-    ProfileCalls::add_starred(subject).await
+    metered("add_starred", async {
+        has_access().await?;
+        ProfileCalls::add_starred(subject).await
+    })
+    .await
 }
 
 /// Removes a starred subject from the caller his profile - [`[update]`](update)
@@ -158,8 +210,11 @@ pub async fn add_starred(subject: Subject) -> CanisterResult<ProfileResponse> {
 /// This function is guarded by the [`has_access`](has_access) function.
 #[update(guard = "is_not_anonymous")]
 pub async fn remove_starred(subject: Subject) -> CanisterResult<ProfileResponse> {
-    has_access().await?;
-    ProfileCalls::remove_starred(subject).await
+    metered("remove_starred", async {
+        has_access().await?;
+        ProfileCalls::remove_starred(subject).await
+    })
+    .await
 }
 
 /// Gets the starred subjects from the caller his profile - [`[query]`](query)
@@ -186,8 +241,11 @@ pub async fn get_starred_by_subject_type(subject_type: SubjectType) -> CanisterR
 /// This function is guarded by the [`has_access`](has_access) function.
 #[update(guard = "is_not_anonymous")]
 pub async fn add_pinned(subject: Subject) -> CanisterResult<ProfileResponse> {
-    has_access().await?;
-    ProfileCalls::add_pinned(subject).await
+    metered("add_pinned", async {
+        has_access().await?;
+        ProfileCalls::add_pinned(subject).await
+    })
+    .await
 }
 
 /// Removes a pinned subject from the caller his profile - [`[update]`](update)
@@ -201,8 +259,11 @@ pub async fn add_pinned(subject: Subject) -> CanisterResult<ProfileResponse> {
 /// This function is guarded by the [`has_access`](has_access) function.
 #[update(guard = "is_not_anonymous")]
 pub async fn remove_pinned(subject: Subject) -> CanisterResult<ProfileResponse> {
-    has_access().await?;
-    ProfileCalls::remove_pinned(subject).await
+    metered("remove_pinned", async {
+        has_access().await?;
+        ProfileCalls::remove_pinned(subject).await
+    })
+    .await
 }
 
 /// Gets the pinned subjects from the caller his profile - [`[query]`](query)
@@ -237,8 +298,11 @@ pub async fn add_friend_request(
     to: Principal,
     message: String,
 ) -> CanisterResult<FriendRequestResponse> {
-    has_access().await?;
-    FriendRequestCalls::add_friend_request(to, message).await
+    metered("add_friend_request", async {
+        has_access().await?;
+        FriendRequestCalls::add_friend_request(to, message).await
+    })
+    .await
 }
 
 /// Accept a friend request that is addressed to the caller - [`[update]`](update)
@@ -252,8 +316,11 @@ pub async fn add_friend_request(
 /// This function is guarded by the [`has_access`](has_access) function.
 #[update(guard = "is_not_anonymous")]
 pub async fn accept_friend_request(friend_request_id: u64) -> CanisterResult<bool> {
-    has_access().await?;
-    FriendRequestCalls::accept_friend_request(friend_request_id).await
+    metered("accept_friend_request", async {
+        has_access().await?;
+        FriendRequestCalls::accept_friend_request(friend_request_id).await
+    })
+    .await
 }
 
 /// Remove a friend request created by the caller - [`[update]`](update)
@@ -267,8 +334,11 @@ pub async fn accept_friend_request(friend_request_id: u64) -> CanisterResult<boo
 /// This function is guarded by the [`has_access`](has_access) function.
 #[update(guard = "is_not_anonymous")]
 pub async fn remove_friend_request(friend_request_id: u64) -> CanisterResult<bool> {
-    has_access().await?;
-    FriendRequestCalls::remove_friend_request(friend_request_id).await
+    metered("remove_friend_request", async {
+        has_access().await?;
+        FriendRequestCalls::remove_friend_request(friend_request_id).await
+    })
+    .await
 }
 
 /// Gets the friend requests that are addressed to the caller - [`[query]`](query)
@@ -328,8 +398,11 @@ pub async fn get_outgoing_friend_requests_with_profile(
 /// This function is guarded by the [`has_access`](has_access) function.
 #[update(guard = "is_not_anonymous")]
 pub async fn decline_friend_request(friend_request_id: u64) -> CanisterResult<bool> {
-    has_access().await?;
-    FriendRequestCalls::decline_friend_request(friend_request_id).await
+    metered("decline_friend_request", async {
+        has_access().await?;
+        FriendRequestCalls::decline_friend_request(friend_request_id).await
+    })
+    .await
 }
 
 /// Remove friend from caller profile and remove caller from friend profile - [`[update]`](update)
@@ -343,8 +416,11 @@ pub async fn decline_friend_request(friend_request_id: u64) -> CanisterResult<bo
 /// This function is guarded by the [`has_access`](has_access) function.
 #[update(guard = "is_not_anonymous")]
 pub async fn remove_friend(principal: Principal) -> CanisterResult<ProfileResponse> {
-    has_access().await?;
-    ProfileCalls::remove_friend(principal).await
+    metered("remove_friend", async {
+        has_access().await?;
+        ProfileCalls::remove_friend(principal).await
+    })
+    .await
 }
 
 /// Block a user on the application level - [`[update]`](update)
@@ -359,8 +435,11 @@ pub async fn remove_friend(principal: Principal) -> CanisterResult<ProfileRespon
 /// TODO: Check full implementation for this
 #[update(guard = "is_not_anonymous")]
 pub async fn block_user(principal: Principal) -> CanisterResult<ProfileResponse> {
-    has_access().await?;
-    ProfileCalls::block_user(principal).await
+    metered("block_user", async {
+        has_access().await?;
+        ProfileCalls::block_user(principal).await
+    })
+    .await
 }
 
 /// Unblock a user on the application level - [`[update]`](update)
@@ -375,8 +454,11 @@ pub async fn block_user(principal: Principal) -> CanisterResult<ProfileResponse>
 /// TODO: Check full implementation for this
 #[update(guard = "is_not_anonymous")]
 pub async fn unblock_user(principal: Principal) -> CanisterResult<ProfileResponse> {
-    has_access().await?;
-    ProfileCalls::unblock_user(principal).await
+    metered("unblock_user", async {
+        has_access().await?;
+        ProfileCalls::unblock_user(principal).await
+    })
+    .await
 }
 
 /// Get the current relation for the caller based on the relation type - [`[query]`](query)
@@ -486,8 +568,11 @@ pub async fn get_relations_count_by_principal(
 /// This function is guarded by the [`has_access`](has_access) function.
 #[update(guard = "is_not_anonymous")]
 pub async fn approve_code_of_conduct(version: u64) -> CanisterResult<bool> {
-    has_access().await?;
-    ProfileCalls::approve_code_of_conduct(version).await
+    metered("approve_code_of_conduct", async {
+        has_access().await?;
+        ProfileCalls::approve_code_of_conduct(version).await
+    })
+    .await
 }
 
 /// Approve a privacy policy version - [`[update]`](update)
@@ -501,8 +586,11 @@ pub async fn approve_code_of_conduct(version: u64) -> CanisterResult<bool> {
 /// This function is guarded by the [`has_access`](has_access) function.
 #[update(guard = "is_not_anonymous")]
 pub async fn approve_privacy_policy(version: u64) -> CanisterResult<bool> {
-    has_access().await?;
-    ProfileCalls::approve_privacy_policy(version).await
+    metered("approve_privacy_policy", async {
+        has_access().await?;
+        ProfileCalls::approve_privacy_policy(version).await
+    })
+    .await
 }
 
 /// Approve a terms of service version - [`[update]`](update)
@@ -516,6 +604,9 @@ pub async fn approve_privacy_policy(version: u64) -> CanisterResult<bool> {
 /// This function is guarded by the [`has_access`](has_access) function.
 #[update(guard = "is_not_anonymous")]
 pub async fn approve_terms_of_service(version: u64) -> CanisterResult<bool> {
-    has_access().await?;
-    ProfileCalls::approve_terms_of_service(version).await
+    metered("approve_terms_of_service", async {
+        has_access().await?;
+        ProfileCalls::approve_terms_of_service(version).await
+    })
+    .await
 }
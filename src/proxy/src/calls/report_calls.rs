@@ -6,18 +6,17 @@
 /// Check the public / private access of these calls? (anon / registered / group role)
 ///
 use ic_cdk::{query, update};
+use proxy_macros::group_permission;
 
 use crate::{
     helpers::{
-        group_permission::{can_read, can_write},
-        guards::has_access,
+        guards::{has_access, is_not_anonymous},
+        metrics::metered,
     },
     logic::report_logic::ReportCalls,
 };
 use catalyze_shared::{
-    guards::is_not_anonymous,
     paged_response::PagedResponse,
-    permission::PermissionType,
     report::{PostReport, ReportFilter, ReportResponse, ReportSort},
     CanisterResult,
 };
@@ -33,8 +32,11 @@ use catalyze_shared::{
 /// This function is guarded by the [`has_access`](has_access) function.
 #[update(guard = "is_not_anonymous")]
 pub async fn add_report(post_report: PostReport) -> CanisterResult<ReportResponse> {
-    has_access().await?;
-    ReportCalls::add_report(post_report).await
+    metered("add_report", async {
+        has_access().await?;
+        ReportCalls::add_report(post_report).await
+    })
+    .await
 }
 
 /// Get a report
@@ -47,10 +49,10 @@ pub async fn add_report(post_report: PostReport) -> CanisterResult<ReportRespons
 /// * `ApiError` - If something went wrong while getting the report
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
+#[group_permission(Group, Write)]
 #[query(composite = true, guard = "is_not_anonymous")]
 pub async fn get_report(report_id: u64, group_id: u64) -> CanisterResult<ReportResponse> {
     has_access().await?;
-    can_write(group_id, PermissionType::Group(None)).await?;
     ReportCalls::get_report(report_id).await
 }
 
@@ -69,6 +71,7 @@ pub async fn get_report(report_id: u64, group_id: u64) -> CanisterResult<ReportR
 /// * `ApiError` - If something went wrong while getting the reports
 /// # Note
 /// This function is guarded by the [`has_access`](has_access) function.
+#[group_permission(Group, Read)]
 #[query(composite = true, guard = "is_not_anonymous")]
 pub async fn get_reports(
     limit: usize,
@@ -78,6 +81,5 @@ pub async fn get_reports(
     group_id: u64,
 ) -> CanisterResult<PagedResponse<ReportResponse>> {
     has_access().await?;
-    can_read(group_id, PermissionType::Group(None)).await?;
     ReportCalls::get_reports(limit, page, sort, filters, group_id).await
 }
@@ -0,0 +1,38 @@
+use catalyze_shared::CanisterResult;
+use ic_cdk::{query, update};
+
+use crate::{helpers::guards::is_developer, logic::saga_logic::SagaCalls, models::Saga};
+
+/// Returns the sagas that failed or stopped making progress - [`[query]`](query)
+/// # Returns
+/// * `Vec<(u64, Saga)>` - The stuck sagas with their ids
+/// # Note
+/// This function is guarded by the [`is_developer`](is_developer) function.
+#[query(guard = "is_developer")]
+fn _dev_get_stuck_sagas() -> Vec<(u64, Saga)> {
+    SagaCalls::get_stuck_sagas()
+}
+
+/// Executes the remaining steps of a stuck saga - [`[update]`](update)
+/// # Arguments
+/// * `id` - The id of the saga
+/// # Errors
+/// * `ApiError` - If the saga is not found, still in progress or a step fails again
+/// # Note
+/// This function is guarded by the [`is_developer`](is_developer) function.
+#[update(guard = "is_developer")]
+async fn _dev_retry_saga(id: u64) -> CanisterResult<()> {
+    SagaCalls::retry_saga(id).await
+}
+
+/// Compensates the completed steps of a stuck saga - [`[update]`](update)
+/// # Arguments
+/// * `id` - The id of the saga
+/// # Errors
+/// * `ApiError` - If the saga is not found, still in progress, contains steps that can't be compensated or a compensation fails
+/// # Note
+/// This function is guarded by the [`is_developer`](is_developer) function.
+#[update(guard = "is_developer")]
+async fn _dev_rollback_saga(id: u64) -> CanisterResult<()> {
+    SagaCalls::rollback_saga(id).await
+}
@@ -1,5 +1,8 @@
 use crate::helpers::guards::is_monitor;
-use crate::storage::*;
+use crate::storage::{
+    cache::{group_cache_stats, profile_cache_stats, CacheStats},
+    *,
+};
 use catalyze_shared::{CanisterResult, StorageClient};
 use ic_cdk::query;
 
@@ -14,5 +17,14 @@ async fn store_stats() -> CanisterResult<Vec<String>> {
         format!("BoostStore: {}", boosts().size().await?),
         format!("NotificationStore: {}", notifications().size().await?),
         format!("TopicStore: {}", topics().size().await?),
+        format_cache_stats("ProfileCache", profile_cache_stats()),
+        format_cache_stats("GroupCache", group_cache_stats()),
     ])
 }
+
+fn format_cache_stats(name: &str, stats: CacheStats) -> String {
+    format!(
+        "{}: {} hits, {} misses, {} entries",
+        name, stats.hits, stats.misses, stats.size
+    )
+}
@@ -1,4 +1,7 @@
-use crate::{helpers::guards::is_developer, logic::topic_logic::TopicCalls};
+use crate::{
+    helpers::{guards::is_developer, metrics::metered},
+    logic::topic_logic::TopicCalls,
+};
 use catalyze_shared::{
     topic::{TopicEntry, TopicKind},
     CanisterResult,
@@ -17,7 +20,7 @@ use ic_cdk::{query, update};
 /// This function is guarded by the [`is_developer`](is_developer) function.
 #[update(guard = "is_developer")]
 pub async fn add_topic(kind: TopicKind, value: String) -> CanisterResult<TopicEntry> {
-    TopicCalls::add(kind, value).await
+    metered("add_topic", async { TopicCalls::add(kind, value).await }).await
 }
 
 /// Remove a topic from the canister  - [`[update]`](update)
@@ -30,7 +33,7 @@ pub async fn add_topic(kind: TopicKind, value: String) -> CanisterResult<TopicEn
 /// This function is guarded by the [`is_developer`](is_developer) function.
 #[update(guard = "is_developer")]
 pub async fn remove_topic(id: u64) -> CanisterResult<bool> {
-    TopicCalls::remove(id).await
+    metered("remove_topic", async { TopicCalls::remove(id).await }).await
 }
 
 /// Add many topics to the canister  - [`[update]`](update)
@@ -45,7 +48,10 @@ pub async fn remove_topic(id: u64) -> CanisterResult<bool> {
 /// This function is guarded by the [`is_developer`](is_developer) function.
 #[update(guard = "is_developer")]
 pub async fn add_topics(kind: TopicKind, value: Vec<String>) -> CanisterResult<Vec<TopicEntry>> {
-    TopicCalls::add_many(kind, value).await
+    metered("add_topics", async {
+        TopicCalls::add_many(kind, value).await
+    })
+    .await
 }
 
 /// Get a topic - [`[query]`](query)
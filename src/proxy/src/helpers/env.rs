@@ -0,0 +1,175 @@
+//! The canister environment used by the logic layer, under `cfg(test)` the caller
+//! and time are read from thread locals that can be set by the tests, timers and
+//! spawned futures are queued until a test runs them, websocket messages are recorded
+//! and printed messages go to stdout.
+
+#[cfg(not(test))]
+pub use ic_cdk::{
+    api::{print, time},
+    caller, id, spawn,
+};
+#[cfg(not(test))]
+pub use ic_cdk_timers::{set_timer, set_timer_interval};
+#[cfg(not(test))]
+pub use ic_websocket_cdk::send as ws_send;
+
+/// 32 random bytes from the management canister
+#[cfg(not(test))]
+pub async fn raw_rand() -> catalyze_shared::CanisterResult<Vec<u8>> {
+    ic_cdk::api::management_canister::main::raw_rand()
+        .await
+        .map(|(bytes,)| bytes)
+        .map_err(|(_, err)| {
+            catalyze_shared::api_error::ApiError::unexpected()
+                .add_method_name("raw_rand")
+                .add_message(err)
+        })
+}
+
+#[cfg(test)]
+pub use mock::*;
+
+#[cfg(test)]
+mod mock {
+    use candid::Principal;
+    use std::{cell::RefCell, future::Future, pin::Pin, time::Duration};
+
+    type Timer = (u64, Box<dyn FnOnce()>);
+    type Interval = (Duration, Box<dyn FnMut()>);
+    type Spawned = Pin<Box<dyn Future<Output = ()>>>;
+
+    thread_local! {
+        static CALLER: RefCell<Principal> = RefCell::new(Principal::anonymous());
+        static TIME: RefCell<u64> = const { RefCell::new(0) };
+        static RAND_COUNTER: RefCell<u8> = const { RefCell::new(0) };
+        static TIMERS: RefCell<Vec<Timer>> = const { RefCell::new(Vec::new()) };
+        static INTERVALS: RefCell<Vec<Interval>> = const { RefCell::new(Vec::new()) };
+        static SPAWNED: RefCell<Vec<Spawned>> = const { RefCell::new(Vec::new()) };
+        static WS_MESSAGES: RefCell<Vec<(Principal, Vec<u8>)>> = const { RefCell::new(Vec::new()) };
+    }
+
+    pub fn caller() -> Principal {
+        CALLER.with(|caller| *caller.borrow())
+    }
+
+    pub fn time() -> u64 {
+        TIME.with(|time| *time.borrow())
+    }
+
+    pub fn id() -> Principal {
+        Principal::management_canister()
+    }
+
+    /// Returns different bytes on every call so generated identifiers don't collide
+    pub async fn raw_rand() -> catalyze_shared::CanisterResult<Vec<u8>> {
+        let counter = RAND_COUNTER.with(|counter| {
+            let mut counter = counter.borrow_mut();
+            *counter = counter.wrapping_add(1);
+            *counter
+        });
+
+        Ok(vec![counter; 32])
+    }
+
+    pub fn set_caller(principal: Principal) {
+        CALLER.with(|caller| *caller.borrow_mut() = principal);
+    }
+
+    pub fn set_time(nanos: u64) {
+        TIME.with(|time| *time.borrow_mut() = nanos);
+    }
+
+    /// Queues the timer, it fires on `run_due_timers` once `time` passed the delay
+    pub fn set_timer(delay: Duration, func: impl FnOnce() + 'static) {
+        let due_at = time().saturating_add(delay.as_nanos() as u64);
+        TIMERS.with(|timers| timers.borrow_mut().push((due_at, Box::new(func))));
+    }
+
+    /// Queues the interval, it fires on every `run_intervals`
+    pub fn set_timer_interval(interval: Duration, func: impl FnMut() + 'static) {
+        INTERVALS.with(|intervals| intervals.borrow_mut().push((interval, Box::new(func))));
+    }
+
+    /// Queues the future, it is awaited by `run_due_timers` and `run_intervals`
+    pub fn spawn<F: Future<Output = ()> + 'static>(future: F) {
+        SPAWNED.with(|spawned| spawned.borrow_mut().push(Box::pin(future)));
+    }
+
+    pub fn print<S: AsRef<str>>(message: S) {
+        println!("{}", message.as_ref());
+    }
+
+    pub fn ws_send(principal: Principal, message: Vec<u8>) -> Result<(), String> {
+        WS_MESSAGES.with(|messages| messages.borrow_mut().push((principal, message)));
+        Ok(())
+    }
+
+    /// The intervals that are set, in the order they were set
+    pub fn intervals() -> Vec<Duration> {
+        INTERVALS.with(|intervals| intervals.borrow().iter().map(|(i, _)| *i).collect())
+    }
+
+    /// The number of timers that did not fire yet
+    pub fn pending_timers() -> usize {
+        TIMERS.with(|timers| timers.borrow().len())
+    }
+
+    /// Takes the websocket messages that were sent
+    pub fn take_ws_messages() -> Vec<(Principal, Vec<u8>)> {
+        WS_MESSAGES.with(|messages| messages.take())
+    }
+
+    /// Fires the timers that are due at the current time and awaits the futures they spawned,
+    /// timers that are set by those futures and are already due fire as well
+    pub async fn run_due_timers() {
+        loop {
+            let due = TIMERS.with(|timers| {
+                let (due, pending): (Vec<_>, Vec<_>) = timers
+                    .take()
+                    .into_iter()
+                    .partition(|(due_at, _)| *due_at <= time());
+                *timers.borrow_mut() = pending;
+                due
+            });
+
+            let fired = !due.is_empty();
+            for (_, func) in due {
+                func();
+            }
+
+            if !run_spawned().await && !fired {
+                return;
+            }
+        }
+    }
+
+    /// Fires every interval once and awaits the futures they spawned
+    pub async fn run_intervals() {
+        let mut current = INTERVALS.with(|intervals| intervals.take());
+        for (_, func) in current.iter_mut() {
+            func();
+        }
+        INTERVALS.with(|intervals| {
+            let mut intervals = intervals.borrow_mut();
+            current.append(&mut intervals);
+            *intervals = current;
+        });
+
+        run_spawned().await;
+    }
+
+    async fn run_spawned() -> bool {
+        let mut ran = false;
+        loop {
+            let spawned = SPAWNED.with(|spawned| spawned.take());
+            if spawned.is_empty() {
+                return ran;
+            }
+
+            ran = true;
+            for future in spawned {
+                future.await;
+            }
+        }
+    }
+}
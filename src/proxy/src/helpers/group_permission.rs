@@ -1,56 +1,48 @@
+use super::env::caller;
 use candid::Principal;
 use catalyze_shared::{
     api_error::ApiError,
     permission::{PermissionActionType, PermissionType},
     role::Role,
-    CanisterResult, StorageClient,
+    CanisterResult,
 };
-use ic_cdk::caller;
 
-use crate::storage::groups;
+use crate::{
+    storage::{archived_groups, groups},
+    GROUP_PERMISSION_GUARDS,
+};
 
-/// Determine if the caller has permission to perform an action on group based entities
-/// # Arguments
-/// * `group_identifier` - The group identifier
-/// * `permission_type` - The permission type to check
-pub async fn can_edit(group_id: u64, permission_type: PermissionType) -> CanisterResult<()> {
-    has_permission(
-        caller(),
-        group_id,
-        &permission_type,
-        &PermissionActionType::Edit,
-    )
-    .await
+/// The group permission an endpoint requires, declared on the endpoint with `#[group_permission]`
+pub struct GroupPermissionGuard {
+    pub method: &'static str,
+    pub permission: PermissionType,
+    pub action: PermissionActionType,
 }
 
-pub async fn can_write(group_id: u64, permission_type: PermissionType) -> CanisterResult<()> {
-    has_permission(
-        caller(),
-        group_id,
-        &permission_type,
-        &PermissionActionType::Write,
-    )
-    .await
-}
+/// Checks the permission of a `#[group_permission]` endpoint, called by the code the attribute adds
+/// # Arguments
+/// * `guard` - The permission the endpoint is declared with
+/// * `group_id` - The group the endpoint acts on, `None` when the argument it is read from has none
+/// # Errors
+/// * `ApiError` - If the group id is missing or the caller lacks the permission
+pub async fn group_guard(
+    guard: &GroupPermissionGuard,
+    group_id: impl Into<Option<u64>>,
+) -> CanisterResult<()> {
+    let group_id = group_id.into().ok_or_else(|| {
+        ApiError::bad_request()
+            .add_method_name(guard.method)
+            .add_message("Group id is required")
+    })?;
 
-pub async fn can_delete(group_id: u64, permission_type: PermissionType) -> CanisterResult<()> {
-    has_permission(
-        caller(),
-        group_id,
-        &permission_type,
-        &PermissionActionType::Delete,
-    )
-    .await
+    has_permission(caller(), group_id, &guard.permission, &guard.action).await
 }
 
-pub async fn can_read(group_id: u64, permission_type: PermissionType) -> CanisterResult<()> {
-    has_permission(
-        caller(),
-        group_id,
-        &permission_type,
-        &PermissionActionType::Read,
-    )
-    .await
+/// The permission an endpoint is declared with, read from [`GROUP_PERMISSION_GUARDS`]
+pub fn get_group_permission_guard(method: &str) -> Option<&'static GroupPermissionGuard> {
+    GROUP_PERMISSION_GUARDS
+        .iter()
+        .find(|guard| guard.method == method)
 }
 
 /// Check if the caller has permission to perform an action on a group
@@ -70,6 +62,13 @@ pub async fn has_permission(
 ) -> CanisterResult<()> {
     let (_, group) = groups().get(group_id).await?;
 
+    // archived groups are read-only until they are restored
+    if !matches!(permission_action, PermissionActionType::Read)
+        && archived_groups().contains_key(group_id)
+    {
+        return Err(ApiError::bad_request().add_message("Group is archived"));
+    }
+
     let member = group
         .members
         .members
@@ -1,10 +1,14 @@
-use candid::Principal;
-use catalyze_shared::{
-    api_error::ApiError, application_role::ApplicationRole, CanisterResult, StorageClient,
-};
-use ic_cdk::caller;
+use super::{env::caller, metrics::count_guard_rejection};
+use catalyze_shared::{api_error::ApiError, application_role::ApplicationRole, CanisterResult};
+use ic_cdk::api::is_controller;
 
-use crate::storage::profiles;
+use crate::{logic::admin_logic::AdminCalls, models::AdminRole, storage::profiles};
+
+/// Checks if the caller is not anonymous, counts the rejection when it is
+pub fn is_not_anonymous() -> Result<(), String> {
+    catalyze_shared::guards::is_not_anonymous()
+        .inspect_err(|_| count_guard_rejection("is_not_anonymous"))
+}
 
 /// Checks if the caller has a profile and is not blocked or banned on the application level
 /// # Returns
@@ -21,55 +25,38 @@ pub async fn has_access() -> CanisterResult<()> {
     Err(ApiError::unauthorized().add_message("Blocked or banned"))
 }
 
-/// Checks if the caller is the monitor principal
+/// Checks if the caller has the monitor role in the admin registry
 pub fn is_monitor() -> Result<(), String> {
-    // monitor principal
-    let monitor_principal =
-        Principal::from_text("6or45-oyaaa-aaaap-absua-cai").expect("Invalid principal");
-    if caller() == monitor_principal {
-        Ok(())
-    } else {
-        Err(ApiError::unauthorized()
-            .add_message("Unauthorized")
-            .to_string())
-    }
+    has_admin_role("is_monitor", &[AdminRole::Monitor])
 }
 
+/// Checks if the caller has the production developer role in the admin registry
 pub fn is_prod_developer() -> Result<(), String> {
-    let developers = [
-        // production
-        "ledm3-52ncq-rffuv-6ed44-hg5uo-iicyu-pwkzj-syfva-heo4k-p7itq-aqe",
-    ];
-
-    if developers.contains(&caller().to_text().as_str()) {
-        Ok(())
-    } else {
-        Err(ApiError::unauthorized()
-            .add_message("Unauthorized")
-            .to_string())
-    }
+    has_admin_role("is_prod_developer", &[AdminRole::ProdDeveloper])
 }
 
-// Check if the caller is the Catalyze developer principal
+// Check if the caller is a Catalyze developer or the monitor
 pub fn is_developer() -> Result<(), String> {
-    let developers = [
-        // production
-        "ledm3-52ncq-rffuv-6ed44-hg5uo-iicyu-pwkzj-syfva-heo4k-p7itq-aqe",
-        // staging
-        "syzio-xu6ca-burmx-4afo2-ojpcw-e75j3-m67o5-s5bes-5vvsv-du3t4-wae",
-        // Monitor
-        "6or45-oyaaa-aaaap-absua-cai",
-    ];
+    has_admin_role("is_developer", &[AdminRole::Developer, AdminRole::Monitor])
+}
 
-    if developers.contains(&caller().to_text().as_str()) {
+/// Checks if the caller is a developer or a controller of the canister,
+/// controllers can always manage the admin registry so it can't lock itself out
+pub fn is_developer_or_controller() -> Result<(), String> {
+    if is_controller(&caller()) {
+        return Ok(());
+    }
+
+    has_admin_role("is_developer_or_controller", &[AdminRole::Developer])
+}
+
+fn has_admin_role(guard: &'static str, roles: &[AdminRole]) -> Result<(), String> {
+    if AdminCalls::has_role(caller(), roles) {
         Ok(())
     } else {
+        count_guard_rejection(guard);
         Err(ApiError::unauthorized()
             .add_message("Unauthorized")
             .to_string())
     }
 }
-
-// TODO: add guards for group role based access
-// https://forum.dfinity.org/t/rust-guard-access-arguments/22229?u=rmcs
-// https://docs.rs/ic-cdk/latest/ic_cdk/api/call/fn.arg_data.html
@@ -0,0 +1,80 @@
+use std::{cell::RefCell, collections::BTreeMap, future::Future};
+
+use catalyze_shared::CanisterResult;
+
+thread_local! {
+    static CALL_COUNTERS: RefCell<BTreeMap<&'static str, CallCounter>> = RefCell::new(BTreeMap::new());
+    static GUARD_REJECTIONS: RefCell<BTreeMap<&'static str, u64>> = RefCell::new(BTreeMap::new());
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CallCounter {
+    pub calls: u64,
+    pub errors: u64,
+}
+
+/// Counts the call and, when it returns an error, the error of an endpoint
+/// # Arguments
+/// * `method` - The name of the endpoint
+/// * `call` - The body of the endpoint
+/// # Note
+/// Only wrap update calls, state changes made by queries are discarded.
+/// The counters live on the heap and restart on upgrade.
+pub async fn metered<T>(
+    method: &'static str,
+    call: impl Future<Output = CanisterResult<T>>,
+) -> CanisterResult<T> {
+    metered_by(method, call, CanisterResult::is_err).await
+}
+
+/// Like `metered` for endpoints that don't return a `CanisterResult`
+/// # Arguments
+/// * `method` - The name of the endpoint
+/// * `call` - The body of the endpoint
+/// * `is_error` - Decides if the returned value counts as an error
+pub async fn metered_by<T>(
+    method: &'static str,
+    call: impl Future<Output = T>,
+    is_error: impl FnOnce(&T) -> bool,
+) -> T {
+    let result = call.await;
+
+    CALL_COUNTERS.with(|counters| {
+        let mut counters = counters.borrow_mut();
+        let counter = counters.entry(method).or_default();
+        counter.calls += 1;
+        if is_error(&result) {
+            counter.errors += 1;
+        }
+    });
+
+    result
+}
+
+/// Counts a call that was rejected by a guard before it reached the endpoint
+/// # Note
+/// A rejection is a reply, so the count of a rejected update call is kept.
+/// Rejected queries are not counted as their state changes are discarded.
+pub fn count_guard_rejection(guard: &'static str) {
+    GUARD_REJECTIONS.with(|rejections| *rejections.borrow_mut().entry(guard).or_default() += 1);
+}
+
+pub fn get_guard_rejections() -> Vec<(&'static str, u64)> {
+    GUARD_REJECTIONS.with(|rejections| {
+        rejections
+            .borrow()
+            .iter()
+            .map(|(guard, count)| (*guard, *count))
+            .collect()
+    })
+}
+
+pub fn get_call_counters() -> Vec<(&'static str, CallCounter)> {
+    CALL_COUNTERS.with(|counters| {
+        counters
+            .borrow()
+            .iter()
+            .map(|(method, counter)| (*method, *counter))
+            .collect()
+    })
+}
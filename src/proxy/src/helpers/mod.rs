@@ -1,3 +1,7 @@
+pub mod env;
 pub mod group_permission;
 pub mod guards;
+pub mod metrics;
+pub mod nns_governance;
+pub mod sns_governance;
 pub mod token_balance;
@@ -0,0 +1,74 @@
+use std::collections::HashSet;
+
+use candid::Principal;
+use catalyze_shared::models::neuron::{
+    DissolveState as NnsDissolveState, ListNeurons, ListNeuronsResponse, Neuron as NnsNeuron,
+};
+use ic_cdk::api::{self};
+
+use super::sns_governance::{DissolveState, Neuron, NeuronId, MAX_NEURON_PAGES, NEURON_PAGE_SIZE};
+
+/// The fields of an NNS neuron that are read by the neuron rules, the NNS rules have no
+/// voting power rule so the fees, maturity and multiplier don't count
+impl From<NnsNeuron> for Neuron {
+    fn from(neuron: NnsNeuron) -> Self {
+        Self {
+            id: neuron.id.map(|id| NeuronId { id: id.id }),
+            cached_neuron_stake_e8s: neuron.cached_neuron_stake_e8s,
+            neuron_fees_e8s: 0,
+            staked_maturity_e8s_equivalent: None,
+            created_timestamp_seconds: neuron.created_timestamp_seconds,
+            aging_since_timestamp_seconds: neuron.created_timestamp_seconds,
+            dissolve_state: neuron.dissolve_state.map(|state| match state {
+                NnsDissolveState::DissolveDelaySeconds(delay) => {
+                    DissolveState::DissolveDelaySeconds(delay)
+                }
+                NnsDissolveState::WhenDissolvedTimestampSeconds(dissolved_at) => {
+                    DissolveState::WhenDissolvedTimestampSeconds(dissolved_at)
+                }
+            }),
+            voting_power_percentage_multiplier: 100,
+        }
+    }
+}
+
+/// Pages through the neurons of the principal on the NNS governance canister,
+/// `None` when a page could not be read
+pub async fn list_nns_neurons_of(
+    governance_canister: Principal,
+    principal: Principal,
+) -> Option<Vec<Neuron>> {
+    let mut neurons: Vec<Neuron> = vec![];
+    let mut neuron_ids: HashSet<Vec<u8>> = HashSet::new();
+    let mut start_page_at = None;
+
+    for _ in 0..MAX_NEURON_PAGES {
+        let list_neurons = ListNeurons {
+            of_principal: Some(principal),
+            limit: NEURON_PAGE_SIZE,
+            start_page_at: start_page_at.clone(),
+        };
+
+        let call: Result<(ListNeuronsResponse,), _> =
+            api::call::call(governance_canister, "list_neurons", (list_neurons,)).await;
+        let page = call.ok()?.0.neurons;
+
+        let is_last_page = page.len() < NEURON_PAGE_SIZE as usize;
+        start_page_at = page.last().and_then(|neuron| neuron.id.clone());
+
+        // the page can start at the last neuron of the previous page
+        for neuron in page {
+            if let Some(id) = &neuron.id {
+                if neuron_ids.insert(id.id.clone()) {
+                    neurons.push(Neuron::from(neuron));
+                }
+            }
+        }
+
+        if is_last_page || start_page_at.is_none() {
+            break;
+        }
+    }
+
+    Some(neurons)
+}
@@ -0,0 +1,173 @@
+use std::collections::HashSet;
+
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::api::{self};
+
+/// The number of neurons requested per `list_neurons` call
+pub const NEURON_PAGE_SIZE: u32 = 100;
+/// The maximum number of `list_neurons` pages read for a single principal
+pub const MAX_NEURON_PAGES: usize = 10;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, CandidType, Deserialize)]
+pub struct NeuronId {
+    pub id: Vec<u8>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct ListNeurons {
+    pub of_principal: Option<Principal>,
+    pub limit: u32,
+    pub start_page_at: Option<NeuronId>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub enum DissolveState {
+    DissolveDelaySeconds(u64),
+    WhenDissolvedTimestampSeconds(u64),
+}
+
+/// The fields of an SNS neuron that are used for gating, the other fields are skipped when decoding
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct Neuron {
+    pub id: Option<NeuronId>,
+    pub cached_neuron_stake_e8s: u64,
+    pub neuron_fees_e8s: u64,
+    pub staked_maturity_e8s_equivalent: Option<u64>,
+    pub created_timestamp_seconds: u64,
+    pub aging_since_timestamp_seconds: u64,
+    pub dissolve_state: Option<DissolveState>,
+    pub voting_power_percentage_multiplier: u64,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct ListNeuronsResponse {
+    pub neurons: Vec<Neuron>,
+}
+
+/// The voting power parameters of an SNS, the other parameters are skipped when decoding
+#[derive(Clone, Debug, Default, CandidType, Deserialize)]
+pub struct NervousSystemParameters {
+    pub neuron_minimum_dissolve_delay_to_vote_seconds: Option<u64>,
+    pub max_dissolve_delay_seconds: Option<u64>,
+    pub max_dissolve_delay_bonus_percentage: Option<u64>,
+    pub max_neuron_age_for_age_bonus: Option<u64>,
+    pub max_age_bonus_percentage: Option<u64>,
+}
+
+impl Neuron {
+    pub fn is_dissolving(&self) -> bool {
+        matches!(
+            self.dissolve_state,
+            Some(DissolveState::WhenDissolvedTimestampSeconds(_))
+        )
+    }
+
+    /// The dissolve delay of a locked neuron, or the time left until a dissolving neuron is dissolved
+    pub fn dissolve_delay_seconds(&self, now_seconds: u64) -> u64 {
+        match self.dissolve_state {
+            Some(DissolveState::DissolveDelaySeconds(delay)) => delay,
+            Some(DissolveState::WhenDissolvedTimestampSeconds(dissolved_at)) => {
+                dissolved_at.saturating_sub(now_seconds)
+            }
+            None => 0,
+        }
+    }
+
+    /// Dissolving neurons don't age, their `aging_since_timestamp_seconds` is `u64::MAX`
+    pub fn age_seconds(&self, now_seconds: u64) -> u64 {
+        now_seconds.saturating_sub(self.aging_since_timestamp_seconds)
+    }
+
+    /// The voting power as calculated by SNS governance, the stake with the dissolve delay
+    /// and age bonuses, zero when the dissolve delay is too short to vote
+    pub fn voting_power(&self, parameters: &NervousSystemParameters, now_seconds: u64) -> u64 {
+        let dissolve_delay = self.dissolve_delay_seconds(now_seconds);
+        if dissolve_delay
+            < parameters
+                .neuron_minimum_dissolve_delay_to_vote_seconds
+                .unwrap_or_default()
+        {
+            return 0;
+        }
+
+        let stake = self
+            .cached_neuron_stake_e8s
+            .saturating_sub(self.neuron_fees_e8s)
+            .saturating_add(self.staked_maturity_e8s_equivalent.unwrap_or_default())
+            as u128;
+
+        let bonus = |value: u128, amount: u64, max_amount: Option<u64>, percentage: Option<u64>| {
+            let max_amount = max_amount.unwrap_or_default() as u128;
+            if max_amount == 0 {
+                return value;
+            }
+            let amount = (amount as u128).min(max_amount);
+            value + value * amount * percentage.unwrap_or_default() as u128 / (100 * max_amount)
+        };
+
+        let dissolve_delay_stake = bonus(
+            stake,
+            dissolve_delay,
+            parameters.max_dissolve_delay_seconds,
+            parameters.max_dissolve_delay_bonus_percentage,
+        );
+        let age_dissolve_delay_stake = bonus(
+            dissolve_delay_stake,
+            self.age_seconds(now_seconds),
+            parameters.max_neuron_age_for_age_bonus,
+            parameters.max_age_bonus_percentage,
+        );
+
+        let voting_power =
+            age_dissolve_delay_stake * self.voting_power_percentage_multiplier as u128 / 100;
+        voting_power.min(u64::MAX as u128) as u64
+    }
+}
+
+/// Pages through the neurons of the principal, `None` when a page could not be read
+pub async fn list_neurons_of(
+    governance_canister: Principal,
+    principal: Principal,
+) -> Option<Vec<Neuron>> {
+    let mut neurons: Vec<Neuron> = vec![];
+    let mut neuron_ids: HashSet<NeuronId> = HashSet::new();
+    let mut start_page_at = None;
+
+    for _ in 0..MAX_NEURON_PAGES {
+        let list_neurons = ListNeurons {
+            of_principal: Some(principal),
+            limit: NEURON_PAGE_SIZE,
+            start_page_at: start_page_at.clone(),
+        };
+
+        let call: Result<(ListNeuronsResponse,), _> =
+            api::call::call(governance_canister, "list_neurons", (list_neurons,)).await;
+        let page = call.ok()?.0.neurons;
+
+        let is_last_page = page.len() < NEURON_PAGE_SIZE as usize;
+        start_page_at = page.last().and_then(|neuron| neuron.id.clone());
+
+        // the page can start at the last neuron of the previous page
+        for neuron in page {
+            if let Some(id) = neuron.id.clone() {
+                if neuron_ids.insert(id) {
+                    neurons.push(neuron);
+                }
+            }
+        }
+
+        if is_last_page || start_page_at.is_none() {
+            break;
+        }
+    }
+
+    Some(neurons)
+}
+
+pub async fn get_nervous_system_parameters(
+    governance_canister: Principal,
+) -> Option<NervousSystemParameters> {
+    let call: Result<(NervousSystemParameters,), _> =
+        api::call::call(governance_canister, "get_nervous_system_parameters", ((),)).await;
+    call.ok().map(|response| response.0)
+}
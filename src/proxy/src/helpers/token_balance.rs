@@ -38,49 +38,68 @@ pub enum DipNftError {
     SelfTransfer,
 }
 
-pub async fn ext_balance_of(canister: Principal, account_identifier: String) -> u32 {
+// The balance helpers return `None` when the call to the canister fails,
+// so a failed call is not mistaken for an empty balance
+
+pub async fn ext_balance_of(canister: Principal, account_identifier: String) -> Option<u32> {
     let call: Result<(ExtResult,), _> =
         api::call::call(canister, "tokens_ext", (account_identifier,)).await;
     match call {
         Ok(response) => match response.0 {
-            ExtResult::ok(_res) => _res.len() as u32,
-            ExtResult::err(_) => 0,
+            ExtResult::ok(_res) => Some(_res.len() as u32),
+            ExtResult::err(_) => Some(0),
         },
-        Err(_) => 0,
+        Err(_) => None,
     }
 }
 
-pub async fn dip20_balance_of(canister: Principal, principal: &Principal) -> u32 {
+pub async fn dip20_balance_of(canister: Principal, principal: &Principal) -> Option<u32> {
     let call: Result<(u32,), _> = api::call::call(canister, "balanceOf", (principal,)).await;
     match call {
-        Ok(response) => response.0,
-        Err(_) => 0,
+        Ok(response) => Some(response.0),
+        Err(_) => None,
     }
 }
 
-pub async fn legacy_dip721_balance_of(canister: Principal, principal: &Principal) -> u32 {
+pub async fn legacy_dip721_balance_of(canister: Principal, principal: &Principal) -> Option<u32> {
     let call: Result<(Result<u32, DipNftError>,), _> =
         api::call::call(canister, "balanceOf", (principal,)).await;
     match call {
-        Ok(response) => response.0.unwrap_or_default(),
-        Err(_) => 0,
+        Ok(response) => Some(response.0.unwrap_or_default()),
+        Err(_) => None,
     }
 }
 
-pub async fn dip721_balance_of(canister: Principal, principal: &Principal) -> u32 {
+pub async fn dip721_balance_of(canister: Principal, principal: &Principal) -> Option<u32> {
     let call: Result<(Result<u32, DipNftError>,), _> =
         api::call::call(canister, "dip721_balance_of", (principal,)).await;
     match call {
-        Ok(response) => response.0.unwrap_or_default(),
-        Err(_) => 0,
+        Ok(response) => Some(response.0.unwrap_or_default()),
+        Err(_) => None,
+    }
+}
+
+/// An ICRC-1 account, `None` is the default subaccount of the owner
+#[derive(Clone, Debug, PartialEq, Eq, CandidType, Deserialize)]
+pub struct Account {
+    pub owner: Principal,
+    pub subaccount: Option<Vec<u8>>,
+}
+
+pub async fn icrc1_balance_of(canister: Principal, account: &Account) -> Option<u128> {
+    let call: Result<(u128,), _> = api::call::call(canister, "icrc1_balance_of", (account,)).await;
+    match call {
+        Ok(response) => Some(response.0),
+        Err(_) => None,
     }
 }
 
-pub async fn icrc_balance_of(canister: Principal, principal: &Principal) -> u128 {
-    let call: Result<(u128,), _> =
-        api::call::call(canister, "icrc1_balance_of", (principal,)).await;
+/// The number of NFTs of an ICRC-7 collection held by all the accounts together
+pub async fn icrc7_balance_of(canister: Principal, accounts: &[Account]) -> Option<u128> {
+    let call: Result<(Vec<u128>,), _> =
+        api::call::call(canister, "icrc7_balance_of", (accounts,)).await;
     match call {
-        Ok(response) => response.0,
-        Err(_) => 0,
+        Ok(response) => Some(response.0.iter().sum()),
+        Err(_) => None,
     }
 }
@@ -1,23 +1,21 @@
 use candid::Principal;
 use ic_cdk::query;
 
-// canisters used in production, but are not environment dependant
-pub static CATALYZE_MULTI_SIG: &str = "fcygz-gqaaa-aaaap-abpaa-cai";
-pub static MULTISIG_INDEX: &str = "o7ouu-niaaa-aaaap-ahhdq-cai";
-
-pub static E8S_PER_DAY_BOOST_COST: u64 = 3500000;
-pub static USER_GROUP_CREATION_LIMIT: usize = 10;
-
 pub mod calls;
 pub mod helpers;
 pub mod logic;
+pub mod models;
 pub mod storage;
 
+#[cfg(test)]
+mod tests;
+
 // Hacky way to expose the candid interface to the outside world
 #[query(name = "__get_candid_interface_tmp_hack")]
 pub fn __export_did_tmp_() -> String {
     use candid::export_service;
 
+    use crate::models::*;
     use catalyze_shared::attendee::{InviteAttendeeResponse, JoinedAttendeeResponse};
     use catalyze_shared::event_with_attendees::*;
     use catalyze_shared::friend_request::*;
@@ -46,6 +44,52 @@ pub fn __export_did_tmp_() -> String {
     __export_service()
 }
 
+// Every endpoint declared with `#[group_permission]`, an endpoint that is left out is still checked
+// but `get_group_permission_guard` can't find it
+proxy_macros::group_permission_guards![
+    calls::event_calls::add_event,
+    calls::event_calls::edit_event,
+    calls::event_calls::delete_event,
+    calls::event_calls::cancel_event,
+    calls::event_calls::invite_to_event,
+    calls::event_calls::accept_user_request_event_invite,
+    calls::event_calls::decline_user_request_event_invite,
+    calls::event_calls::remove_attendee_from_event,
+    calls::event_calls::remove_attendee_invite_from_event,
+    calls::group_calls::edit_group,
+    calls::group_calls::delete_group,
+    calls::group_calls::add_wallet_to_group,
+    calls::group_calls::remove_wallet_from_group,
+    calls::group_calls::add_role_to_group,
+    calls::group_calls::remove_group_role,
+    calls::group_calls::get_group_roles,
+    calls::group_calls::edit_role_permissions,
+    calls::group_calls::invite_to_group,
+    calls::group_calls::invite_many_to_group,
+    calls::group_calls::accept_user_request_group_invite,
+    calls::group_calls::decline_user_request_group_invite,
+    calls::group_calls::assign_role,
+    calls::group_calls::assign_role_to_many,
+    calls::group_calls::remove_member_role,
+    calls::group_calls::remove_member_from_group,
+    calls::group_calls::remove_many_members_from_group,
+    calls::group_calls::remove_member_invite_from_group,
+    calls::group_calls::get_group_invites,
+    calls::group_calls::get_group_invites_with_profiles,
+    calls::group_calls::get_banned_group_members,
+    calls::group_calls::ban_group_member,
+    calls::group_calls::remove_ban_from_group_member,
+    calls::group_calls::create_invite_code,
+    calls::group_calls::get_invite_codes,
+    calls::group_calls::revoke_invite_code,
+    calls::group_calls::get_gated_removal_reports,
+    calls::group_calls::set_group_gating_rule,
+    calls::group_calls::set_group_member_cap,
+    calls::group_calls::get_group_waitlist,
+    calls::report_calls::get_report,
+    calls::report_calls::get_reports,
+];
+
 // Method used to save the candid interface to a file
 #[test]
 pub fn candid() {
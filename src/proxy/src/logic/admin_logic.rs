@@ -0,0 +1,130 @@
+use candid::Principal;
+use catalyze_shared::{api_error::ApiError, CanisterResult};
+
+use crate::{
+    helpers::env::{caller, id, time},
+    models::{AdminAction, AdminAuditEntry, AdminRole, AdminRoles},
+    storage::{admin_audit_log, admins},
+};
+
+/// The principals that were compiled into the guards before the admin registry existed,
+/// used to seed the registry once so existing deployments keep working after the upgrade.
+/// Only the production principal gets `ProdDeveloper`, like the old hardcoded prod guard.
+const LEGACY_ADMINS: [(&str, AdminRole); 5] = [
+    // production
+    (
+        "ledm3-52ncq-rffuv-6ed44-hg5uo-iicyu-pwkzj-syfva-heo4k-p7itq-aqe",
+        AdminRole::Developer,
+    ),
+    (
+        "ledm3-52ncq-rffuv-6ed44-hg5uo-iicyu-pwkzj-syfva-heo4k-p7itq-aqe",
+        AdminRole::ProdDeveloper,
+    ),
+    // staging
+    (
+        "syzio-xu6ca-burmx-4afo2-ojpcw-e75j3-m67o5-s5bes-5vvsv-du3t4-wae",
+        AdminRole::Developer,
+    ),
+    ("6or45-oyaaa-aaaap-absua-cai", AdminRole::Monitor),
+    // multisig index
+    ("o7ouu-niaaa-aaaap-ahhdq-cai", AdminRole::TrustedNotifier),
+];
+
+pub struct AdminCalls;
+
+impl AdminCalls {
+    pub fn has_role(principal: Principal, roles: &[AdminRole]) -> bool {
+        admins()
+            .find(principal)
+            .is_some_and(|admin| admin.roles.iter().any(|role| roles.contains(role)))
+    }
+
+    pub fn get_admins() -> Vec<(Principal, AdminRoles)> {
+        admins().get_all()
+    }
+
+    pub fn get_audit_log() -> Vec<(u64, AdminAuditEntry)> {
+        admin_audit_log().get_all()
+    }
+
+    pub fn add_role(principal: Principal, role: AdminRole) -> CanisterResult<AdminRoles> {
+        let mut admin = admins().find(principal).unwrap_or_default();
+
+        if admin.roles.contains(&role) {
+            return Err(ApiError::duplicate()
+                .add_method_name("add_role")
+                .add_message(format!("{} already has the {:?} role", principal, role)));
+        }
+
+        admin.roles.push(role);
+        Ok(Self::store(
+            principal,
+            admin,
+            role,
+            AdminAction::Added,
+            caller(),
+        ))
+    }
+
+    pub fn remove_role(principal: Principal, role: AdminRole) -> CanisterResult<AdminRoles> {
+        let mut admin = admins()
+            .find(principal)
+            .filter(|admin| admin.roles.contains(&role))
+            .ok_or_else(|| {
+                ApiError::not_found()
+                    .add_method_name("remove_role")
+                    .add_message(format!("{} does not have the {:?} role", principal, role))
+            })?;
+
+        admin.roles.retain(|r| r != &role);
+        Ok(Self::store(
+            principal,
+            admin,
+            role,
+            AdminAction::Removed,
+            caller(),
+        ))
+    }
+
+    /// Fills the registry with the principals that were hardcoded in the guards.
+    /// Runs only while the audit log is empty, every seed and role change is logged there
+    /// so revoked legacy admins are not brought back by a later upgrade
+    pub fn seed_legacy_admins() {
+        if admin_audit_log().size() > 0 {
+            return;
+        }
+
+        for (principal, role) in LEGACY_ADMINS {
+            let principal = Principal::from_text(principal).expect("Invalid principal");
+            let mut admin = admins().find(principal).unwrap_or_default();
+            admin.roles.push(role);
+            Self::store(principal, admin, role, AdminAction::Added, id());
+        }
+    }
+
+    fn store(
+        principal: Principal,
+        mut admin: AdminRoles,
+        role: AdminRole,
+        action: AdminAction,
+        changed_by: Principal,
+    ) -> AdminRoles {
+        admin.updated_at = time();
+
+        if admin.roles.is_empty() {
+            admins().remove(principal);
+        } else {
+            admins().insert(principal, admin.clone());
+        }
+
+        admin_audit_log().insert_next(AdminAuditEntry {
+            action,
+            principal,
+            role,
+            changed_by,
+            created_at: time(),
+        });
+
+        admin
+    }
+}
@@ -1,5 +1,8 @@
-use super::ledger_logic::Ledger;
-use crate::{storage::boosts, E8S_PER_DAY_BOOST_COST};
+use super::{config_logic::ConfigCalls, ledger_logic::Ledger};
+use crate::{
+    helpers::env::{caller, time},
+    storage::boosts,
+};
 use candid::Principal;
 use catalyze_shared::{
     api_error::ApiError,
@@ -7,7 +10,6 @@ use catalyze_shared::{
     subject::{Subject, SubjectType},
     CanisterResult, Filter, StorageClient, StorageClientInsertable,
 };
-use ic_cdk::{api::time, caller};
 use ic_ledger_types::Tokens;
 use std::{cell::RefCell, time::Duration};
 
@@ -79,7 +81,8 @@ impl BoostCalls {
     }
 
     pub fn calculate_days(tokens: Tokens) -> u64 {
-        ((tokens.e8s() as f64) / (E8S_PER_DAY_BOOST_COST as f64)).round() as u64
+        let e8s_per_day_boost_cost = ConfigCalls::get_platform_config().e8s_per_day_boost_cost;
+        ((tokens.e8s() as f64) / (e8s_per_day_boost_cost as f64)).round() as u64
     }
 
     pub fn get_seconds_from_days(days: u64) -> u64 {
@@ -113,4 +116,36 @@ impl BoostCalls {
             .filter(BoostedFilter::SubjectType(subject).to_vec())
             .await
     }
+
+    /// Resolves the boosts for a set of subjects with a single storage call per subject type,
+    /// the boost store filters on one subject or one subject type but not on a set of subjects
+    /// # Arguments
+    /// * `subjects` - The subjects to get the boosts for
+    /// # Returns
+    /// * `Vec<BoostedEntry>` - The boosts of the subjects that are boosted
+    pub async fn get_boosts_by_subjects(
+        subjects: Vec<Subject>,
+    ) -> CanisterResult<Vec<BoostedEntry>> {
+        let mut subject_types: Vec<SubjectType> = vec![];
+
+        for subject in &subjects {
+            let subject_type = subject.get_type();
+            if !subject_types.contains(&subject_type) {
+                subject_types.push(subject_type);
+            }
+        }
+
+        let mut result = vec![];
+
+        for subject_type in subject_types {
+            let boosts = Self::get_boosts_by_subject(subject_type).await?;
+            result.extend(
+                boosts
+                    .into_iter()
+                    .filter(|(_, boost)| subjects.contains(&boost.subject)),
+            );
+        }
+
+        Ok(result)
+    }
 }
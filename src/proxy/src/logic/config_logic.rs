@@ -0,0 +1,65 @@
+use catalyze_shared::{api_error::ApiError, CanisterResult, CellStorage};
+
+use crate::{
+    helpers::env::time,
+    models::{PlatformConfig, UpdatePlatformConfig},
+    storage::platform_config,
+};
+
+pub struct ConfigCalls;
+
+impl ConfigCalls {
+    /// Returns the stored platform config, or the defaults when nothing is stored yet
+    pub fn get_platform_config() -> PlatformConfig {
+        platform_config()
+            .get()
+            .map(PlatformConfig::with_defaults)
+            .unwrap_or_default()
+    }
+
+    pub fn update_platform_config(update: UpdatePlatformConfig) -> CanisterResult<PlatformConfig> {
+        let mut config = Self::get_platform_config();
+        config.update(update, time());
+
+        Self::validate_platform_config(&config)?;
+        platform_config().set(config)
+    }
+
+    fn validate_platform_config(config: &PlatformConfig) -> CanisterResult<()> {
+        let error = |message: &str| {
+            Err(ApiError::bad_request()
+                .add_method_name("update_platform_config")
+                .add_message(message))
+        };
+
+        if config.e8s_per_day_boost_cost == 0 {
+            return error("Boost cost per day must be greater than 0");
+        }
+
+        if config.topic_min_length == 0 || config.topic_min_length > config.topic_max_length {
+            return error("Topic min length must be greater than 0 and not exceed the max length");
+        }
+
+        if config.friend_request_auto_remove_delay == 0 {
+            return error("Friend request auto remove delay must be greater than 0");
+        }
+
+        if config.group_restore_window() == 0 {
+            return error("Group restore window must be greater than 0");
+        }
+
+        if config.join_request_expiry() == 0 {
+            return error("Join request expiry must be greater than 0");
+        }
+
+        if config.gated_grace_period() == 0 {
+            return error("Gated grace period must be greater than 0");
+        }
+
+        if config.gated_revalidation_batch_size() == 0 {
+            return error("Gated revalidation batch size must be greater than 0");
+        }
+
+        Ok(())
+    }
+}
@@ -1,9 +1,14 @@
 use std::collections::HashMap;
 
-use crate::storage::{boosts, events, groups, profiles};
+use crate::{
+    helpers::env::{caller, time},
+    models::{SagaKind, SagaStep},
+    storage::{boosts, events, groups, profiles},
+};
 
 use super::{
     boost_logic::BoostCalls, notification_logic::NotificationCalls, profile_logic::ProfileCalls,
+    saga_logic::SagaCalls,
 };
 use candid::Principal;
 use catalyze_shared::{
@@ -23,27 +28,31 @@ use catalyze_shared::{
     time_helper::hours_to_nanoseconds,
     CanisterResult, Filter, StorageClient, StorageClientInsertable,
 };
-use ic_cdk::{api::time, caller};
 
 pub struct EventCalls;
 
 impl EventCalls {
     pub async fn add_event(post_event: PostEvent) -> CanisterResult<EventResponse> {
-        let (new_event_id, new_event) = events()
-            .insert(EventWithAttendees::from(post_event.clone()))
-            .await?;
-
-        let group_id = new_event.ensured_group_id()?;
-
-        let (_, mut group) = groups().get(group_id).await?;
-        group.add_event(new_event_id);
-
-        groups().update(group_id, group).await?;
+        let event = EventWithAttendees::from(post_event.clone());
+        let group_id = event.ensured_group_id()?;
 
-        let (_, mut profile) = profiles().get(caller()).await?;
-        profile.add_event(new_event_id);
+        let (new_event_id, new_event) = events().insert(event).await?;
 
-        profiles().update(caller(), profile).await?;
+        SagaCalls::run(
+            SagaKind::AddEvent,
+            vec![SagaStep::InsertEvent(new_event_id)],
+            vec![
+                SagaStep::AddEventToGroup {
+                    group_id,
+                    event_id: new_event_id,
+                },
+                SagaStep::AddEventToProfile {
+                    principal: caller(),
+                    event_id: new_event_id,
+                },
+            ],
+        )
+        .await?;
 
         Ok(EventResponse::new(
             new_event_id,
@@ -83,15 +92,11 @@ impl EventCalls {
             .filter_paginated(limit, page, sort, filters)
             .await?;
 
-        let mut boosted_events = HashMap::new();
-
-        for (id, _) in resp.data.clone() {
-            let boosted_event = Self::get_boosted_event(id).await?;
-            boosted_events.insert(id, boosted_event);
-        }
+        let boosted_events =
+            Self::get_boosted_events_by_id(resp.data.iter().map(|(id, _)| *id).collect()).await?;
 
         resp.map(|(id, event)| {
-            EventResponse::new(*id, event.clone(), boosted_events.get(id).unwrap().clone())
+            EventResponse::new(*id, event.clone(), boosted_events.get(id).cloned())
         })
         .into_result()
     }
@@ -111,13 +116,21 @@ impl EventCalls {
     }
 
     pub async fn get_boosted_events() -> CanisterResult<Vec<EventResponse>> {
-        let ids = BoostCalls::get_boosts_by_subject(SubjectType::Event)
+        let boosted_events: HashMap<u64, Boost> =
+            BoostCalls::get_boosts_by_subject(SubjectType::Event)
+                .await?
+                .into_iter()
+                .map(|(_, boost)| (*boost.subject.get_id(), boost))
+                .collect();
+
+        let result = events()
+            .get_many(boosted_events.keys().cloned().collect())
             .await?
             .into_iter()
-            .map(|(_, boost)| *boost.subject.get_id())
+            .map(|(id, event)| EventResponse::new(id, event, boosted_events.get(&id).cloned()))
             .collect();
 
-        Self::get_events_by_id(ids).await
+        Ok(result)
     }
 
     pub async fn get_events_count(
@@ -481,18 +494,12 @@ impl EventCalls {
     pub async fn get_events_by_id(event_ids: Vec<u64>) -> CanisterResult<Vec<EventResponse>> {
         let events = events().get_many(event_ids).await?;
 
-        let mut boosted_events = HashMap::new();
-
-        for (id, _) in events.clone() {
-            let boosted_event = Self::get_boosted_event(id).await?;
-            boosted_events.insert(id, boosted_event);
-        }
+        let boosted_events =
+            Self::get_boosted_events_by_id(events.iter().map(|(id, _)| *id).collect()).await?;
 
         let events = events
-            .iter()
-            .map(|(id, event)| {
-                EventResponse::new(*id, event.clone(), boosted_events.get(id).unwrap().clone())
-            })
+            .into_iter()
+            .map(|(id, event)| EventResponse::new(id, event, boosted_events.get(&id).cloned()))
             .collect();
 
         Ok(events)
@@ -640,6 +647,17 @@ impl EventCalls {
         Ok(boosted.map(|(_, x)| x))
     }
 
+    async fn get_boosted_events_by_id(ids: Vec<u64>) -> CanisterResult<HashMap<u64, Boost>> {
+        let boosts =
+            BoostCalls::get_boosts_by_subjects(ids.into_iter().map(Subject::Event).collect())
+                .await?
+                .into_iter()
+                .map(|(_, boost)| (*boost.subject.get_id(), boost))
+                .collect();
+
+        Ok(boosts)
+    }
+
     async fn get_event_by_id_and_group(
         event_id: u64,
         group_id: u64,
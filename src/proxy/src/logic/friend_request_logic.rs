@@ -5,23 +5,25 @@ use catalyze_shared::{
     api_error::ApiError,
     friend_request::{FriendRequest, FriendRequestFilter, FriendRequestResponse},
     profile_with_refs::ProfileResponse,
-    relation_type::RelationType,
     CanisterResult, Filter, StorageClient, StorageClientInsertable,
 };
-use ic_cdk::{caller, spawn};
+use ic_cdk::spawn;
 use ic_cdk_timers::set_timer;
 
-use crate::storage::{friend_requests, profiles};
+use crate::{
+    helpers::env::caller,
+    models::{SagaKind, SagaStep},
+    storage::{friend_requests, profiles},
+};
 
-use super::notification_logic::NotificationCalls;
+use super::{
+    config_logic::ConfigCalls, notification_logic::NotificationCalls, saga_logic::SagaCalls,
+};
 
 pub struct FriendRequestCalls;
 pub struct FriendRequestMapper;
 pub struct FriendRequestValidation;
 
-const SECONDS_IN_A_MONTH: u64 = 30 * 24 * 60 * 60;
-const FRIEND_REQUEST_AUTO_REMOVE_DELAY: Duration = Duration::from_secs(SECONDS_IN_A_MONTH);
-
 impl FriendRequestCalls {
     pub async fn add_friend_request(
         to: Principal,
@@ -74,7 +76,11 @@ impl FriendRequestCalls {
             .update(friend_request_id, inserted_friend_request.clone())
             .await;
 
-        set_timer(FRIEND_REQUEST_AUTO_REMOVE_DELAY, move || {
+        let auto_remove_delay = Duration::from_secs(
+            ConfigCalls::get_platform_config().friend_request_auto_remove_delay,
+        );
+
+        set_timer(auto_remove_delay, move || {
             spawn(async move {
                 let _ = friend_requests().remove(friend_request_id).await;
                 NotificationCalls::notification_remove_friend_request(
@@ -97,25 +103,22 @@ impl FriendRequestCalls {
                 .add_message("You are not authorized to accept this friend request"));
         }
 
-        let (_, mut caller_profile) = profiles().get(caller()).await?;
-
-        caller_profile.references.relations.insert(
-            friend_request.requested_by,
-            RelationType::Friend.to_string(),
-        );
-
-        let (requested_by_principal, mut to_profile) =
-            profiles().get(friend_request.requested_by).await?;
-
-        to_profile
-            .references
-            .relations
-            .insert(friend_request.to, RelationType::Friend.to_string());
-
-        profiles().update(caller(), caller_profile).await?;
-        profiles()
-            .update(requested_by_principal, to_profile)
-            .await?;
+        SagaCalls::run(
+            SagaKind::AcceptFriendRequest,
+            vec![],
+            vec![
+                SagaStep::AddFriend {
+                    principal: friend_request.to,
+                    friend: friend_request.requested_by,
+                },
+                SagaStep::AddFriend {
+                    principal: friend_request.requested_by,
+                    friend: friend_request.to,
+                },
+                SagaStep::RemoveFriendRequest(friend_request_id),
+            ],
+        )
+        .await?;
 
         NotificationCalls::notification_accept_or_decline_friend_request(
             (friend_request_id, friend_request),
@@ -123,7 +126,7 @@ impl FriendRequestCalls {
         )
         .await?;
 
-        friend_requests().remove(friend_request_id).await
+        Ok(true)
     }
 
     pub async fn decline_friend_request(friend_request_id: u64) -> CanisterResult<bool> {
@@ -1,21 +1,36 @@
 use super::{
-    boost_logic::BoostCalls, history_event_logic::HistoryEventLogic,
-    notification_logic::NotificationCalls, profile_logic::ProfileCalls,
+    boost_logic::BoostCalls, config_logic::ConfigCalls, history_event_logic::HistoryEventLogic,
+    notification_logic::NotificationCalls, profile_logic::ProfileCalls, saga_logic::SagaCalls,
 };
 use crate::{
     helpers::{
+        env::{caller, print, raw_rand, set_timer, set_timer_interval, spawn, time},
         group_permission::has_permission,
+        nns_governance::list_nns_neurons_of,
+        sns_governance::{
+            get_nervous_system_parameters, list_neurons_of, DissolveState, NervousSystemParameters,
+            Neuron,
+        },
         token_balance::{
-            dip20_balance_of, dip721_balance_of, ext_balance_of, icrc_balance_of,
-            legacy_dip721_balance_of,
+            dip20_balance_of, dip721_balance_of, ext_balance_of, icrc1_balance_of,
+            icrc7_balance_of, legacy_dip721_balance_of, Account,
         },
     },
-    storage::{boosts, events, global, groups, profiles},
-    USER_GROUP_CREATION_LIMIT,
+    models::{
+        ArchivedGroup, GatedGracePeriod, GatedRemovalReport, GatingConditionCheck, GatingRule,
+        GroupEligibility, GroupGatingRule, GroupMemberCap, GroupMemberFilter, GroupMemberSort,
+        GroupNotice, GroupPurgeSummary, InviteCode, JoinGroupResult, JoinRequest, NeuronRule,
+        OwnershipTransfer, PostInviteCode, RoleRank, SagaKind, SagaStep, SnsNeuronGated, SortOrder,
+        TokenStandard, WaitlistEntry,
+    },
+    storage::{
+        archived_groups, events, gated_grace_periods, gated_removal_reports, gating_rules, global,
+        groups, invite_codes, join_requests, member_caps, notifications, ownership_transfers,
+        profiles, reports, role_ranks, task_state, token_subaccounts,
+    },
 };
 use candid::Principal;
 use catalyze_shared::{
-    boosted::BoostedFilter,
     general_structs::privacy::Privacy,
     misc::role_misc::{default_roles, read_only_permissions},
     models::{
@@ -28,27 +43,39 @@ use catalyze_shared::{
         },
         history_event::GroupRoleChangeKind,
         invite_type::InviteType,
-        neuron::{DissolveState, ListNeurons, ListNeuronsResponse},
         paged_response::PagedResponse,
         permission::{Permission, PermissionActionType, PermissionType, PostPermission},
-        privacy::{GatedType, NeuronGatedRules, TokenGated},
-        profile_with_refs::ProfileResponse,
+        privacy::{NeuronGated, TokenGated},
+        profile_with_refs::{ProfileResponse, ProfileWithRefs},
         relation_type::RelationType,
         role::Role,
         subject::{Subject, SubjectType},
         validation::{ValidateField, ValidationType},
     },
-    old_member::{InviteMemberResponse, JoinedMemberResponse},
+    old_member::{InviteMemberResponse, JoinedMemberResponse, MemberInvite},
     privacy::PrivacyType,
+    report::ReportFilter,
     time_helper::hours_to_nanoseconds,
     validator::Validator,
-    CanisterResult, Filter, StorageClient, StorageClientInsertable,
+    CanisterResult, CellStorage, Filter, StorageClient, StorageClientInsertable,
 };
-use ic_cdk::{
-    api::{call, time},
-    caller,
-};
-use std::collections::HashMap;
+use std::{collections::HashMap, future::Future, pin::Pin, time::Duration};
+
+/// The maximum number of principals the batch member calls accept
+pub const MEMBER_BATCH_LIMIT: usize = 100;
+
+/// The number of random bytes in an invite code, hex encoded
+const INVITE_CODE_BYTES: usize = 12;
+
+/// The maximum nesting of a gating rule
+pub const MAX_GATING_RULE_DEPTH: usize = 4;
+/// The maximum number of token and neuron conditions of a gating rule
+pub const MAX_GATING_RULE_CONDITIONS: usize = 10;
+
+const GATED_REVALIDATION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+const GROUP_PURGE_RETRY_DELAY: Duration = Duration::from_secs(60 * 60);
+const JOIN_REQUEST_EXPIRY_RETRY_DELAY: Duration = Duration::from_secs(60 * 60);
 
 pub struct GroupCalls;
 pub struct GroupValidation;
@@ -58,6 +85,7 @@ impl GroupCalls {
     pub async fn add_group(
         post_group: PostGroup,
         account_identifier: Option<String>,
+        gating_rule: Option<GatingRule>,
     ) -> CanisterResult<GroupResponse> {
         // Check if the group data is valid
         GroupValidation::validate_post_group(post_group.clone())?;
@@ -72,23 +100,27 @@ impl GroupCalls {
         }
 
         // Check if the caller has permission to create the group
-        GroupValidation::validate_group_privacy(&caller(), account_identifier, &post_group).await?;
+        GroupValidation::validate_group_privacy(
+            &caller(),
+            account_identifier,
+            &post_group,
+            gating_rule.as_ref(),
+        )
+        .await?;
 
-        // Get the member and add the group to the member
-        let owned = groups()
-            .filter(GroupFilter::Owner(caller()).to_vec())
-            .await?
-            .len();
+        GroupValidation::validate_group_limit(caller()).await?;
 
-        if owned >= USER_GROUP_CREATION_LIMIT {
-            return Err(ApiError::bad_request().add_message(
-                format!("You can only own {} groups", USER_GROUP_CREATION_LIMIT).as_str(),
-            ));
-        }
+        let privacy = post_group.privacy.clone();
+        let privacy_gated_type_amount = post_group.privacy_gated_type_amount;
 
         // Create and store the group
         let (new_group_id, new_group) = groups().insert(post_group.into()).await?;
 
+        match gating_rule {
+            Some(gating_rule) => Self::store_gating_rule(new_group_id, gating_rule, true),
+            None => Self::update_gating_rule(new_group_id, &privacy, privacy_gated_type_amount),
+        }
+
         // notify the reward buffer store that the group member count has changed
         global()
             .notify_group_member_count_changed(new_group_id)
@@ -126,35 +158,52 @@ impl GroupCalls {
             .cloned()
             .collect::<Vec<_>>();
 
-        let resp = groups()
-            .filter_paginated(limit, page, sort, filters)
+        let mut resp = groups()
+            .filter_paginated(limit, page, sort, filters.clone())
             .await?;
 
-        let mut boosted_groups = HashMap::new();
+        // the group store has no filter for archived groups, they are left out of the page
+        // so a page that contains archived groups holds fewer than `limit` groups
+        let archived_ids = archived_groups()
+            .filter(|_, archived| archived.purged.is_none())
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>();
+
+        if !archived_ids.is_empty() {
+            // the filters are combined, so this only reads the archived groups that match them
+            let archived = groups()
+                .filter([filters, GroupFilter::Ids(archived_ids).to_vec()].concat())
+                .await?;
 
-        for (id, _) in resp.data.clone() {
-            boosted_groups.insert(id, Self::get_boosted_group(id).await?);
+            resp.data
+                .retain(|(id, _)| !archived.iter().any(|(archived_id, _)| archived_id == id));
+            resp.total = resp.total.saturating_sub(archived.len() as _);
         }
 
+        let boosted_groups =
+            Self::get_boosted_groups_by_id(resp.data.iter().map(|(id, _)| *id).collect()).await?;
+
         resp.map(|(id, group)| {
-            GroupResponse::new(*id, group.clone(), boosted_groups.get(id).unwrap().clone())
+            GroupResponse::new(*id, group.clone(), boosted_groups.get(id).cloned())
         })
         .into_result()
     }
 
     pub async fn get_boosted_groups() -> CanisterResult<Vec<GroupResponse>> {
-        let mut result = vec![];
-
-        for (_, boosted) in BoostCalls::get_boosts_by_subject(SubjectType::Group).await? {
-            let id = *boosted.subject.get_id();
-
-            let resp = GroupResponse::from_result(
-                groups().get(id).await,
-                Self::get_boosted_group(id).await?,
-            )?;
+        let boosted_groups: HashMap<u64, Boost> =
+            BoostCalls::get_boosts_by_subject(SubjectType::Group)
+                .await?
+                .into_iter()
+                .map(|(_, boost)| (*boost.subject.get_id(), boost))
+                .collect();
 
-            result.push(resp);
-        }
+        let result = groups()
+            .get_many(boosted_groups.keys().cloned().collect())
+            .await?
+            .into_iter()
+            .map(|(id, group)| GroupResponse::new(id, group, boosted_groups.get(&id).cloned()))
+            .collect();
 
         Ok(result)
     }
@@ -163,7 +212,10 @@ impl GroupCalls {
         let groups = match query {
             Some(query) => groups().filter(GroupFilter::Name(query).to_vec()).await,
             None => groups().get_all().await,
-        }?;
+        }?
+        .into_iter()
+        .filter(|(id, _)| !archived_groups().contains_key(*id))
+        .collect::<Vec<_>>();
 
         let user_id = caller();
 
@@ -203,14 +255,26 @@ impl GroupCalls {
         })
     }
 
+    /// Updates the group, an update without `privacy_gated_type_amount` keeps the stored amount
     pub async fn edit_group(id: u64, update_group: UpdateGroup) -> CanisterResult<GroupResponse> {
         let (id, mut group) = groups().get(id).await?;
+        let privacy_gated_type_amount = update_group
+            .privacy_gated_type_amount
+            .or(group.privacy_gated_type_amount);
         group.update(update_group);
+        group.privacy_gated_type_amount = privacy_gated_type_amount;
 
-        GroupResponse::from_result(
-            groups().update(id, group).await,
-            Self::get_boosted_group(id).await?,
-        )
+        if let PrivacyType::Gated(gated_type) = &group.privacy.privacy_type {
+            GroupValidation::validate_token_standards(&GatingRule::from_gated_type(
+                gated_type.clone(),
+                privacy_gated_type_amount,
+            ))?;
+        }
+
+        let (id, group) = groups().update(id, group).await?;
+        Self::update_gating_rule(id, &group.privacy.privacy_type, privacy_gated_type_amount);
+
+        GroupResponse::from_result(Ok((id, group)), Self::get_boosted_group(id).await?)
     }
 
     pub async fn get_group_owner_and_privacy(id: u64) -> CanisterResult<(Principal, Privacy)> {
@@ -219,51 +283,280 @@ impl GroupCalls {
     }
 
     pub async fn get_groups_by_id(group_ids: Vec<u64>) -> CanisterResult<Vec<GroupResponse>> {
-        let mut result = vec![];
+        let groups = groups().get_many(group_ids).await?;
 
-        for (group_id, group) in groups().get_many(group_ids).await? {
-            let resp =
-                GroupResponse::new(group_id, group, Self::get_boosted_group(group_id).await?);
+        let boosted_groups =
+            Self::get_boosted_groups_by_id(groups.iter().map(|(id, _)| *id).collect()).await?;
 
-            result.push(resp);
-        }
+        let result = groups
+            .into_iter()
+            .map(|(id, group)| GroupResponse::new(id, group, boosted_groups.get(&id).cloned()))
+            .collect();
 
         Ok(result)
     }
 
+    /// Archives the group, it is hidden and read-only until it is restored or purged
+    /// after the `group_restore_window` of the platform config
     pub async fn delete_group(group_id: u64) -> CanisterResult<bool> {
         let (_, group) = groups().get(group_id).await?;
+        GroupValidation::validate_not_archived(group_id)?;
 
-        if let Some((boost_id, _)) = boosts()
-            .find(BoostedFilter::Subject(Subject::Group(group_id)).to_vec())
-            .await?
+        let now = time();
+        let restore_window = ConfigCalls::get_platform_config().group_restore_window();
+
+        let (_, archived) = archived_groups().insert(
+            group_id,
+            ArchivedGroup {
+                group_id,
+                owner: group.owner,
+                archived_by: caller(),
+                archived_at: now,
+                purge_at: now + Duration::from_secs(restore_window).as_nanos() as u64,
+                purged: None,
+            },
+        );
+
+        Self::schedule_group_purge(&archived);
+        Ok(true)
+    }
+
+    /// Restores an archived group, only the owner can do this before the restore window has passed
+    pub async fn restore_group(group_id: u64) -> CanisterResult<GroupResponse> {
+        let archived = archived_groups()
+            .find(group_id)
+            .ok_or_else(|| ApiError::not_found().add_message("Group is not archived"))?;
+
+        if archived.owner != caller() {
+            return Err(
+                ApiError::unauthorized().add_message("Only the owner can restore the group")
+            );
+        }
+
+        if !archived.can_restore(time()) {
+            return Err(ApiError::bad_request().add_message("The restore window has passed"));
+        }
+
+        archived_groups().remove(group_id);
+        Self::get_group(group_id).await
+    }
+
+    pub fn get_archived_groups_for(owner: Principal) -> Vec<ArchivedGroup> {
+        archived_groups()
+            .filter(|_, archived| archived.owner == owner)
+            .into_iter()
+            .map(|(_, archived)| archived)
+            .collect()
+    }
+
+    /// Schedules the purge of every archived group that is not purged yet,
+    /// timers do not survive an upgrade so this is called on `post_upgrade`
+    pub fn schedule_archived_group_purges() {
+        for (_, archived) in archived_groups().filter(|_, archived| archived.purged.is_none()) {
+            Self::schedule_group_purge(&archived);
+        }
+    }
+
+    fn schedule_group_purge(archived: &ArchivedGroup) {
+        let delay = Duration::from_nanos(archived.purge_at.saturating_sub(time()));
+        Self::schedule_group_purge_in(archived.group_id, delay);
+    }
+
+    /// A failed purge is tried again after `GROUP_PURGE_RETRY_DELAY`
+    fn schedule_group_purge_in(group_id: u64, delay: Duration) {
+        set_timer(delay, move || {
+            spawn(async move {
+                if Self::purge_group(group_id).await.is_err() {
+                    Self::schedule_group_purge_in(group_id, GROUP_PURGE_RETRY_DELAY);
+                }
+            })
+        });
+    }
+
+    /// Removes an archived group and everything that refers to it once the restore window has passed,
+    /// a group that was restored in the meantime is left alone
+    /// # Returns
+    /// * `Option<GroupPurgeSummary>` - What was removed, `None` when the group is not purged
+    /// # Note
+    /// The purge runs as a saga, when a step fails the next call resumes the journaled saga instead of starting over.
+    pub async fn purge_group(group_id: u64) -> CanisterResult<Option<GroupPurgeSummary>> {
+        let Some(mut archived) = archived_groups().find(group_id) else {
+            return Ok(None);
+        };
+
+        if archived.purged.is_some() {
+            return Ok(archived.purged);
+        }
+
+        if archived.can_restore(time()) {
+            return Ok(None);
+        }
+
+        let pending = SagaCalls::find_saga(|saga| {
+            saga.kind == SagaKind::DeleteGroup
+                && saga
+                    .steps
+                    .iter()
+                    .any(|step| matches!(step, SagaStep::RemoveGroup(id) if *id == group_id))
+        });
+
+        let steps = match pending {
+            Some((saga_id, saga)) => {
+                SagaCalls::resume_saga(saga_id).await?;
+                saga.steps
+            }
+            None => {
+                let steps = Self::get_group_purge_steps(group_id).await?;
+                SagaCalls::run(SagaKind::DeleteGroup, vec![], steps.clone()).await?;
+                steps
+            }
+        };
+
+        let summary = GroupPurgeSummary {
+            purged_at: time(),
+            ..Self::get_group_purge_summary(&steps)
+        };
+
+        archived.purged = Some(summary.clone());
+        archived_groups().insert(group_id, archived);
+
+        Self::remove_invite_codes(group_id);
+        gating_rules().remove(group_id);
+        member_caps().remove(group_id);
+        role_ranks().remove(group_id);
+        ownership_transfers().remove(group_id);
+
+        for (notification_id, _) in
+            join_requests().filter(|_, join_request| join_request.group_id == group_id)
         {
-            boosts().remove(boost_id).await?;
+            join_requests().remove(notification_id);
         }
 
-        // remove all pinned and starred from the profiles
-        let profile_list = profiles()
-            .get_many(group.get_members())
+        for (key, _) in
+            gated_grace_periods().filter(|_, grace_period| grace_period.group_id == group_id)
+        {
+            gated_grace_periods().remove(key);
+        }
+
+        Ok(Some(summary))
+    }
+
+    /// Collects everything that refers to the group, the group itself is removed last
+    /// so a failed purge can be planned again from the remaining data
+    async fn get_group_purge_steps(group_id: u64) -> CanisterResult<Vec<SagaStep>> {
+        let (_, group) = groups().get(group_id).await?;
+        let group_events = events().get_many(group.events.clone()).await?;
+
+        let subjects = [Subject::Group(group_id)]
+            .into_iter()
+            .chain(group.events.iter().map(|id| Subject::Event(*id)))
+            .collect();
+
+        let boost_ids = BoostCalls::get_boosts_by_subjects(subjects)
             .await?
-            .clone()
-            .iter_mut()
-            .map(|(id, profile)| {
-                let subject = Subject::Group(group_id);
-
-                if profile.is_starred(&subject) || profile.is_pinned(&subject) {
-                    profile.remove_starred(&subject);
-                    profile.remove_pinned(&subject);
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>();
+
+        // the starred and pinned entries of the events can only be removed from profiles that are known here
+        let mut principals = group.get_members();
+        for (_, event) in &group_events {
+            for principal in event.get_members() {
+                if !principals.contains(&principal) {
+                    principals.push(principal);
                 }
+            }
+        }
 
-                profile.remove_group(group_id);
+        let invite_notifications = group
+            .members
+            .invites
+            .iter()
+            .filter_map(|(principal, invite)| invite.notification_id.map(|id| (*principal, id)))
+            .chain(group_events.iter().flat_map(|(_, event)| {
+                event
+                    .attendees
+                    .invites
+                    .iter()
+                    .filter_map(|(principal, invite)| {
+                        invite.notification_id.map(|id| (*principal, id))
+                    })
+                    .collect::<Vec<_>>()
+            }))
+            .collect::<Vec<_>>();
 
-                (*id, profile.clone())
-            })
+        let report_ids = reports()
+            .filter(ReportFilter::GroupId(group_id).to_vec())
+            .await?
+            .into_iter()
+            .map(|(id, _)| id)
             .collect::<Vec<_>>();
 
-        profiles().update_many(profile_list).await?;
-        events().remove_many(group.events).await?;
-        groups().remove(group_id).await
+        let mut steps = vec![];
+
+        if !boost_ids.is_empty() {
+            steps.push(SagaStep::RemoveBoosts(boost_ids));
+        }
+
+        if !group.events.is_empty() {
+            steps.push(SagaStep::RemoveEventsFromProfiles {
+                event_ids: group.events.clone(),
+                principals,
+            });
+        }
+
+        // remove the group and all pinned and starred from the profiles
+        steps.push(SagaStep::RemoveGroupFromProfiles {
+            group_id,
+            principals: group.get_members(),
+        });
+
+        if !invite_notifications.is_empty() {
+            steps.push(SagaStep::RemoveNotifications(invite_notifications));
+        }
+
+        if !report_ids.is_empty() {
+            steps.push(SagaStep::RemoveReports(report_ids));
+        }
+
+        steps.push(SagaStep::RemoveEvents(group.events));
+        steps.push(SagaStep::RemoveGroup(group_id));
+
+        Ok(steps)
+    }
+
+    fn get_group_purge_summary(steps: &[SagaStep]) -> GroupPurgeSummary {
+        let mut summary = GroupPurgeSummary::default();
+        let mut profiles: Vec<Principal> = vec![];
+
+        let mut add_profiles = |principals: Vec<Principal>| {
+            for principal in principals {
+                if !profiles.contains(&principal) {
+                    profiles.push(principal);
+                }
+            }
+        };
+
+        for step in steps {
+            match step {
+                SagaStep::RemoveBoost(_) => summary.boosts += 1,
+                SagaStep::RemoveBoosts(ids) => summary.boosts += ids.len() as u64,
+                SagaStep::RemoveEvents(ids) => summary.events += ids.len() as u64,
+                SagaStep::RemoveReports(ids) => summary.reports += ids.len() as u64,
+                SagaStep::RemoveNotifications(receivers) => {
+                    summary.invite_notifications += receivers.len() as u64;
+                    add_profiles(receivers.iter().map(|(principal, _)| *principal).collect());
+                }
+                SagaStep::RemoveGroupFromProfiles { principals, .. }
+                | SagaStep::RemoveEventsFromProfiles { principals, .. } => {
+                    add_profiles(principals.clone())
+                }
+                _ => {}
+            }
+        }
+
+        summary.profiles = profiles.len() as u64;
+        summary
     }
 
     pub async fn add_wallet_to_group(
@@ -294,13 +587,27 @@ impl GroupCalls {
     }
 
     // Was add_role
+    /// The rank defaults to `RoleRank::CUSTOM`, it has to be above `member`, below `owner` and below the rank of the caller
     pub async fn add_role_to_group(
         group_id: u64,
         role_name: String,
         color: String,
         index: u64,
+        rank: Option<u64>,
     ) -> CanisterResult<Role> {
+        if RoleRank::of_default_role(&role_name).is_some() {
+            return Err(ApiError::bad_request().add_message("Default roles can not be added"));
+        }
+
+        let rank = rank.map(RoleRank).unwrap_or(RoleRank::CUSTOM);
+        if rank <= RoleRank::MEMBER || rank >= RoleRank::OWNER {
+            return Err(ApiError::bad_request()
+                .add_message("The rank has to be above the member and below the owner role"));
+        }
+
         let (id, mut group) = groups().get(group_id).await?;
+        GroupValidation::validate_outranks_rank(id, &group, caller(), rank)?;
+
         let role = Role::new(
             role_name,
             false,
@@ -310,10 +617,20 @@ impl GroupCalls {
         );
         group.members.roles.push(role.clone());
         groups().update(id, group).await?;
+
+        let mut ranks = role_ranks().find(id).unwrap_or_default();
+        ranks.ranks.insert(role.name.clone(), rank);
+        ranks.updated_at = time();
+        role_ranks().insert(id, ranks);
+
         Ok(role)
     }
 
     pub async fn remove_group_role(group_id: u64, role_name: String) -> CanisterResult<bool> {
+        if RoleRank::of_default_role(&role_name) == Some(RoleRank::OWNER) {
+            return Err(ApiError::bad_request().add_message("The owner role can not be removed"));
+        }
+
         let (group_id, mut group) = groups().get(group_id).await?;
 
         // get the index of the role
@@ -345,6 +662,12 @@ impl GroupCalls {
 
         groups().update(group_id, group).await?;
 
+        if let Some(mut ranks) = role_ranks().find(group_id) {
+            ranks.ranks.remove(&role_name);
+            ranks.updated_at = time();
+            role_ranks().insert(group_id, ranks);
+        }
+
         Ok(true)
     }
 
@@ -359,6 +682,7 @@ impl GroupCalls {
         post_permissions: Vec<PostPermission>,
     ) -> CanisterResult<bool> {
         let (id, mut group) = groups().get(group_id).await?;
+        GroupValidation::validate_outranks_role(group_id, &group, caller(), &role_name)?;
 
         // get the index of the role
         let index = group.members.roles.iter().position(|r| r.name == role_name);
@@ -377,18 +701,46 @@ impl GroupCalls {
     pub async fn join_group(
         group_id: u64,
         account_identifier: Option<String>,
-    ) -> CanisterResult<JoinedMemberResponse> {
+    ) -> CanisterResult<JoinGroupResult> {
+        let (_, group) = groups().get(group_id).await?;
+
+        // a full group puts the caller on the waitlist, private groups are capped when the request is accepted
+        if group.privacy.privacy_type != PrivacyType::Private
+            && Self::is_group_full(group_id, &group)
+        {
+            GroupValidation::validate_waitlist_join(
+                caller(),
+                group_id,
+                &group,
+                &account_identifier,
+            )
+            .await?;
+            Self::add_to_waitlist(group_id, caller(), None);
+            return Ok(JoinGroupResult::Waitlisted);
+        }
+
         GroupValidation::validate_member_join(caller(), group_id, &account_identifier).await?;
 
         let (_, mut group) = groups().get(group_id).await?;
 
+        // a private group is joined once the join request is accepted
+        if group.privacy.privacy_type == PrivacyType::Private
+            && Self::has_pending_join_request(group.clone(), caller())
+        {
+            return Ok(JoinGroupResult::Requested);
+        }
+
         group.add_member(caller());
         let join = group.members.members.get(&caller()).unwrap().clone();
         groups().update(group_id, group).await?;
 
         Self::add_group_to_profile(group_id, caller()).await?;
 
-        Ok(JoinedMemberResponse::new(caller(), join.roles, group_id))
+        Ok(JoinGroupResult::Joined(JoinedMemberResponse::new(
+            caller(),
+            join.roles,
+            group_id,
+        )))
     }
 
     // Invite a member to the group
@@ -396,6 +748,7 @@ impl GroupCalls {
         invitee_principal: Principal,
         group_id: u64,
     ) -> CanisterResult<()> {
+        GroupValidation::validate_not_archived(group_id)?;
         let (_, mut group) = groups().get(group_id).await?;
 
         // Check if the member is already in the group
@@ -465,15 +818,23 @@ impl GroupCalls {
             .ok_or(ApiError::not_found().add_message("Invite not found"))?
             .into();
 
-        NotificationCalls::notification_user_join_request_group_accept_or_decline(
-            invite,
-            accept,
-            group.get_members(),
-            Self::get_higher_role_members(group_id).await,
-        )
-        .await?;
+        Self::remove_join_request(&invite);
+
+        // an accepted request to a full group is put on the waitlist
+        let is_waitlisted = accept && Self::is_group_full(group_id, &group);
+        if is_waitlisted {
+            NotificationCalls::notification_join_waitlisted(principal, group_id, invite).await?;
+        } else {
+            NotificationCalls::notification_user_join_request_group_accept_or_decline(
+                invite,
+                accept,
+                group.get_members(),
+                Self::get_higher_role_members(group_id).await,
+            )
+            .await?;
+        }
 
-        if accept {
+        if accept && !is_waitlisted {
             group.convert_invite_to_member(principal);
         } else {
             group.remove_invite(principal);
@@ -482,6 +843,11 @@ impl GroupCalls {
 
         groups().update(group_id, group).await?;
 
+        if is_waitlisted {
+            Self::add_to_waitlist(group_id, principal, None);
+            return Ok(());
+        }
+
         // notify the reward buffer store that the group member count has changed
         global().notify_group_member_count_changed(group_id).await?;
 
@@ -493,6 +859,7 @@ impl GroupCalls {
         group_id: u64,
         accept: bool,
     ) -> CanisterResult<()> {
+        GroupValidation::validate_not_archived(group_id)?;
         let (_, mut group) = groups().get(group_id).await?;
 
         let principal = caller();
@@ -503,8 +870,11 @@ impl GroupCalls {
 
         let invite = group.members.invites.get(&principal).unwrap().clone();
 
+        // an accepted invite to a full group is put on the waitlist
+        let is_waitlisted = accept && Self::is_group_full(group_id, &group);
+
         // Add the group to the member and set the role
-        if accept {
+        if accept && !is_waitlisted {
             group.convert_invite_to_member(principal);
         } else {
             group.remove_invite(principal);
@@ -513,6 +883,13 @@ impl GroupCalls {
 
         groups().update(group_id, group.clone()).await?;
 
+        if is_waitlisted {
+            Self::add_to_waitlist(group_id, principal, None);
+            NotificationCalls::notification_join_waitlisted(principal, group_id, invite.into())
+                .await?;
+            return Ok(());
+        }
+
         NotificationCalls::notification_owner_join_request_group_accept_or_decline(
             principal,
             invite.into(),
@@ -543,6 +920,9 @@ impl GroupCalls {
             return Err(ApiError::bad_request().add_message("Role does not exist"));
         }
 
+        GroupValidation::validate_outranks_role(group_id, &group, caller(), &role)?;
+        GroupValidation::validate_outranks_member(group_id, &group, caller(), member_principal)?;
+
         let member = group.members.members.get_mut(&member_principal);
         if member.is_none() {
             return Err(ApiError::bad_request().add_message("Member is not in the group"));
@@ -595,6 +975,9 @@ impl GroupCalls {
             return Err(ApiError::bad_request().add_message("Role does not exist"));
         }
 
+        GroupValidation::validate_outranks_role(group_id, &group, caller(), &role)?;
+        GroupValidation::validate_outranks_member(group_id, &group, caller(), member_principal)?;
+
         let member = group.members.members.get_mut(&member_principal);
         if member.is_none() {
             return Err(ApiError::bad_request().add_message("Member is not in the group"));
@@ -655,6 +1038,84 @@ impl GroupCalls {
         Ok(result)
     }
 
+    pub async fn get_group_members_paged(
+        group_id: u64,
+        limit: usize,
+        page: usize,
+        filters: Vec<GroupMemberFilter>,
+        sort: GroupMemberSort,
+    ) -> CanisterResult<PagedResponse<JoinedMemberResponse>> {
+        let members = Self::get_group_members_filtered(group_id, filters, sort).await?;
+        PagedResponse::new(page, limit, members).into_result()
+    }
+
+    /// Returns the group members that match all filters in the given order,
+    /// the profiles are only read when filtering or sorting on the name
+    pub async fn get_group_members_filtered(
+        group_id: u64,
+        filters: Vec<GroupMemberFilter>,
+        sort: GroupMemberSort,
+    ) -> CanisterResult<Vec<JoinedMemberResponse>> {
+        let (_, group) = groups().get(group_id).await?;
+
+        let member_profiles: HashMap<Principal, ProfileWithRefs> =
+            if sort.needs_profile() || filters.iter().any(|f| f.needs_profile()) {
+                profiles()
+                    .get_many(group.get_members())
+                    .await?
+                    .into_iter()
+                    .collect()
+            } else {
+                HashMap::new()
+            };
+
+        let mut members = group
+            .members
+            .members
+            .into_iter()
+            .filter(|(principal, member)| {
+                filters.iter().all(|filter| {
+                    filter.is_match(
+                        &member.roles,
+                        member.created_at,
+                        member_profiles.get(principal),
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let order = match &sort {
+            GroupMemberSort::JoinedOn(order) | GroupMemberSort::Name(order) => order.clone(),
+        };
+
+        members.sort_by(|(a_principal, a), (b_principal, b)| {
+            let ordering = match &sort {
+                GroupMemberSort::JoinedOn(_) => a.created_at.cmp(&b.created_at),
+                GroupMemberSort::Name(_) => {
+                    let name = |principal| {
+                        member_profiles
+                            .get(principal)
+                            .map(|profile| profile.metadata.display_name.to_lowercase())
+                            .unwrap_or_default()
+                    };
+                    name(a_principal).cmp(&name(b_principal))
+                }
+            };
+
+            match order {
+                SortOrder::Asc => ordering,
+                SortOrder::Desc => ordering.reverse(),
+            }
+        });
+
+        let result = members
+            .into_iter()
+            .map(|(principal, member)| JoinedMemberResponse::new(principal, member.roles, group_id))
+            .collect();
+
+        Ok(result)
+    }
+
     pub async fn get_group_member_with_profile(
         principal: Principal,
         group_id: u64,
@@ -755,6 +1216,7 @@ impl GroupCalls {
     }
 
     pub async fn leave_group(group_id: u64) -> CanisterResult<()> {
+        GroupValidation::validate_not_archived(group_id)?;
         let (_, mut group) = groups().get(group_id).await?;
 
         // Check if the member is in the group
@@ -773,10 +1235,13 @@ impl GroupCalls {
         Self::remove_group_from_profile(group_id, caller()).await?;
 
         NotificationCalls::notification_leave_group(group.get_members(), group_id).await;
+
+        Self::try_admit_from_waitlist(group_id).await;
         Ok(())
     }
 
     pub async fn remove_invite(group_id: u64) -> CanisterResult<()> {
+        GroupValidation::validate_not_archived(group_id)?;
         let (_, mut group) = groups().get(group_id).await?;
 
         // Check if the member is in the group
@@ -816,6 +1281,8 @@ impl GroupCalls {
             return Err(ApiError::bad_request().add_message("Member is not in the group"));
         }
 
+        GroupValidation::validate_outranks_member(group_id, &group, caller(), principal)?;
+
         let roles = group.members.members.get(&principal).unwrap().roles.clone();
 
         // Remove the group from the member
@@ -830,50 +1297,235 @@ impl GroupCalls {
         )
         .await;
 
+        Self::try_admit_from_waitlist(group_id).await;
+
         Ok(())
     }
 
-    pub async fn remove_member_invite_from_group(
-        principal: Principal,
+    /// Batch variant of `invite_to_group`
+    /// # Returns
+    /// * `Vec<(Principal, CanisterResult<()>)>` - The result per invitee
+    pub async fn invite_many_to_group(
+        invitees: Vec<Principal>,
         group_id: u64,
-    ) -> CanisterResult<()> {
+    ) -> CanisterResult<Vec<(Principal, CanisterResult<()>)>> {
+        GroupValidation::validate_batch_size(&invitees)?;
+        GroupValidation::validate_not_archived(group_id)?;
         let (_, mut group) = groups().get(group_id).await?;
 
-        // Check if the member is in the group
-        if !group.is_invited(principal) {
-            return Err(ApiError::bad_request().add_message("Member is not invited to the group"));
+        // Check if the group is invite only
+        if group.privacy.privacy_type == PrivacyType::InviteOnly {
+            return Err(ApiError::bad_request().add_message("Group is invite only"));
         }
 
-        let invite = group
-            .members
-            .invites
-            .get(&principal)
-            .cloned()
-            .map(|k| k.into());
+        let mut results = vec![];
+        let mut invites = vec![];
 
-        // Remove the group from the member
-        group.remove_invite(principal);
-        groups().update(group_id, group).await?;
+        for invitee in Self::dedup_principals(invitees) {
+            let result = if group.is_member(invitee) {
+                Err(ApiError::bad_request().add_message("Member is already in the group"))
+            } else if group.is_invited(invitee) {
+                Err(ApiError::bad_request().add_message("Member is already invited to the group"))
+            } else {
+                invites.push(InviteMemberResponse::new(invitee, None, group_id));
+                Ok(())
+            };
 
-        Self::remove_group_from_profile(group_id, principal).await?;
+            results.push((invitee, result));
+        }
 
-        NotificationCalls::notification_remove_group_invite(
-            InviteMemberResponse::new(principal, invite, group_id),
+        if invites.is_empty() {
+            return Ok(results);
+        }
+
+        let notification_ids = NotificationCalls::notification_owner_join_request_group_many(
+            invites,
             Self::get_higher_role_members(group_id).await,
         )
-        .await;
+        .await?;
 
-        Ok(())
-    }
+        for (invitee, result) in results.iter_mut() {
+            if result.is_err() {
+                continue;
+            }
 
-    pub async fn get_group_invites(group_id: u64) -> CanisterResult<Vec<InviteMemberResponse>> {
-        let (_, group) = groups().get(group_id).await?;
+            match notification_ids.get(invitee) {
+                Some(notification_id) => {
+                    group.add_invite(*invitee, InviteType::OwnerRequest, Some(*notification_id))
+                }
+                None => *result = Err(ApiError::not_found().add_message("Profile not found")),
+            }
+        }
 
-        let members = group
-            .members
-            .invites
-            .into_iter()
-            .map(|(principal, invite)| {
+        groups().update(group_id, group).await?;
+
+        Self::add_group_to_profiles(group_id, Self::get_succeeded(&results)).await?;
+
+        Ok(results)
+    }
+
+    /// Batch variant of `remove_member_from_group`
+    /// # Returns
+    /// * `Vec<(Principal, CanisterResult<()>)>` - The result per member
+    pub async fn remove_many_members_from_group(
+        principals: Vec<Principal>,
+        group_id: u64,
+    ) -> CanisterResult<Vec<(Principal, CanisterResult<()>)>> {
+        GroupValidation::validate_batch_size(&principals)?;
+        let (_, mut group) = groups().get(group_id).await?;
+
+        let caller = caller();
+        let mut results = vec![];
+        let mut removed = vec![];
+
+        for principal in Self::dedup_principals(principals) {
+            let result =
+                match group.members.members.get(&principal).cloned() {
+                    None => Err(ApiError::bad_request().add_message("Member is not in the group")),
+                    Some(_) if principal == group.owner => {
+                        Err(ApiError::bad_request().add_message("The owner can not be removed"))
+                    }
+                    Some(member) => GroupValidation::validate_outranks_member(
+                        group_id, &group, caller, principal,
+                    )
+                    .map(|_| {
+                        group.remove_member(principal);
+                        removed.push(JoinedMemberResponse::new(principal, member.roles, group_id));
+                    }),
+                };
+
+            results.push((principal, result));
+        }
+
+        if removed.is_empty() {
+            return Ok(results);
+        }
+
+        groups().update(group_id, group).await?;
+
+        Self::remove_group_from_profiles(group_id, Self::get_succeeded(&results)).await?;
+
+        NotificationCalls::notification_remove_group_members(
+            removed,
+            Self::get_higher_role_members(group_id).await,
+        )
+        .await;
+
+        Self::try_admit_from_waitlist(group_id).await;
+
+        Ok(results)
+    }
+
+    /// Batch variant of `add_group_role_to_member`
+    /// # Returns
+    /// * `Vec<(Principal, CanisterResult<()>)>` - The result per member
+    pub async fn add_group_role_to_many_members(
+        role: String,
+        principals: Vec<Principal>,
+        group_id: u64,
+    ) -> CanisterResult<Vec<(Principal, CanisterResult<()>)>> {
+        GroupValidation::validate_batch_size(&principals)?;
+        let (_, mut group) = groups().get(group_id).await?;
+
+        let mut roles = default_roles();
+        roles.append(&mut group.members.roles.clone());
+        // Check if the role exists
+        if !roles.iter().any(|r| r.name == role) {
+            return Err(ApiError::bad_request().add_message("Role does not exist"));
+        }
+
+        let caller = caller();
+        GroupValidation::validate_outranks_role(group_id, &group, caller, &role)?;
+
+        let mut results = vec![];
+
+        for principal in Self::dedup_principals(principals) {
+            let result =
+                GroupValidation::validate_outranks_member(group_id, &group, caller, principal)
+                    .and_then(|_| match group.members.members.get_mut(&principal) {
+                        Some(member) => {
+                            member.set_role(role.clone());
+                            Ok(())
+                        }
+                        None => {
+                            Err(ApiError::bad_request().add_message("Member is not in the group"))
+                        }
+                    });
+
+            results.push((principal, result));
+        }
+
+        let assigned = Self::get_succeeded(&results);
+
+        if assigned.is_empty() {
+            return Ok(results);
+        }
+
+        groups().update(group_id, group).await?;
+
+        HistoryEventLogic::send_many(
+            group_id,
+            assigned
+                .iter()
+                .map(|principal| (*principal, vec![role.clone()]))
+                .collect(),
+            GroupRoleChangeKind::Replace,
+        )
+        .await?;
+
+        NotificationCalls::notification_change_group_member_roles(
+            assigned
+                .into_iter()
+                .map(|principal| JoinedMemberResponse::new(principal, vec![role.clone()], group_id))
+                .collect(),
+            Self::get_higher_role_members(group_id).await,
+        )
+        .await;
+
+        Ok(results)
+    }
+
+    pub async fn remove_member_invite_from_group(
+        principal: Principal,
+        group_id: u64,
+    ) -> CanisterResult<()> {
+        let (_, mut group) = groups().get(group_id).await?;
+
+        // Check if the member is in the group
+        if !group.is_invited(principal) {
+            return Err(ApiError::bad_request().add_message("Member is not invited to the group"));
+        }
+
+        let invite = group
+            .members
+            .invites
+            .get(&principal)
+            .cloned()
+            .map(|k| k.into());
+
+        // Remove the group from the member
+        group.remove_invite(principal);
+        groups().update(group_id, group).await?;
+
+        Self::remove_group_from_profile(group_id, principal).await?;
+
+        NotificationCalls::notification_remove_group_invite(
+            InviteMemberResponse::new(principal, invite, group_id),
+            Self::get_higher_role_members(group_id).await,
+        )
+        .await;
+
+        Ok(())
+    }
+
+    pub async fn get_group_invites(group_id: u64) -> CanisterResult<Vec<InviteMemberResponse>> {
+        let (_, group) = groups().get(group_id).await?;
+
+        let members = group
+            .members
+            .invites
+            .into_iter()
+            .map(|(principal, invite)| {
                 InviteMemberResponse::new(principal, Some(invite.into()), group_id)
             })
             .collect();
@@ -908,12 +1560,1111 @@ impl GroupCalls {
         Ok(result)
     }
 
-    async fn get_boosted_group(id: u64) -> CanisterResult<Option<Boost>> {
-        let boost = BoostCalls::get_boost_by_subject(Subject::Group(id))
+    /// Stores the join request so it expires after the `join_request_expiry` of the platform config
+    fn add_join_request(group_id: u64, principal: Principal, notification_id: u64) {
+        let now = time();
+        let expiry = ConfigCalls::get_platform_config().join_request_expiry();
+
+        let (_, join_request) = join_requests().insert(
+            notification_id,
+            JoinRequest {
+                group_id,
+                principal,
+                notification_id,
+                created_at: now,
+                expires_at: now + Duration::from_secs(expiry).as_nanos() as u64,
+            },
+        );
+
+        Self::schedule_join_request_expiry(&join_request);
+    }
+
+    fn remove_join_request(invite: &MemberInvite) {
+        if let Some(notification_id) = invite.notification_id {
+            join_requests().remove(notification_id);
+        }
+    }
+
+    /// Schedules the expiry of every pending join request,
+    /// timers do not survive an upgrade so this is called on `post_upgrade`
+    pub fn schedule_join_request_expiries() {
+        for (_, join_request) in join_requests().get_all() {
+            Self::schedule_join_request_expiry(&join_request);
+        }
+
+        // the group canister can't be called during the upgrade itself
+        if task_state()
+            .get()
+            .unwrap_or_default()
+            .join_requests_backfilled_at
+            .is_none()
+        {
+            set_timer(Duration::ZERO, || {
+                spawn(async {
+                    let _ = Self::backfill_join_requests().await;
+                })
+            });
+        }
+    }
+
+    /// Stores a join request for the pending requests that were made before join requests expired,
+    /// they expire a full `join_request_expiry` after the backfill. The backfill runs once,
+    /// it is marked as done in the `TaskState` after all groups are read.
+    pub async fn backfill_join_requests() -> CanisterResult<()> {
+        if task_state()
+            .get()
+            .unwrap_or_default()
+            .join_requests_backfilled_at
+            .is_some()
+        {
+            return Ok(());
+        }
+
+        for (group_id, group) in groups().get_all().await? {
+            for (principal, invite) in group.members.invites {
+                if invite.invite_type != InviteType::UserRequest {
+                    continue;
+                }
+
+                if let Some(notification_id) = invite.notification_id {
+                    if !join_requests().contains_key(notification_id) {
+                        Self::add_join_request(group_id, principal, notification_id);
+                    }
+                }
+            }
+        }
+
+        // read again, the other tasks can have changed the state during the calls
+        let mut state = task_state().get().unwrap_or_default();
+        state.join_requests_backfilled_at = Some(time());
+        task_state().set(state)?;
+
+        Ok(())
+    }
+
+    fn schedule_join_request_expiry(join_request: &JoinRequest) {
+        let delay = Duration::from_nanos(join_request.expires_at.saturating_sub(time()));
+        Self::schedule_join_request_expiry_in(join_request.notification_id, delay);
+    }
+
+    /// A failed expiry is tried again after `JOIN_REQUEST_EXPIRY_RETRY_DELAY`
+    fn schedule_join_request_expiry_in(notification_id: u64, delay: Duration) {
+        set_timer(delay, move || {
+            spawn(async move {
+                if Self::expire_join_request(notification_id).await.is_err() {
+                    Self::schedule_join_request_expiry_in(
+                        notification_id,
+                        JOIN_REQUEST_EXPIRY_RETRY_DELAY,
+                    );
+                }
+            })
+        });
+    }
+
+    /// Declines a join request that was not answered in time, the requester is notified
+    /// and the notification is removed from the members that could have answered it and deleted
+    pub async fn expire_join_request(notification_id: u64) -> CanisterResult<()> {
+        let Some(join_request) = join_requests().find(notification_id) else {
+            return Ok(());
+        };
+
+        if join_request.expires_at > time() {
+            return Ok(());
+        }
+
+        let JoinRequest {
+            group_id,
+            principal,
+            ..
+        } = join_request;
+
+        let (_, group) = groups().get(group_id).await?;
+
+        // the request was answered or replaced in the meantime
+        let is_pending = group.members.invites.get(&principal).is_some_and(|invite| {
+            invite.invite_type == InviteType::UserRequest
+                && invite.notification_id == Some(notification_id)
+        });
+
+        if !is_pending {
+            join_requests().remove(notification_id);
+            return Ok(());
+        }
+
+        let higher_role_members = Self::get_higher_role_members(group_id).await;
+
+        Self::accept_or_decline_user_request_group_invite(principal, group_id, false).await?;
+
+        let profile_list = profiles()
+            .get_many(higher_role_members)
             .await?
-            .map(|x| x.1);
+            .into_iter()
+            .map(|(id, mut profile)| {
+                profile
+                    .references
+                    .notifications
+                    .remove_many(vec![notification_id]);
+                (id, profile)
+            })
+            .collect::<Vec<_>>();
+
+        profiles().update_many(profile_list).await?;
+        notifications().remove(notification_id).await?;
+
+        // removed last, a request whose expiry failed is still stored and expired again
+        join_requests().remove(notification_id);
+
+        Ok(())
+    }
+
+    /// The rule members of the group have to meet, `None` when the group is not gated.
+    /// Groups without a stored rule have to meet `privacy_gated_type_amount` (and at least one)
+    /// of the canisters of their `GatedType`.
+    pub fn get_gating_rule(group_id: u64, group: &GroupWithMembers) -> Option<GatingRule> {
+        let PrivacyType::Gated(gated_type) = &group.privacy.privacy_type else {
+            return None;
+        };
+
+        let gating_rule = match gating_rules().find(group_id) {
+            Some(group_gating_rule) => group_gating_rule.rule,
+            None => {
+                GatingRule::from_gated_type(gated_type.clone(), group.privacy_gated_type_amount)
+            }
+        };
+
+        Some(gating_rule)
+    }
+
+    pub async fn get_group_gating_rule(group_id: u64) -> CanisterResult<Option<GatingRule>> {
+        let (_, group) = groups().get(group_id).await?;
+        Ok(Self::get_gating_rule(group_id, &group))
+    }
+
+    /// Checks every token and neuron condition of the gating rule of the group for the caller without joining,
+    /// all conditions are checked so the caller can see everything they are missing
+    pub async fn check_group_eligibility(
+        group_id: u64,
+        account_identifier: Option<String>,
+    ) -> CanisterResult<GroupEligibility> {
+        let (_, group) = groups().get(group_id).await?;
+
+        let Some(gating_rule) = Self::get_gating_rule(group_id, &group) else {
+            return Err(ApiError::bad_request().add_message("Group is not gated"));
+        };
+
+        let principal = caller();
+        let mut checks = vec![];
+
+        for condition in gating_rule.get_conditions() {
+            let check = match condition {
+                GatingRule::Token(token) => {
+                    GroupValidation::check_token_gated(principal, &account_identifier, token).await
+                }
+                GatingRule::Neuron(neuron) => GroupValidation::check_neuron_gated(
+                    neuron.governance_canister,
+                    GroupValidation::count_eligible_nns_neurons(principal, neuron).await,
+                ),
+                GatingRule::SnsNeuron(neuron) => GroupValidation::check_neuron_gated(
+                    neuron.governance_canister,
+                    GroupValidation::count_eligible_sns_neurons(principal, neuron).await,
+                ),
+                // `get_conditions` only returns tokens and neurons
+                _ => continue,
+            };
+
+            checks.push(check);
+        }
+
+        let eligible = gating_rule.is_met_by(&mut checks.iter().map(|check| check.passed));
+
+        Ok(GroupEligibility {
+            group_id,
+            eligible,
+            checks,
+        })
+    }
+
+    /// Replaces the gating rule of a gated group, `None` replaces a custom rule with the rule
+    /// where `privacy_gated_type_amount` of the canisters of the `GatedType` have to be met
+    pub async fn set_group_gating_rule(
+        group_id: u64,
+        gating_rule: Option<GatingRule>,
+    ) -> CanisterResult<Option<GatingRule>> {
+        GroupValidation::validate_not_archived(group_id)?;
+        let (_, group) = groups().get(group_id).await?;
+
+        if !matches!(group.privacy.privacy_type, PrivacyType::Gated(_)) {
+            return Err(
+                ApiError::bad_request().add_message("Only gated groups can have a gating rule")
+            );
+        }
+
+        match gating_rule {
+            Some(gating_rule) => {
+                GroupValidation::validate_gating_rule(&gating_rule)?;
+                Self::store_gating_rule(group_id, gating_rule, true);
+            }
+            // the derived rule keeps the group in the index of gated groups
+            None => {
+                gating_rules().remove(group_id);
+                Self::update_gating_rule(
+                    group_id,
+                    &group.privacy.privacy_type,
+                    group.privacy_gated_type_amount,
+                );
+            }
+        }
+
+        Ok(Self::get_gating_rule(group_id, &group))
+    }
+
+    fn store_gating_rule(group_id: u64, gating_rule: GatingRule, is_custom: bool) {
+        gating_rules().insert(
+            group_id,
+            GroupGatingRule {
+                rule: gating_rule,
+                is_custom,
+                updated_at: time(),
+            },
+        );
+    }
+
+    /// Keeps the stored rule in line with the `GatedType` of the group, custom rules are kept
+    /// as long as the group stays gated
+    fn update_gating_rule(
+        group_id: u64,
+        privacy_type: &PrivacyType,
+        privacy_gated_type_amount: Option<u64>,
+    ) {
+        let PrivacyType::Gated(gated_type) = privacy_type else {
+            gating_rules().remove(group_id);
+            return;
+        };
+
+        if gating_rules()
+            .find(group_id)
+            .is_some_and(|group_gating_rule| group_gating_rule.is_custom)
+        {
+            return;
+        }
+
+        Self::store_gating_rule(
+            group_id,
+            GatingRule::from_gated_type(gated_type.clone(), privacy_gated_type_amount),
+            false,
+        );
+    }
+
+    /// Re-checks the gate every `GATED_REVALIDATION_INTERVAL`,
+    /// timers do not survive an upgrade so this is called on `init` and `post_upgrade`
+    pub fn start_gated_revalidation_timer() {
+        set_timer_interval(GATED_REVALIDATION_INTERVAL, || {
+            spawn(async {
+                let _ = Self::revalidate_gated_members().await;
+            })
+        });
+    }
+
+    /// Stores the rule of every gated group made before gating rules were stored, the stored rules
+    /// index the gated groups so the re-validation only reads the groups of its batch.
+    /// The backfill runs once, it is marked as done in the `TaskState` after all groups are read.
+    pub async fn backfill_gating_rules() -> CanisterResult<()> {
+        if task_state()
+            .get()
+            .unwrap_or_default()
+            .gating_rules_backfilled_at
+            .is_some()
+        {
+            return Ok(());
+        }
+
+        for (group_id, group) in groups().get_all().await? {
+            if !gating_rules().contains_key(group_id) {
+                Self::update_gating_rule(
+                    group_id,
+                    &group.privacy.privacy_type,
+                    group.privacy_gated_type_amount,
+                );
+            }
+        }
+
+        let mut state = task_state().get().unwrap_or_default();
+        state.gating_rules_backfilled_at = Some(time());
+        task_state().set(state)?;
+
+        Ok(())
+    }
+
+    /// Checks the next `gated_revalidation_batch_size` members of gated groups against the gate of their group,
+    /// the run continues after the member stored as cursor in the `TaskState` and reads only the groups it needs.
+    /// Members that no longer meet it are notified and get a grace period, when they still don't meet it
+    /// once the grace period ended they are removed and the owner gets a report of the removed members.
+    /// # Returns
+    /// * `Vec<GatedRemovalReport>` - The reports of the groups members were removed from
+    /// # Note
+    /// The owner is never removed. EXT tokens need the account identifier of the member,
+    /// which is only known when joining, so EXT tokens are assumed to be held.
+    /// Members whose outcome depends on a failed ledger or governance call are left as they are
+    /// and checked again in the next run.
+    pub async fn revalidate_gated_members() -> CanisterResult<Vec<GatedRemovalReport>> {
+        Self::backfill_gating_rules().await?;
+
+        let config = ConfigCalls::get_platform_config();
+        let batch_size = config.gated_revalidation_batch_size() as usize;
+        let now = time();
+
+        let cursor = task_state()
+            .get()
+            .unwrap_or_default()
+            .gated_revalidation_cursor;
+
+        let gated_group_ids = gating_rules()
+            .filter(|id, _| {
+                cursor.map_or(true, |(cursor_id, _)| *id >= cursor_id)
+                    && !archived_groups().contains_key(*id)
+            })
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>();
+
+        // one member more than the batch is read to know if the run reached the last member
+        let mut gated_groups: HashMap<u64, GroupWithMembers> = HashMap::new();
+        let mut batch = vec![];
+        for group_ids in gated_group_ids.chunks(batch_size.max(1)) {
+            let mut fetched = groups().get_many(group_ids.to_vec()).await?;
+            fetched.sort_by_key(|(id, _)| *id);
+
+            for (group_id, group) in fetched {
+                if !matches!(group.privacy.privacy_type, PrivacyType::Gated(_)) {
+                    continue;
+                }
+
+                // grace periods of members that left the group
+                for (key, grace_period) in gated_grace_periods()
+                    .filter(|_, grace_period| grace_period.group_id == group_id)
+                {
+                    if grace_period.principal == group.owner
+                        || !group.is_member(grace_period.principal)
+                    {
+                        gated_grace_periods().remove(key);
+                    }
+                }
+
+                let mut principals = group
+                    .members
+                    .members
+                    .keys()
+                    .filter(|principal| **principal != group.owner)
+                    .map(|principal| (group_id, *principal))
+                    .filter(|member| cursor.map_or(true, |cursor| *member > cursor))
+                    .collect::<Vec<_>>();
+                principals.sort();
+                batch.append(&mut principals);
+
+                gated_groups.insert(group_id, group);
+            }
+
+            if batch.len() > batch_size {
+                break;
+            }
+        }
+
+        // the next run starts over when the batch reached the last member
+        let next_cursor = if batch.len() > batch_size {
+            batch.truncate(batch_size);
+            batch.last().copied()
+        } else {
+            None
+        };
+        let mut state = task_state().get().unwrap_or_default();
+        state.gated_revalidation_cursor = next_cursor;
+        task_state().set(state)?;
+
+        if next_cursor.is_none() {
+            // grace periods of groups that are no longer gated
+            for (key, grace_period) in gated_grace_periods().get_all() {
+                if !gating_rules().contains_key(grace_period.group_id)
+                    || archived_groups().contains_key(grace_period.group_id)
+                {
+                    gated_grace_periods().remove(key);
+                }
+            }
+        }
+
+        let mut expired: HashMap<u64, Vec<Principal>> = HashMap::new();
+
+        for (group_id, principal) in batch {
+            let Some(group) = gated_groups.get(&group_id) else {
+                continue;
+            };
+
+            let Some(gating_rule) = Self::get_gating_rule(group_id, group) else {
+                continue;
+            };
+
+            let key = GatedGracePeriod::key(group_id, principal);
+            let gating_rule = gating_rule.with_ext_as_met();
+
+            let mut results = vec![];
+            for condition in gating_rule.get_conditions() {
+                results
+                    .push(GroupValidation::try_meets_condition(principal, &None, condition).await);
+            }
+
+            match gating_rule.try_is_met_by(&mut results.into_iter()) {
+                Some(true) => {
+                    gated_grace_periods().remove(key);
+                    continue;
+                }
+                None => continue,
+                Some(false) => {}
+            }
+
+            match gated_grace_periods().find(key.clone()) {
+                None => {
+                    let expires_at =
+                        now + Duration::from_secs(config.gated_grace_period()).as_nanos() as u64;
+
+                    let notification_id =
+                        NotificationCalls::notification_gated_membership_grace_period(
+                            principal, group_id, expires_at,
+                        )
+                        .await
+                        .ok();
+
+                    gated_grace_periods().insert(
+                        key,
+                        GatedGracePeriod {
+                            group_id,
+                            principal,
+                            notification_id,
+                            created_at: now,
+                            expires_at,
+                        },
+                    );
+                }
+                Some(grace_period) if grace_period.expires_at <= now => {
+                    gated_grace_periods().remove(key);
+                    expired.entry(group_id).or_default().push(principal);
+                }
+                Some(_) => {}
+            }
+        }
+
+        let mut reports = vec![];
+        for (group_id, principals) in expired {
+            if let Some(report) = Self::remove_gated_members(group_id, principals).await? {
+                reports.push(report);
+            }
+        }
+
+        Ok(reports)
+    }
+
+    async fn remove_gated_members(
+        group_id: u64,
+        principals: Vec<Principal>,
+    ) -> CanisterResult<Option<GatedRemovalReport>> {
+        let (_, mut group) = groups().get(group_id).await?;
+
+        let mut removed = vec![];
+        for principal in principals {
+            if let Some(member) = group.members.members.get(&principal).cloned() {
+                group.remove_member(principal);
+                removed.push(JoinedMemberResponse::new(principal, member.roles, group_id));
+            }
+        }
+
+        if removed.is_empty() {
+            return Ok(None);
+        }
+
+        groups().update(group_id, group.clone()).await?;
+
+        let principals = removed
+            .iter()
+            .map(|member| member.principal)
+            .collect::<Vec<_>>();
+        Self::remove_group_from_profiles(group_id, principals.clone()).await?;
+
+        // the removed members and the owner are notified of every removal
+        NotificationCalls::notification_remove_group_members(removed, vec![group.owner]).await;
+
+        // notify the reward buffer store that the group member count has changed
+        global().notify_group_member_count_changed(group_id).await?;
+
+        let (_, report) = gated_removal_reports().insert_next(GatedRemovalReport {
+            group_id,
+            removed: principals,
+            created_at: time(),
+        });
+
+        Self::try_admit_from_waitlist(group_id).await;
+
+        Ok(Some(report))
+    }
+
+    pub fn get_gated_removal_reports(group_id: u64) -> Vec<GatedRemovalReport> {
+        gated_removal_reports()
+            .filter(|_, report| report.group_id == group_id)
+            .into_iter()
+            .map(|(_, report)| report)
+            .collect()
+    }
+
+    pub fn get_gated_grace_periods_for(principal: Principal) -> Vec<GatedGracePeriod> {
+        gated_grace_periods()
+            .filter(|_, grace_period| grace_period.principal == principal)
+            .into_iter()
+            .map(|(_, grace_period)| grace_period)
+            .collect()
+    }
+
+    /// Proposes `new_owner` as the owner of the group, the transfer completes when they accept it
+    pub async fn transfer_group_ownership(
+        group_id: u64,
+        new_owner: Principal,
+    ) -> CanisterResult<OwnershipTransfer> {
+        GroupValidation::validate_not_archived(group_id)?;
+        let (_, group) = groups().get(group_id).await?;
+
+        if group.owner != caller() {
+            return Err(
+                ApiError::unauthorized().add_message("Only the owner can transfer the group")
+            );
+        }
+
+        if new_owner == caller() {
+            return Err(ApiError::bad_request().add_message("You already own this group"));
+        }
+
+        if !group.is_member(new_owner) {
+            return Err(ApiError::bad_request().add_message("New owner is not in the group"));
+        }
+
+        let (_, transfer) = ownership_transfers().insert(
+            group_id,
+            OwnershipTransfer {
+                group_id,
+                from: caller(),
+                to: new_owner,
+                created_at: time(),
+            },
+        );
+
+        // the transfer is stored, a failed notification does not undo it
+        let _ = NotificationCalls::notification_group_notice(
+            vec![new_owner],
+            group_id,
+            GroupNotice::OwnershipTransferProposed { from: caller() },
+        )
+        .await;
+
+        Ok(transfer)
+    }
+
+    /// Completes a pending transfer, the new owner gets the owner role and the previous owner the admin role
+    pub async fn accept_group_ownership_transfer(group_id: u64) -> CanisterResult<GroupResponse> {
+        let transfer = Self::get_group_ownership_transfer(group_id)?;
+        GroupValidation::validate_not_archived(group_id)?;
+
+        if transfer.to != caller() {
+            return Err(ApiError::unauthorized()
+                .add_message("The ownership transfer is not addressed to you"));
+        }
+
+        let (_, mut group) = groups().get(group_id).await?;
+
+        // the transfer is stale when the owner changed or the new owner left in the meantime
+        if group.owner != transfer.from || !group.is_member(transfer.to) {
+            ownership_transfers().remove(group_id);
+            return Err(
+                ApiError::bad_request().add_message("The ownership transfer is no longer valid")
+            );
+        }
+
+        GroupValidation::validate_group_limit(transfer.to).await?;
+
+        group.owner = transfer.to;
+
+        for (principal, role) in [(transfer.to, "owner"), (transfer.from, "admin")] {
+            if let Some(member) = group.members.members.get_mut(&principal) {
+                member.set_role(role.to_string());
+            }
+        }
+
+        groups().update(group_id, group.clone()).await?;
+        ownership_transfers().remove(group_id);
+
+        // the ownership is committed, a failed history event must not report the transfer as failed
+        for (principal, role) in [(transfer.to, "owner"), (transfer.from, "admin")] {
+            let _ = HistoryEventLogic::send(
+                group_id,
+                principal,
+                vec![role.to_string()],
+                GroupRoleChangeKind::Replace,
+            )
+            .await;
+
+            NotificationCalls::notification_change_group_member_role(
+                JoinedMemberResponse::new(principal, vec![role.to_string()], group_id),
+                vec![transfer.from, transfer.to],
+            )
+            .await;
+        }
+
+        GroupResponse::from_result(
+            Ok((group_id, group)),
+            Self::get_boosted_group(group_id).await?,
+        )
+    }
+
+    /// Removes a pending transfer, can be done by the owner and by the proposed new owner
+    pub fn decline_group_ownership_transfer(group_id: u64) -> CanisterResult<()> {
+        let transfer = Self::get_group_ownership_transfer(group_id)?;
+
+        if ![transfer.from, transfer.to].contains(&caller()) {
+            return Err(ApiError::unauthorized()
+                .add_message("The ownership transfer is not addressed to you"));
+        }
+
+        ownership_transfers().remove(group_id);
+        Ok(())
+    }
+
+    pub fn get_group_ownership_transfer(group_id: u64) -> CanisterResult<OwnershipTransfer> {
+        ownership_transfers().get(group_id)
+    }
+
+    pub fn get_ownership_transfers_for(principal: Principal) -> Vec<OwnershipTransfer> {
+        ownership_transfers()
+            .filter(|_, transfer| transfer.to == principal)
+            .into_iter()
+            .map(|(_, transfer)| transfer)
+            .collect()
+    }
+
+    /// Creates a shareable code to join the group, the role defaults to `member`
+    pub async fn create_invite_code(
+        group_id: u64,
+        post_invite_code: PostInviteCode,
+    ) -> CanisterResult<InviteCode> {
+        GroupValidation::validate_not_archived(group_id)?;
+        let (_, group) = groups().get(group_id).await?;
+
+        let role = post_invite_code
+            .role
+            .unwrap_or_else(|| "member".to_string());
+        GroupValidation::validate_invite_code_role(&group, &role)?;
+        GroupValidation::validate_outranks_role(group_id, &group, caller(), &role)?;
+
+        if post_invite_code.max_uses == Some(0) {
+            return Err(ApiError::bad_request().add_message("Max uses must be greater than 0"));
+        }
+
+        let now = time();
+        if post_invite_code
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= now)
+        {
+            return Err(ApiError::bad_request().add_message("Expiry must be in the future"));
+        }
+
+        let code = raw_rand().await?[..INVITE_CODE_BYTES]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+
+        let (_, invite_code) = invite_codes().insert(
+            code.clone(),
+            InviteCode {
+                code,
+                group_id,
+                role,
+                expires_at: post_invite_code.expires_at,
+                max_uses: post_invite_code.max_uses,
+                uses: 0,
+                created_by: caller(),
+                created_at: now,
+            },
+        );
+
+        Ok(invite_code)
+    }
+
+    pub fn get_invite_codes(group_id: u64) -> Vec<InviteCode> {
+        invite_codes()
+            .filter(|_, invite_code| invite_code.group_id == group_id)
+            .into_iter()
+            .map(|(_, invite_code)| invite_code)
+            .collect()
+    }
+
+    pub fn revoke_invite_code(group_id: u64, code: String) -> CanisterResult<()> {
+        let invite_code = invite_codes().get(code.clone())?;
+
+        // the permission check is done against the given group
+        if invite_code.group_id != group_id {
+            return Err(ApiError::not_found().add_message("Invite code not found"));
+        }
+
+        invite_codes().remove(code);
+        Ok(())
+    }
+
+    fn remove_invite_codes(group_id: u64) {
+        for invite_code in Self::get_invite_codes(group_id) {
+            invite_codes().remove(invite_code.code);
+        }
+    }
+
+    /// Joins the group the code belongs to with the role of the code,
+    /// the privacy of the group is not checked
+    pub async fn join_group_with_code(code: String) -> CanisterResult<JoinGroupResult> {
+        let invite_code = Self::reserve_invite_code_use(&code)?;
+        let group_id = invite_code.group_id;
+        let principal = caller();
+
+        let group = match Self::add_member_with_code(principal, &invite_code).await {
+            Ok(group) => group,
+            Err(err) => {
+                Self::release_invite_code_use(&code);
+                return Err(err);
+            }
+        };
+
+        // the code is used when the caller is put on the waitlist of a full group
+        let Some(group) = group else {
+            return Ok(JoinGroupResult::Waitlisted);
+        };
+
+        let roles = group
+            .members
+            .members
+            .get(&principal)
+            .map(|member| member.roles.clone())
+            .unwrap_or_default();
+
+        Self::add_group_to_profile(group_id, principal).await?;
+
+        NotificationCalls::notification_join_public_group(group.get_members(), group_id).await;
+
+        // notify the reward buffer store that the group member count has changed
+        global().notify_group_member_count_changed(group_id).await?;
+
+        Ok(JoinGroupResult::Joined(JoinedMemberResponse::new(
+            principal, roles, group_id,
+        )))
+    }
+
+    /// Counts the use of the code before the first await, so concurrent joins can't use it more than `max_uses` times
+    fn reserve_invite_code_use(code: &str) -> CanisterResult<InviteCode> {
+        let mut invite_code = invite_codes().get(code.to_string())?;
+
+        if !invite_code.is_usable(time()) {
+            return Err(ApiError::bad_request().add_message("Invite code is no longer valid"));
+        }
+
+        invite_code.uses += 1;
+        invite_codes().insert(code.to_string(), invite_code.clone());
+        Ok(invite_code)
+    }
+
+    /// Gives back a reserved use when the join failed, a code that was revoked in the meantime stays removed
+    fn release_invite_code_use(code: &str) {
+        if let Some(mut invite_code) = invite_codes().find(code.to_string()) {
+            invite_code.uses = invite_code.uses.saturating_sub(1);
+            invite_codes().insert(code.to_string(), invite_code);
+        }
+    }
+
+    /// Adds the principal to the group with the role of the code, `None` when the group is full
+    /// and the principal is put on the waitlist instead
+    async fn add_member_with_code(
+        principal: Principal,
+        invite_code: &InviteCode,
+    ) -> CanisterResult<Option<GroupWithMembers>> {
+        let group_id = invite_code.group_id;
+        GroupValidation::validate_not_archived(group_id)?;
+        let (_, mut group) = groups().get(group_id).await?;
+
+        if group.is_banned_member(principal) {
+            return Err(
+                ApiError::unauthorized().add_message("You are not allowed to join this group")
+            );
+        }
+
+        if group.is_member(principal) {
+            return Err(ApiError::bad_request().add_message("Member is already in the group"));
+        }
+
+        if Self::is_group_full(group_id, &group) {
+            Self::add_to_waitlist(group_id, principal, Some(invite_code.role.clone()));
+            return Ok(None);
+        }
+
+        // a pending invite or join request is replaced by the membership
+        if let Some(invite) = group.members.invites.get(&principal).cloned() {
+            Self::remove_join_request(&invite.into());
+            group.remove_invite(principal);
+        }
+
+        group.add_member(principal);
+        if let Some(member) = group.members.members.get_mut(&principal) {
+            member.set_role(invite_code.role.clone());
+        }
+        groups().update(group_id, group.clone()).await?;
+
+        Ok(Some(group))
+    }
+
+    /// Sets the maximum number of members of the group, a lower cap than the current member count
+    /// keeps the members and waitlists new joins, `None` removes the cap and admits the waitlist
+    pub async fn set_group_member_cap(
+        group_id: u64,
+        max_members: Option<u64>,
+    ) -> CanisterResult<Option<u64>> {
+        GroupValidation::validate_not_archived(group_id)?;
+        groups().get(group_id).await?;
+
+        let waitlist = member_caps()
+            .find(group_id)
+            .map(|member_cap| member_cap.waitlist)
+            .unwrap_or_default();
+
+        let Some(max_members) = max_members else {
+            if !waitlist.is_empty() {
+                member_caps().insert(
+                    group_id,
+                    GroupMemberCap {
+                        max_members: u64::MAX,
+                        waitlist,
+                        updated_at: time(),
+                    },
+                );
+                Self::try_admit_from_waitlist(group_id).await;
+            }
+
+            member_caps().remove(group_id);
+            return Ok(None);
+        };
+
+        if max_members == 0 {
+            return Err(ApiError::bad_request().add_message("Member cap must be at least 1"));
+        }
+
+        member_caps().insert(
+            group_id,
+            GroupMemberCap {
+                max_members,
+                waitlist,
+                updated_at: time(),
+            },
+        );
+
+        // a raised cap opens spots for the waitlist
+        Self::try_admit_from_waitlist(group_id).await;
+
+        Ok(Some(max_members))
+    }
+
+    pub fn get_group_member_cap(group_id: u64) -> Option<u64> {
+        member_caps()
+            .find(group_id)
+            .map(|member_cap| member_cap.max_members)
+    }
+
+    pub fn get_group_waitlist(group_id: u64) -> Vec<WaitlistEntry> {
+        member_caps()
+            .find(group_id)
+            .map(|member_cap| member_cap.waitlist)
+            .unwrap_or_default()
+    }
+
+    pub fn leave_group_waitlist(group_id: u64) -> CanisterResult<()> {
+        let principal = caller();
+        let Some(mut member_cap) = member_caps()
+            .find(group_id)
+            .filter(|member_cap| member_cap.is_waitlisted(principal))
+        else {
+            return Err(ApiError::not_found().add_message("You are not on the waitlist"));
+        };
+
+        member_cap
+            .waitlist
+            .retain(|entry| entry.principal != principal);
+        member_caps().insert(group_id, member_cap);
+
+        Ok(())
+    }
+
+    pub fn is_group_full(group_id: u64, group: &GroupWithMembers) -> bool {
+        member_caps()
+            .find(group_id)
+            .is_some_and(|member_cap| group.members.members.len() as u64 >= member_cap.max_members)
+    }
+
+    fn remove_from_waitlist(group_id: u64, principals: &[Principal]) {
+        let Some(mut member_cap) = member_caps().find(group_id) else {
+            return;
+        };
+
+        member_cap
+            .waitlist
+            .retain(|entry| !principals.contains(&entry.principal));
+        member_caps().insert(group_id, member_cap);
+    }
+
+    /// Puts the entries of a failed admission back at the front of the waitlist in their order,
+    /// a principal that joined the waitlist again in the meantime keeps its new place
+    fn return_to_waitlist(group_id: u64, entries: Vec<WaitlistEntry>) {
+        let Some(mut member_cap) = member_caps().find(group_id) else {
+            return;
+        };
+
+        let mut waitlist = entries
+            .into_iter()
+            .filter(|entry| !member_cap.is_waitlisted(entry.principal))
+            .collect::<Vec<_>>();
+        waitlist.append(&mut member_cap.waitlist);
+        member_cap.waitlist = waitlist;
+        member_caps().insert(group_id, member_cap);
+    }
+
+    /// Admits the waitlist after a spot opened, a failed admission is printed
+    /// so it doesn't fail the call that opened the spot
+    async fn try_admit_from_waitlist(group_id: u64) {
+        if let Err(err) = Self::admit_from_waitlist(group_id).await {
+            print(format!(
+                "Failed to admit the waitlist of group {}: {}",
+                group_id, err
+            ));
+        }
+    }
+
+    /// Adds the principal to the end of the waitlist, a principal that is already on it keeps its place
+    fn add_to_waitlist(group_id: u64, principal: Principal, role: Option<String>) {
+        let Some(mut member_cap) = member_caps().find(group_id) else {
+            return;
+        };
+
+        if member_cap.is_waitlisted(principal) {
+            return;
+        }
+
+        member_cap.waitlist.push(WaitlistEntry {
+            principal,
+            role,
+            created_at: time(),
+        });
+        member_caps().insert(group_id, member_cap);
+    }
+
+    /// Admits the waitlist in order until the group is full, the admitted members are notified.
+    /// The gating rule was checked when they were put on the waitlist, lapses are handled by the re-validation
+    pub async fn admit_from_waitlist(group_id: u64) -> CanisterResult<Vec<Principal>> {
+        if !member_caps()
+            .find(group_id)
+            .is_some_and(|member_cap| !member_cap.waitlist.is_empty())
+        {
+            return Ok(vec![]);
+        }
+
+        let (_, mut group) = groups().get(group_id).await?;
+
+        // the cap is read after the group, the cap and waitlist can change while the group is read
+        let Some(member_cap) = member_caps().find(group_id) else {
+            return Ok(vec![]);
+        };
+
+        let mut taken = vec![];
+        let mut admitted_entries = vec![];
+        for entry in member_cap.waitlist {
+            if group.members.members.len() as u64 >= member_cap.max_members {
+                break;
+            }
+
+            let principal = entry.principal;
+            taken.push(principal);
+
+            if group.is_member(principal) || group.is_banned_member(principal) {
+                continue;
+            }
+
+            // a pending invite or join request is replaced by the membership
+            if let Some(invite) = group.members.invites.get(&principal).cloned() {
+                Self::remove_join_request(&invite.into());
+                group.remove_invite(principal);
+            }
+
+            group.add_member(principal);
+            if let (Some(role), Some(member)) = (
+                entry.role.clone(),
+                group.members.members.get_mut(&principal),
+            ) {
+                member.set_role(role);
+            }
+
+            admitted_entries.push(entry);
+        }
+
+        // only the taken entries are removed, the rest of the waitlist is kept as stored,
+        // they are removed before the update so a concurrent admission can't take them again
+        Self::remove_from_waitlist(group_id, &taken);
+
+        if admitted_entries.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let admitted = admitted_entries
+            .iter()
+            .map(|entry| entry.principal)
+            .collect::<Vec<_>>();
+
+        if let Err(err) = groups().update(group_id, group.clone()).await {
+            Self::return_to_waitlist(group_id, admitted_entries);
+            return Err(err);
+        }
+
+        Self::add_group_to_profiles(group_id, admitted.clone()).await?;
+
+        // a failed notice doesn't undo the admission
+        for principal in &admitted {
+            if let Err(err) =
+                NotificationCalls::notification_waitlist_admitted(*principal, group_id).await
+            {
+                print(format!(
+                    "Failed to notify {} of the admission to group {}: {}",
+                    principal, group_id, err
+                ));
+            }
+        }
+
+        NotificationCalls::notification_join_public_group(group.get_members(), group_id).await;
+
+        // notify the reward buffer store that the group member count has changed
+        global().notify_group_member_count_changed(group_id).await?;
+
+        Ok(admitted)
+    }
+
+    async fn get_boosted_group(id: u64) -> CanisterResult<Option<Boost>> {
+        let boost = BoostCalls::get_boost_by_subject(Subject::Group(id))
+            .await?
+            .map(|x| x.1);
+
+        Ok(boost)
+    }
+
+    async fn get_boosted_groups_by_id(ids: Vec<u64>) -> CanisterResult<HashMap<u64, Boost>> {
+        let boosts =
+            BoostCalls::get_boosts_by_subjects(ids.into_iter().map(Subject::Group).collect())
+                .await?
+                .into_iter()
+                .map(|(_, boost)| (*boost.subject.get_id(), boost))
+                .collect();
 
-        Ok(boost)
+        Ok(boosts)
     }
 
     pub async fn add_special_member_to_group(
@@ -939,17 +2690,18 @@ impl GroupCalls {
         Ok(())
     }
 
+    /// The members ranked `moderator` or higher
     pub async fn get_higher_role_members(group_id: u64) -> Vec<Principal> {
-        GroupCalls::get_group_members_by_permission(
-            group_id,
-            PermissionType::Invite(None),
-            PermissionActionType::Write,
-        )
-        .await
-        .unwrap_or_default()
-        .iter()
-        .map(|m| m.principal)
-        .collect()
+        let Ok((_, group)) = groups().get(group_id).await else {
+            return vec![];
+        };
+
+        let ranks = role_ranks().find(group_id).unwrap_or_default();
+        group
+            .get_members()
+            .into_iter()
+            .filter(|principal| ranks.rank_of_member(&group, *principal) >= RoleRank::MODERATOR)
+            .collect()
     }
 
     fn has_pending_join_request(group: GroupWithMembers, principal: Principal) -> bool {
@@ -969,6 +2721,68 @@ impl GroupCalls {
         Ok(())
     }
 
+    async fn add_group_to_profiles(
+        group_id: u64,
+        principals: Vec<Principal>,
+    ) -> CanisterResult<()> {
+        let profile_list = profiles()
+            .get_many(principals)
+            .await?
+            .into_iter()
+            .filter(|(_, profile)| !profile.is_group_member(group_id))
+            .map(|(principal, mut profile)| {
+                profile.add_group(group_id);
+                (principal, profile)
+            })
+            .collect::<Vec<_>>();
+
+        if !profile_list.is_empty() {
+            profiles().update_many(profile_list).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn remove_group_from_profiles(
+        group_id: u64,
+        principals: Vec<Principal>,
+    ) -> CanisterResult<()> {
+        let profile_list = profiles()
+            .get_many(principals)
+            .await?
+            .into_iter()
+            .filter(|(_, profile)| profile.is_group_member(group_id))
+            .map(|(principal, mut profile)| {
+                profile.remove_group(group_id);
+                (principal, profile)
+            })
+            .collect::<Vec<_>>();
+
+        if !profile_list.is_empty() {
+            profiles().update_many(profile_list).await?;
+        }
+
+        Ok(())
+    }
+
+    fn dedup_principals(principals: Vec<Principal>) -> Vec<Principal> {
+        let mut result: Vec<Principal> = vec![];
+        for principal in principals {
+            if !result.contains(&principal) {
+                result.push(principal);
+            }
+        }
+        result
+    }
+
+    fn get_succeeded(results: &[(Principal, CanisterResult<()>)]) -> Vec<Principal> {
+        results
+            .iter()
+            .filter(|(_, result)| result.is_ok())
+            .map(|(principal, _)| *principal)
+            .collect()
+    }
+
     async fn remove_group_from_profile(group_id: u64, principal: Principal) -> CanisterResult<()> {
         let (_, mut profile) = profiles().get(principal).await?;
         if profile.is_group_member(group_id) {
@@ -1034,15 +2848,162 @@ impl GroupValidation {
         Validator::new(validator_fields).validate()
     }
 
+    /// Checks if the principal can own another group
+    async fn validate_group_limit(principal: Principal) -> CanisterResult<()> {
+        let owned = groups()
+            .filter(GroupFilter::Owner(principal).to_vec())
+            .await?
+            .len();
+
+        let user_group_creation_limit =
+            ConfigCalls::get_platform_config().user_group_creation_limit;
+
+        if owned as u64 >= user_group_creation_limit {
+            return Err(ApiError::bad_request().add_message(
+                format!("You can only own {} groups", user_group_creation_limit).as_str(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_batch_size(principals: &[Principal]) -> CanisterResult<()> {
+        if principals.len() > MEMBER_BATCH_LIMIT {
+            return Err(ApiError::bad_request().add_message(
+                format!(
+                    "At most {} principals can be passed at once",
+                    MEMBER_BATCH_LIMIT
+                )
+                .as_str(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// The caller has to outrank the member they act on
+    pub fn validate_outranks_member(
+        group_id: u64,
+        group: &GroupWithMembers,
+        caller: Principal,
+        principal: Principal,
+    ) -> CanisterResult<()> {
+        let ranks = role_ranks().find(group_id).unwrap_or_default();
+        if ranks.rank_of_member(group, caller) <= ranks.rank_of_member(group, principal) {
+            return Err(ApiError::unauthorized()
+                .add_message("You can only manage members ranked below you"));
+        }
+
+        Ok(())
+    }
+
+    /// The caller has to outrank the role they grant, revoke or edit
+    pub fn validate_outranks_role(
+        group_id: u64,
+        group: &GroupWithMembers,
+        caller: Principal,
+        role: &str,
+    ) -> CanisterResult<()> {
+        let ranks = role_ranks().find(group_id).unwrap_or_default();
+        Self::validate_outranks_rank(group_id, group, caller, ranks.rank_of(role))
+    }
+
+    /// The caller has to outrank the rank of a role they add
+    pub fn validate_outranks_rank(
+        group_id: u64,
+        group: &GroupWithMembers,
+        caller: Principal,
+        rank: RoleRank,
+    ) -> CanisterResult<()> {
+        let ranks = role_ranks().find(group_id).unwrap_or_default();
+        if ranks.rank_of_member(group, caller) <= rank {
+            return Err(
+                ApiError::unauthorized().add_message("You can only manage roles ranked below you")
+            );
+        }
+
+        Ok(())
+    }
+
+    /// The role of an invite code has to exist in the group and can't be the owner role
+    fn validate_invite_code_role(group: &GroupWithMembers, role: &str) -> CanisterResult<()> {
+        if role == "owner" {
+            return Err(
+                ApiError::bad_request().add_message("Invite codes can't grant the owner role")
+            );
+        }
+
+        let mut roles = default_roles();
+        roles.append(&mut group.members.roles.clone());
+        if !roles.iter().any(|r| r.name == role) {
+            return Err(ApiError::bad_request().add_message("Role does not exist"));
+        }
+
+        Ok(())
+    }
+
+    /// Archived groups are read-only until they are restored
+    pub fn validate_not_archived(group_id: u64) -> CanisterResult<()> {
+        if archived_groups().contains_key(group_id) {
+            return Err(ApiError::bad_request().add_message("Group is archived"));
+        }
+
+        Ok(())
+    }
+
+    /// The checks of `validate_member_join` without joining, a full group can't be joined
+    /// so its privacy and gating rule are checked before the caller is put on the waitlist
+    async fn validate_waitlist_join(
+        caller: Principal,
+        group_id: u64,
+        group: &GroupWithMembers,
+        account_identifier: &Option<String>,
+    ) -> CanisterResult<()> {
+        Self::validate_not_archived(group_id)?;
+
+        if group.is_banned_member(caller) {
+            return Err(
+                ApiError::unauthorized().add_message("You are not allowed to join this group")
+            );
+        }
+
+        if group.is_member(caller) {
+            return Err(ApiError::bad_request().add_message("Member is already in the group"));
+        }
+
+        if member_caps()
+            .find(group_id)
+            .is_some_and(|member_cap| member_cap.is_waitlisted(caller))
+        {
+            return Err(ApiError::bad_request().add_message("You are already on the waitlist"));
+        }
+
+        match &group.privacy.privacy_type {
+            PrivacyType::InviteOnly => {
+                Err(ApiError::bad_request().add_message("Group is invite only"))
+            }
+            PrivacyType::Gated(_) => match GroupCalls::get_gating_rule(group_id, group) {
+                Some(gating_rule) => {
+                    Self::validate_gating_rule_met(caller, account_identifier, &gating_rule).await
+                }
+                None => Ok(()),
+            },
+            _ => Ok(()),
+        }
+    }
+
     async fn validate_member_join(
         caller: Principal,
         group_id: u64,
         account_identifier: &Option<String>,
     ) -> CanisterResult<()> {
-        let (group_id, group) = groups().get(group_id).await?;
+        Self::validate_not_archived(group_id)?;
+        let (group_id, mut group) = groups().get(group_id).await?;
 
         if group.is_banned_member(caller) {
-            return Err(ApiError::unauthorized().add_message("You are allowed to join this group"));
+            return Err(
+                ApiError::unauthorized().add_message("You are not allowed to join this group")
+            );
         }
 
         // Check if the member is already in the group
@@ -1071,19 +3032,22 @@ impl GroupValidation {
             }
             // If the group is private, add the invite to the member
             Private => {
-                let invite = group
-                    .members
-                    .invites
-                    .get(&caller)
-                    .cloned()
-                    .map(|k| k.into());
+                if group.is_invited(caller) {
+                    return Err(ApiError::bad_request()
+                        .add_message("Member is already invited to the group"));
+                }
 
-                NotificationCalls::notification_user_join_request_group(
+                let notification_id = NotificationCalls::notification_user_join_request_group(
                     GroupCalls::get_higher_role_members(group_id).await,
-                    InviteMemberResponse::new(caller, invite, group_id),
+                    InviteMemberResponse::new(caller, None, group_id),
                 )
                 .await?;
 
+                group.add_invite(caller, InviteType::UserRequest, Some(notification_id));
+                groups().update(group_id, group).await?;
+
+                GroupCalls::add_join_request(group_id, caller, notification_id);
+
                 if !profile.is_group_member(group_id) {
                     profile.add_group(group_id);
                     profiles().update(caller, profile).await?;
@@ -1094,137 +3058,238 @@ impl GroupValidation {
             // If the group is invite only, throw an error
             InviteOnly => Err(ApiError::bad_request().add_message("Group is invite only")),
 
-            // Self::validate_neuron(caller, neuron_canister.governance_canister, neuron_canister.rules).await
-            // If the group is gated, check if the caller owns a specific NFT
-            Gated(gated_type) => {
-                let mut is_valid = false;
-                use GatedType::*;
-                match gated_type {
-                    Neuron(neuron_canisters) => {
-                        for neuron_canister in neuron_canisters {
-                            is_valid = Self::validate_neuron_gated(
-                                caller,
-                                neuron_canister.governance_canister,
-                                neuron_canister.rules,
-                            )
-                            .await;
-                            if is_valid {
-                                break;
-                            }
-                        }
-                        if is_valid {
-                            if !profile.is_group_member(group_id) {
-                                profile.add_group(group_id);
-                                profiles().update(caller, profile).await?;
-                            }
-                            // notify the reward buffer store that the group member count has changed
-                            global().notify_group_member_count_changed(group_id).await?;
+            // If the group is gated, check if the caller meets the gating rule
+            Gated(_) => {
+                if let Some(gating_rule) = GroupCalls::get_gating_rule(group_id, &group) {
+                    Self::validate_gating_rule_met(caller, account_identifier, &gating_rule)
+                        .await?;
+                }
 
-                            Ok(())
-                        } else {
-                            // If the caller does not own the neuron, throw an error
-                            Err(ApiError::unauthorized().add_message(
-                                "You are not owning the required neuron to join this group",
-                            ))
-                        }
-                    }
-                    Token(nft_canisters) => {
-                        // Loop over the canisters and check if the caller owns a specific NFT (inter-canister call)
-                        for nft_canister in nft_canisters {
-                            is_valid = Self::validate_nft_gated(
-                                &caller,
-                                account_identifier,
-                                &nft_canister,
-                            )
-                            .await;
-                            if is_valid {
-                                break;
-                            }
-                        }
-                        if is_valid {
-                            if !profile.is_group_member(group_id) {
-                                profile.add_group(group_id);
-                                profiles().update(caller, profile).await?;
-                            }
+                if !profile.is_group_member(group_id) {
+                    profile.add_group(group_id);
+                    profiles().update(caller, profile).await?;
+                }
 
-                            // notify the reward buffer store that the group member count has changed
-                            global().notify_group_member_count_changed(group_id).await?;
+                // notify the reward buffer store that the group member count has changed
+                global().notify_group_member_count_changed(group_id).await?;
 
-                            Ok(())
-                            // If the caller does not own the NFT, throw an error
-                        } else {
-                            Err(ApiError::unauthorized().add_message(
-                                "You are not owning the required NFT to join this group",
-                            ))
-                        }
-                    }
-                }
+                Ok(())
             }
         }
     }
 
+    /// The creator of a gated group has to meet its gating rule,
+    /// without a custom rule at least `privacy_gated_type_amount` of its canisters have to be met
     async fn validate_group_privacy(
         caller: &Principal,
         account_identifier: Option<String>,
         post_group: &PostGroup,
+        gating_rule: Option<&GatingRule>,
     ) -> CanisterResult<()> {
-        use PrivacyType::*;
-        match post_group.privacy.clone() {
-            Public => Ok(()),
-            Private => Ok(()),
-            InviteOnly => Ok(()),
-            Gated(gated_type) => {
-                let mut is_valid: u64 = 0;
-                use GatedType::*;
-                match gated_type {
-                    Neuron(neuron_canisters) => {
-                        for neuron_canister in neuron_canisters {
-                            if Self::validate_neuron_gated(
-                                *caller,
-                                neuron_canister.governance_canister,
-                                neuron_canister.rules,
-                            )
-                            .await
-                            {
-                                is_valid += 1;
-                            }
-                            if is_valid >= post_group.privacy_gated_type_amount.unwrap_or_default()
-                            {
-                                break;
-                            }
+        let PrivacyType::Gated(gated_type) = post_group.privacy.clone() else {
+            if gating_rule.is_some() {
+                return Err(
+                    ApiError::bad_request().add_message("Only gated groups can have a gating rule")
+                );
+            }
+
+            return Ok(());
+        };
+
+        let gating_rule = match gating_rule {
+            Some(gating_rule) => {
+                Self::validate_gating_rule(gating_rule)?;
+                gating_rule.clone()
+            }
+            None => {
+                let gating_rule =
+                    GatingRule::from_gated_type(gated_type, post_group.privacy_gated_type_amount);
+                Self::validate_token_standards(&gating_rule)?;
+                gating_rule
+            }
+        };
+
+        Self::validate_gating_rule_met(*caller, &account_identifier, &gating_rule).await
+    }
+
+    /// Bounds the rule tree so checking it takes a limited number of calls
+    pub fn validate_gating_rule(gating_rule: &GatingRule) -> CanisterResult<()> {
+        if gating_rule.depth() > MAX_GATING_RULE_DEPTH {
+            return Err(ApiError::bad_request().add_message(format!(
+                "Gating rule can not be nested more than {} levels",
+                MAX_GATING_RULE_DEPTH
+            )));
+        }
+
+        if gating_rule.conditions() > MAX_GATING_RULE_CONDITIONS {
+            return Err(ApiError::bad_request().add_message(format!(
+                "Gating rule can not have more than {} conditions",
+                MAX_GATING_RULE_CONDITIONS
+            )));
+        }
+
+        Self::validate_gating_rule_node(gating_rule)?;
+        Self::validate_token_standards(gating_rule)
+    }
+
+    /// Every token of the rule has to use a known `TokenStandard`,
+    /// a misspelled standard would fail every join
+    pub fn validate_token_standards(gating_rule: &GatingRule) -> CanisterResult<()> {
+        gating_rule
+            .get_conditions()
+            .into_iter()
+            .try_for_each(|condition| match condition {
+                GatingRule::Token(token) => token.standard.parse::<TokenStandard>().map(|_| ()),
+                _ => Ok(()),
+            })
+    }
+
+    fn validate_gating_rule_node(gating_rule: &GatingRule) -> CanisterResult<()> {
+        use GatingRule::*;
+        let rules = match gating_rule {
+            Token(_) | Neuron(_) | SnsNeuron(_) => return Ok(()),
+            All(rules) | Any(rules) => rules,
+            AtLeast { amount, rules } => {
+                if *amount == 0 || *amount > rules.len() as u64 {
+                    return Err(ApiError::bad_request().add_message(
+                        "Gating rule amount must be between 1 and the number of rules",
+                    ));
+                }
+                rules
+            }
+        };
+
+        if rules.is_empty() {
+            return Err(ApiError::bad_request().add_message("Gating rule can not be empty"));
+        }
+
+        rules.iter().try_for_each(Self::validate_gating_rule_node)
+    }
+
+    async fn validate_gating_rule_met(
+        principal: Principal,
+        account_identifier: &Option<String>,
+        gating_rule: &GatingRule,
+    ) -> CanisterResult<()> {
+        if Self::meets_gating_rule(principal, account_identifier, gating_rule).await {
+            return Ok(());
+        }
+
+        Err(ApiError::unauthorized().add_message("You do not meet the gating rule of this group"))
+    }
+
+    /// Evaluates the rule tree, the tokens are checked with `validate_nft_gated` and the
+    /// neurons with `count_eligible_nns_neurons` and `count_eligible_sns_neurons`
+    pub fn meets_gating_rule<'a>(
+        principal: Principal,
+        account_identifier: &'a Option<String>,
+        gating_rule: &'a GatingRule,
+    ) -> Pin<Box<dyn Future<Output = bool> + 'a>> {
+        Box::pin(async move {
+            use GatingRule::*;
+            match gating_rule {
+                Token(token) => {
+                    Self::validate_nft_gated(&principal, account_identifier, token).await
+                }
+                Neuron(neuron) => Self::count_eligible_nns_neurons(principal, neuron)
+                    .await
+                    .is_some_and(|count| count > 0),
+                SnsNeuron(neuron) => Self::count_eligible_sns_neurons(principal, neuron)
+                    .await
+                    .is_some_and(|count| count > 0),
+                All(rules) => {
+                    for rule in rules {
+                        if !Self::meets_gating_rule(principal, account_identifier, rule).await {
+                            return false;
                         }
-                        if is_valid >= post_group.privacy_gated_type_amount.unwrap_or_default() {
-                            return Ok(());
-                            // If the caller does not own the neuron, throw an error
+                    }
+                    true
+                }
+                Any(rules) => {
+                    for rule in rules {
+                        if Self::meets_gating_rule(principal, account_identifier, rule).await {
+                            return true;
                         }
-
-                        Err(ApiError::unauthorized().add_message(
-                            "You are not owning the required neuron to join this group",
-                        ))
                     }
-                    Token(nft_canisters) => {
-                        // Loop over the canisters and check if the caller owns a specific NFT (inter-canister call)
-                        for nft_canister in nft_canisters {
-                            if Self::validate_nft_gated(caller, &account_identifier, &nft_canister)
-                                .await
-                            {
-                                is_valid += 1;
-                            }
-                            if is_valid >= post_group.privacy_gated_type_amount.unwrap_or_default()
-                            {
-                                break;
-                            }
+                    false
+                }
+                AtLeast { amount, rules } => {
+                    let mut met = 0;
+                    for rule in rules {
+                        if met >= *amount {
+                            break;
                         }
-                        if is_valid >= post_group.privacy_gated_type_amount.unwrap_or_default() {
-                            return Ok(());
-                            // If the caller does not own the neuron, throw an error
+                        if Self::meets_gating_rule(principal, account_identifier, rule).await {
+                            met += 1;
                         }
-
-                        Err(ApiError::unauthorized()
-                            .add_message("You are not owning the required NFT to join this group"))
                     }
+                    met >= *amount
+                }
+            }
+        })
+    }
+
+    pub async fn check_token_gated(
+        principal: Principal,
+        account_identifier: &Option<String>,
+        token: &TokenGated,
+    ) -> GatingConditionCheck {
+        let observed_amount = Self::get_token_balance(&principal, account_identifier, token).await;
+
+        GatingConditionCheck {
+            standard: token.standard.clone(),
+            canister: token.principal,
+            required_amount: token.amount as u128,
+            observed_amount,
+            passed: observed_amount.is_some_and(|balance| balance >= token.amount as u128),
+        }
+    }
+
+    /// The check of a neuron condition with the number of neurons of the principal that meet its rules
+    pub fn check_neuron_gated(
+        governance_canister: Principal,
+        eligible_neurons: Option<u64>,
+    ) -> GatingConditionCheck {
+        let observed_amount = eligible_neurons.map(u128::from);
+
+        GatingConditionCheck {
+            standard: "NEURON".to_string(),
+            canister: governance_canister,
+            required_amount: 1,
+            observed_amount,
+            passed: observed_amount.is_some_and(|count| count > 0),
+        }
+    }
+
+    /// Whether the principal meets a token or neuron condition, `None` when a ledger or governance call failed.
+    /// Unknown token standards and EXT tokens without account identifier are not met.
+    pub async fn try_meets_condition(
+        principal: Principal,
+        account_identifier: &Option<String>,
+        condition: &GatingRule,
+    ) -> Option<bool> {
+        match condition {
+            GatingRule::Token(token) => {
+                let Ok(standard) = token.standard.parse::<TokenStandard>() else {
+                    return Some(false);
+                };
+
+                if standard == TokenStandard::Ext && account_identifier.is_none() {
+                    return Some(false);
                 }
+
+                Self::get_token_balance(&principal, account_identifier, token)
+                    .await
+                    .map(|balance| balance >= token.amount as u128)
             }
+            GatingRule::Neuron(neuron) => Self::count_eligible_nns_neurons(principal, neuron)
+                .await
+                .map(|count| count > 0),
+            GatingRule::SnsNeuron(neuron) => Self::count_eligible_sns_neurons(principal, neuron)
+                .await
+                .map(|count| count > 0),
+            // `get_conditions` only returns tokens and neurons
+            GatingRule::All(_) | GatingRule::Any(_) | GatingRule::AtLeast { .. } => None,
         }
     }
 
@@ -1234,137 +3299,155 @@ impl GroupValidation {
         account_identifier: &Option<String>,
         nft_canister: &TokenGated,
     ) -> bool {
-        // Check if the canister is a EXT, DIP20 or DIP721 canister
-        match nft_canister.standard.as_str() {
-            // If the canister is a EXT canister, check if the caller owns the NFT
+        Self::get_token_balance(principal, account_identifier, nft_canister)
+            .await
+            .is_some_and(|balance| balance >= nft_canister.amount as u128)
+    }
+
+    /// The balance of the token, `None` for unknown standards, EXT tokens without account identifier
+    /// and when a call to the token canister failed
+    pub async fn get_token_balance(
+        principal: &Principal,
+        account_identifier: &Option<String>,
+        nft_canister: &TokenGated,
+    ) -> Option<u128> {
+        let Ok(standard) = nft_canister.standard.parse::<TokenStandard>() else {
+            return None;
+        };
+
+        let canister = nft_canister.principal;
+        let balance = match standard {
+            // If the canister is a EXT canister, get the NFT balance of the account
             // This call uses the account_identifier
-            "EXT" => match account_identifier {
-                Some(_account_identifier) => {
-                    let response =
-                        ext_balance_of(nft_canister.principal, _account_identifier.clone()).await;
-                    response as u64 >= nft_canister.amount
-                }
-                None => false,
-            },
-            // If the canister is a DIP20 canister, check if the caller owns the NFT
-            "DIP20" => {
-                let response = dip20_balance_of(nft_canister.principal, principal).await;
-                response as u64 >= nft_canister.amount
+            TokenStandard::Ext => {
+                ext_balance_of(canister, account_identifier.clone()?).await? as u128
             }
-            // If the canister is a DIP721 canister, check if the caller owns the NFT
-            "DIP721" => {
-                let response = dip721_balance_of(nft_canister.principal, principal).await;
-                response as u64 >= nft_canister.amount
+            // If the canister is a DIP20 canister, get the NFT balance of the caller
+            TokenStandard::Dip20 => dip20_balance_of(canister, principal).await? as u128,
+            // If the canister is a DIP721 canister, get the NFT balance of the caller
+            TokenStandard::Dip721 => dip721_balance_of(canister, principal).await? as u128,
+            // If the canister is a LEGACY DIP721 canister, get the NFT balance of the caller
+            TokenStandard::Dip721Legacy => {
+                legacy_dip721_balance_of(canister, principal).await? as u128
             }
-            // If the canister is a LEGACY DIP721 canister, check if the caller owns the NFT
-            "DIP721_LEGACY" => {
-                let response = legacy_dip721_balance_of(nft_canister.principal, principal).await;
-                response as u64 >= nft_canister.amount
+            // If the canister is a ICRC-1 canister, sum the token balances of the accounts of the caller
+            TokenStandard::Icrc1 => {
+                let mut balance: u128 = 0;
+                for account in Self::get_token_accounts(*principal) {
+                    balance = balance.saturating_add(icrc1_balance_of(canister, &account).await?);
+                }
+                balance
             }
-            // If the canister is a ICRC canister, check if the caller owns the amount of tokens
-            "ICRC" => {
-                let response = icrc_balance_of(nft_canister.principal, principal).await;
-                response >= nft_canister.amount as u128
+            // If the canister is a ICRC-7 canister, get the NFT balance of the accounts of the caller
+            TokenStandard::Icrc7 => {
+                icrc7_balance_of(canister, &Self::get_token_accounts(*principal)).await?
             }
-            _ => false,
+        };
+
+        Some(balance)
+    }
+
+    /// The ICRC accounts of the principal, its default and registered subaccounts.
+    /// The wallets linked to the profile are not proven to belong to the principal and don't count.
+    pub fn get_token_accounts(principal: Principal) -> Vec<Account> {
+        let subaccounts = token_subaccounts()
+            .find(principal)
+            .map(|token_subaccounts| token_subaccounts.subaccounts)
+            .unwrap_or_default();
+
+        let mut accounts = vec![Account {
+            owner: principal,
+            subaccount: None,
+        }];
+        for subaccount in subaccounts {
+            accounts.push(Account {
+                owner: principal,
+                subaccount: Some(subaccount),
+            });
         }
+
+        accounts
     }
 
-    // Method to check if the caller owns a specific neuron and it applies to the set rules
-    pub async fn validate_neuron_gated(
+    /// The number of neurons of the principal on the NNS governance canister that apply to all the set rules,
+    /// every page of neurons is read, `None` when the neurons could not be read
+    pub async fn count_eligible_nns_neurons(
         principal: Principal,
-        governance_canister: Principal,
-        rules: Vec<NeuronGatedRules>,
-    ) -> bool {
-        let list_neuron_arg = ListNeurons {
-            of_principal: Some(principal),
-            limit: 100,
-            start_page_at: None,
+        neuron: &NeuronGated,
+    ) -> Option<u64> {
+        let rules: Vec<NeuronRule> = neuron.rules.iter().cloned().map(NeuronRule::from).collect();
+
+        let now_seconds = time() / 1_000_000_000;
+        let neurons = list_nns_neurons_of(neuron.governance_canister, principal).await?;
+
+        let count = neurons
+            .iter()
+            .filter(|n| Self::neuron_meets_rules(n, &rules, None, now_seconds))
+            .count();
+
+        Some(count as u64)
+    }
+
+    /// The number of neurons of the principal on the SNS governance canister that apply to all the set rules,
+    /// every page of neurons is read, `None` when the neurons or the voting power parameters could not be read
+    pub async fn count_eligible_sns_neurons(
+        principal: Principal,
+        neuron: &SnsNeuronGated,
+    ) -> Option<u64> {
+        let parameters = if neuron
+            .rules
+            .iter()
+            .any(|rule| matches!(rule, NeuronRule::MinVotingPower(_)))
+        {
+            Some(get_nervous_system_parameters(neuron.governance_canister).await?)
+        } else {
+            None
         };
 
-        let call: Result<(ListNeuronsResponse,), _> =
-            call::call(governance_canister, "list_neurons", (list_neuron_arg,)).await;
-
-        match call {
-            Ok((neurons,)) => {
-                let mut is_valid: HashMap<Vec<u8>, bool> = HashMap::new();
-                // iterate over the neurons and check if the neuron applies to all the set rules
-                for neuron in neurons.neurons {
-                    let neuron_id = neuron.id.unwrap().id;
-                    is_valid.insert(neuron_id.clone(), true);
-                    for rule in rules.clone() {
-                        use NeuronGatedRules::*;
-                        match rule {
-                            IsDisolving(_) => {
-                                match &neuron.dissolve_state {
-                                    Some(_state) => {
-                                        use DissolveState::*;
-                                        match _state {
-                                            // neuron is not in a dissolving state
-                                            DissolveDelaySeconds(_time) => {
-                                                is_valid.insert(neuron_id, false);
-                                                break;
-                                            }
-                                            // means that the neuron is in a dissolving state
-                                            WhenDissolvedTimestampSeconds(_time) => {}
-                                        }
-                                    }
-                                    None => {
-                                        is_valid.insert(neuron_id, false);
-                                        break;
-                                    }
-                                }
-                            }
-                            MinAge(_min_age_in_seconds) => {
-                                if neuron.created_timestamp_seconds < _min_age_in_seconds {
-                                    is_valid.insert(neuron_id, false);
-                                    break;
-                                }
-                            }
-                            MinStake(_min_stake) => {
-                                let neuron_stake =
-                                    neuron.cached_neuron_stake_e8s as f64 / 100_000_000.0;
-                                let min_stake = _min_stake as f64 / 100_000_000.0;
-
-                                if neuron_stake.ceil() < min_stake.ceil() {
-                                    is_valid.insert(neuron_id, false);
-                                    break;
-                                }
-                            }
-                            MinDissolveDelay(_min_dissolve_delay_in_seconds) => {
-                                match &neuron.dissolve_state {
-                                    Some(_state) => {
-                                        use DissolveState::*;
-                                        match _state {
-                                            // neuron is not in a dissolving state, time is locking period in seconds
-                                            DissolveDelaySeconds(_dissolve_delay_in_seconds) => {
-                                                if &_min_dissolve_delay_in_seconds
-                                                    > _dissolve_delay_in_seconds
-                                                {
-                                                    is_valid.insert(neuron_id, false);
-                                                    break;
-                                                }
-                                            }
-                                            // if the neuron is dissolving, make invalid
-                                            // means that the neuron is in a dissolving state, timestamp when neuron is done dissolving in seconds
-                                            WhenDissolvedTimestampSeconds(_) => {
-                                                is_valid.insert(neuron_id, false);
-                                                break;
-                                            }
-                                        }
-                                    }
-                                    None => {
-                                        is_valid.insert(neuron_id, false);
-                                        break;
-                                    }
-                                }
-                            }
-                        }
+        let now_seconds = time() / 1_000_000_000;
+        let neurons = list_neurons_of(neuron.governance_canister, principal).await?;
+
+        let count = neurons
+            .iter()
+            .filter(|n| {
+                Self::neuron_meets_rules(n, &neuron.rules, parameters.as_ref(), now_seconds)
+            })
+            .count();
+
+        Some(count as u64)
+    }
+
+    /// A `MinVotingPower` rule is never met without the parameters of the SNS
+    pub fn neuron_meets_rules(
+        neuron: &Neuron,
+        rules: &[NeuronRule],
+        parameters: Option<&NervousSystemParameters>,
+        now_seconds: u64,
+    ) -> bool {
+        rules.iter().all(|rule| match rule {
+            // means that the neuron is in a dissolving state
+            NeuronRule::IsDissolving => neuron.is_dissolving(),
+            NeuronRule::MinAge(min_age_in_seconds) => {
+                now_seconds.saturating_sub(neuron.created_timestamp_seconds) >= *min_age_in_seconds
+            }
+            NeuronRule::MinStake(min_stake) => {
+                let neuron_stake = neuron.cached_neuron_stake_e8s as f64 / 100_000_000.0;
+                let min_stake = *min_stake as f64 / 100_000_000.0;
+
+                neuron_stake.ceil() >= min_stake.ceil()
+            }
+            // a dissolving neuron is not locked, so it never meets a minimum dissolve delay
+            NeuronRule::MinDissolveDelay(min_dissolve_delay_in_seconds) => {
+                match neuron.dissolve_state {
+                    Some(DissolveState::DissolveDelaySeconds(dissolve_delay_in_seconds)) => {
+                        dissolve_delay_in_seconds >= *min_dissolve_delay_in_seconds
                     }
+                    _ => false,
                 }
-                return is_valid.iter().any(|v| v.1 == &true);
             }
-            Err(_) => false,
-        }
+            NeuronRule::MinVotingPower(min_voting_power) => parameters.is_some_and(|parameters| {
+                neuron.voting_power(parameters, now_seconds) >= *min_voting_power
+            }),
+        })
     }
 }
@@ -0,0 +1,86 @@
+use candid::Principal;
+use catalyze_shared::{ic_call::ic_call, CanisterResult, StorageClient};
+use ic_cdk::api::canister_balance128;
+
+use crate::{
+    helpers::env::time,
+    models::{CanisterHealth, HealthReport},
+    storage::{
+        boosts, events, friend_requests, get_canister_config, groups, notifications, profiles,
+        reports, topics,
+    },
+};
+
+pub struct HealthCalls;
+
+impl HealthCalls {
+    /// Checks every canister in the topology, a failing canister never fails the whole report
+    pub async fn get_health() -> HealthReport {
+        let mut canisters = vec![];
+
+        for (name, canister_id) in get_canister_config().entries() {
+            canisters.push(Self::check_canister(name, canister_id).await);
+        }
+
+        HealthReport {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            cycles: canister_balance128(),
+            healthy: canisters.iter().all(|canister| canister.is_healthy()),
+            canisters,
+            checked_at: time(),
+        }
+    }
+
+    async fn check_canister(name: &str, canister_id: Option<Principal>) -> CanisterHealth {
+        let mut health = CanisterHealth {
+            name: name.to_string(),
+            canister_id,
+            configured: canister_id.is_some(),
+            ..Default::default()
+        };
+
+        let Some(canister_id) = canister_id else {
+            return health;
+        };
+
+        let started_at = time();
+        let version: CanisterResult<String> = ic_call(canister_id, "icts_version", ()).await;
+
+        match version {
+            Ok(version) => {
+                health.reachable = true;
+                health.latency = Some(time().saturating_sub(started_at));
+                health.icts_version = Some(version);
+            }
+            Err(err) => {
+                health.error = Some(err.to_string());
+                return health;
+            }
+        }
+
+        match Self::store_size(name).await {
+            Some(Ok(size)) => health.size = Some(size),
+            Some(Err(err)) => health.error = Some(err.to_string()),
+            None => {}
+        }
+
+        health
+    }
+
+    /// Returns the item count of a storage canister, `None` for canisters that are not a store
+    pub async fn store_size(name: &str) -> Option<CanisterResult<u64>> {
+        let size = match name {
+            "profile" => profiles().size().await,
+            "group" => groups().size().await,
+            "event" => events().size().await,
+            "boost" => boosts().size().await,
+            "topic" => topics().size().await,
+            "friend_request" => friend_requests().size().await,
+            "notification" => notifications().size().await,
+            "report" => reports().size().await,
+            _ => return None,
+        };
+
+        Some(size)
+    }
+}
@@ -2,7 +2,7 @@ use candid::{CandidType, Principal};
 use catalyze_shared::{
     api_error::ApiError,
     history_event::{GroupRoleChangeKind, GroupRoleChanged, HistoryEvent},
-    CanisterResult, CellStorage, StorageClient,
+    CanisterResult, CellStorage,
 };
 use ic_cdk::api::call::CallResult;
 use serde::Deserialize;
@@ -38,6 +38,46 @@ impl HistoryEventLogic {
 
         Ok(())
     }
+
+    /// Batch variant of `send`, the profiles are read once for all principals
+    pub async fn send_many(
+        group_id: u64,
+        changes: Vec<(Principal, Vec<String>)>,
+        kind: GroupRoleChangeKind,
+    ) -> CanisterResult<()> {
+        if history_canister().is_empty() {
+            return Ok(());
+        }
+
+        let history_canister_id = history_canister().get()?;
+        let profiles = profiles()
+            .get_many(changes.iter().map(|(principal, _)| *principal).collect())
+            .await?;
+
+        for (principal, roles) in changes {
+            let Some((_, profile)) = profiles.iter().find(|(p, _)| *p == principal) else {
+                continue;
+            };
+
+            let event = GroupRoleChanged::new(
+                group_id,
+                principal,
+                profile.metadata.username.clone(),
+                roles,
+                kind.clone(),
+            )
+            .try_into()
+            .map_err(|e: candid::Error| ApiError::unexpected().add_message(e.to_string()))?;
+
+            let history_point = global().next_history_point().await?;
+
+            ic_cdk::spawn(async move {
+                let _ = send_event(history_canister_id, history_point, event).await;
+            });
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(CandidType, Deserialize)]
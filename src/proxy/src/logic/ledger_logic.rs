@@ -1,4 +1,4 @@
-use crate::CATALYZE_MULTI_SIG;
+use super::config_logic::ConfigCalls;
 use candid::Principal;
 use catalyze_shared::api_error::ApiError;
 use ic_ledger_types::{
@@ -32,7 +32,7 @@ impl Ledger {
                         }
                         if to
                             != Self::principal_to_account_identifier(
-                                Principal::from_text(CATALYZE_MULTI_SIG).unwrap(),
+                                ConfigCalls::get_platform_config().boost_receiver(),
                             )
                         {
                             return Err(ApiError::bad_request().add_message("Invalid to address"));
@@ -0,0 +1,176 @@
+use std::{cell::RefCell, fmt::Write, time::Duration};
+
+use ic_cdk::api::{canister_balance128, stable::stable64_size};
+use ic_cdk_timers::{set_timer, set_timer_interval};
+
+use super::{health_logic::HealthCalls, websocket_logic::Websocket};
+use crate::{
+    helpers::{
+        env::time,
+        metrics::{get_call_counters, get_guard_rejections},
+    },
+    storage::get_canister_config,
+};
+
+const WASM_PAGE_SIZE: u64 = 65536;
+const STORE_SIZES_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+thread_local! {
+    /// The store sizes of the last refresh, `http_request` is a plain query and can not call the stores itself
+    static STORE_SIZES: RefCell<Vec<(&'static str, u64)>> = const { RefCell::new(vec![]) };
+    static STORE_SIZES_UPDATED_AT: RefCell<u64> = const { RefCell::new(0) };
+}
+
+pub struct MetricsCalls;
+
+impl MetricsCalls {
+    /// Refreshes the cached store sizes now and every `STORE_SIZES_INTERVAL`,
+    /// timers do not survive an upgrade so this is called on `init` and `post_upgrade`
+    pub fn start_store_sizes_timer() {
+        set_timer(
+            Duration::ZERO,
+            || ic_cdk::spawn(Self::refresh_store_sizes()),
+        );
+        set_timer_interval(STORE_SIZES_INTERVAL, || {
+            ic_cdk::spawn(Self::refresh_store_sizes())
+        });
+    }
+
+    pub async fn refresh_store_sizes() {
+        let mut sizes = vec![];
+
+        for (name, canister_id) in get_canister_config().entries() {
+            if canister_id.is_none() {
+                continue;
+            }
+
+            if let Some(Ok(size)) = HealthCalls::store_size(name).await {
+                sizes.push((name, size));
+            }
+        }
+
+        STORE_SIZES.with(|store_sizes| *store_sizes.borrow_mut() = sizes);
+        STORE_SIZES_UPDATED_AT.with(|updated_at| *updated_at.borrow_mut() = time());
+    }
+
+    /// Renders the metrics in the Prometheus text format
+    pub fn render() -> String {
+        let mut metrics = String::new();
+
+        Self::gauge(
+            &mut metrics,
+            "proxy_cycle_balance",
+            "The cycle balance of the proxy",
+            canister_balance128(),
+        );
+        Self::gauge(
+            &mut metrics,
+            "proxy_heap_memory_bytes",
+            "The heap memory size of the proxy",
+            Self::heap_memory_size(),
+        );
+        Self::gauge(
+            &mut metrics,
+            "proxy_stable_memory_bytes",
+            "The stable memory size of the proxy",
+            stable64_size() * WASM_PAGE_SIZE,
+        );
+        Self::gauge(
+            &mut metrics,
+            "proxy_websocket_connected_clients",
+            "The number of connected websocket clients",
+            Websocket::get_connected_clients().len(),
+        );
+        Self::gauge(
+            &mut metrics,
+            "proxy_websocket_errors",
+            "The number of logged websocket errors",
+            Websocket::get_ws_errors().len(),
+        );
+
+        Self::header(
+            &mut metrics,
+            "proxy_store_size",
+            "The item count per store at the last refresh",
+            "gauge",
+        );
+        STORE_SIZES.with(|sizes| {
+            for (store, size) in sizes.borrow().iter() {
+                let _ = writeln!(metrics, "proxy_store_size{{store=\"{}\"}} {}", store, size);
+            }
+        });
+        Self::gauge(
+            &mut metrics,
+            "proxy_store_size_updated_at_seconds",
+            "The time of the last store size refresh",
+            Duration::from_nanos(STORE_SIZES_UPDATED_AT.with(|updated_at| *updated_at.borrow()))
+                .as_secs(),
+        );
+
+        let counters = get_call_counters();
+
+        Self::header(
+            &mut metrics,
+            "proxy_calls_total",
+            "The number of update calls per endpoint since the last upgrade",
+            "counter",
+        );
+        for (method, counter) in &counters {
+            let _ = writeln!(
+                metrics,
+                "proxy_calls_total{{method=\"{}\"}} {}",
+                method, counter.calls
+            );
+        }
+
+        Self::header(
+            &mut metrics,
+            "proxy_call_errors_total",
+            "The number of update calls per endpoint that returned an error since the last upgrade",
+            "counter",
+        );
+        for (method, counter) in &counters {
+            let _ = writeln!(
+                metrics,
+                "proxy_call_errors_total{{method=\"{}\"}} {}",
+                method, counter.errors
+            );
+        }
+
+        Self::header(
+            &mut metrics,
+            "proxy_guard_rejections_total",
+            "The number of update calls per guard that were rejected since the last upgrade",
+            "counter",
+        );
+        for (guard, count) in get_guard_rejections() {
+            let _ = writeln!(
+                metrics,
+                "proxy_guard_rejections_total{{guard=\"{}\"}} {}",
+                guard, count
+            );
+        }
+
+        metrics
+    }
+
+    fn header(metrics: &mut String, name: &str, help: &str, kind: &str) {
+        let _ = writeln!(metrics, "# HELP {} {}", name, help);
+        let _ = writeln!(metrics, "# TYPE {} {}", name, kind);
+    }
+
+    fn gauge(metrics: &mut String, name: &str, help: &str, value: impl std::fmt::Display) {
+        Self::header(metrics, name, help, "gauge");
+        let _ = writeln!(metrics, "{} {}", name, value);
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn heap_memory_size() -> u64 {
+        core::arch::wasm32::memory_size(0) as u64 * WASM_PAGE_SIZE
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn heap_memory_size() -> u64 {
+        0
+    }
+}
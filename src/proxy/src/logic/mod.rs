@@ -1,11 +1,16 @@
+pub mod admin_logic;
 pub mod boost_logic;
+pub mod config_logic;
 pub mod event_logic;
 pub mod friend_request_logic;
 pub mod group_logic;
+pub mod health_logic;
 pub mod history_event_logic;
 pub mod ledger_logic;
+pub mod metrics_logic;
 pub mod notification_logic;
 pub mod profile_logic;
 pub mod report_logic;
+pub mod saga_logic;
 pub mod topic_logic;
 pub mod websocket_logic;
@@ -17,14 +17,14 @@ use catalyze_shared::{
     websocket_message::WSMessage,
     CanisterResult, StorageClient, StorageClientInsertable,
 };
-use ic_cdk::caller;
 
 use crate::{
+    helpers::env::caller,
+    models::{AdminRole, GroupNotice},
     storage::{notifications, profiles},
-    MULTISIG_INDEX,
 };
 
-use super::websocket_logic::Websocket;
+use super::{admin_logic::AdminCalls, websocket_logic::Websocket};
 
 pub struct NotificationCalls;
 
@@ -282,6 +282,105 @@ impl NotificationCalls {
         Ok(data.ok_or_else(ApiError::not_found)?.0)
     }
 
+    /// Batch variant of `notification_owner_join_request_group`, the invites are stored
+    /// with a single call and the profiles are read and updated once for all invitees
+    /// # Returns
+    /// * `HashMap<Principal, u64>` - The notification id per invitee
+    pub async fn notification_owner_join_request_group_many(
+        invites: Vec<InviteMemberResponse>,
+        receivers: Vec<Principal>,
+    ) -> CanisterResult<HashMap<Principal, u64>> {
+        let invitees = invites
+            .iter()
+            .map(|invite| invite.principal)
+            .collect::<Vec<_>>();
+
+        // the profiles are resolved first so no notification is stored for an invitee without a profile
+        let profiles = profiles()
+            .get_many([invitees, receivers.clone(), vec![caller()]].concat())
+            .await?;
+
+        let invites = invites
+            .into_iter()
+            .filter(|invite| {
+                profiles
+                    .iter()
+                    .any(|(principal, _)| principal == &invite.principal)
+            })
+            .collect::<Vec<_>>();
+
+        if invites.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let new_notifications = notifications()
+            .insert_many(
+                invites
+                    .into_iter()
+                    .map(|invite| {
+                        Notification::new(
+                            NotificationType::Group(GroupNotificationType::JoinGroupOwnerRequest(
+                                invite,
+                            )),
+                            true,
+                        )
+                    })
+                    .collect(),
+            )
+            .await?;
+
+        // matched on the invite instead of relying on the order of the stored notifications
+        let new_notifications = new_notifications
+            .into_iter()
+            .filter_map(|(id, notification)| match &notification.notification_type {
+                NotificationType::Group(GroupNotificationType::JoinGroupOwnerRequest(invite)) => {
+                    Some((invite.principal, (id, notification.clone())))
+                }
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+
+        let mut profiles_to_update = vec![];
+
+        for (principal, mut profile) in profiles {
+            if let Some((id, notification)) = new_notifications.get(&principal) {
+                profile.references.notifications.add(*id, false, true);
+                Self::send_notification(
+                    Some(*id),
+                    notification.clone(),
+                    (principal, profile.clone()),
+                );
+            }
+
+            if principal == caller() {
+                for (id, _) in new_notifications.values() {
+                    profile.references.notifications.add(*id, false, true);
+                }
+            }
+
+            if receivers.contains(&principal) {
+                for (_, notification) in new_notifications.values() {
+                    Self::send_notification(
+                        None,
+                        notification.clone(),
+                        (principal, profile.clone()),
+                    );
+                }
+            }
+
+            if new_notifications.contains_key(&principal) || principal == caller() {
+                profiles_to_update.push((principal, profile));
+            }
+        }
+
+        profiles().update_many(profiles_to_update).await?;
+
+        Ok(new_notifications
+            .into_iter()
+            .map(|(principal, (id, _))| (principal, id))
+            .collect())
+    }
+
     pub async fn notification_owner_join_request_group_accept_or_decline(
         invitee_principal: Principal,
         invite: MemberInvite,
@@ -358,6 +457,79 @@ impl NotificationCalls {
         }
     }
 
+    /// Batch variant of `notification_change_group_member_role`, every receiver gets a single
+    /// `GroupNotice::MemberRolesChanged` with all members
+    pub async fn notification_change_group_member_roles(
+        members: Vec<JoinedMemberResponse>,
+        receivers: Vec<Principal>,
+    ) {
+        let Some(group_id) = members.first().map(|member| member.group_id) else {
+            return;
+        };
+
+        let notification = Self::new_group_notice(
+            group_id,
+            &GroupNotice::MemberRolesChanged {
+                members: members
+                    .into_iter()
+                    .map(|member| (member.principal, member.roles))
+                    .collect(),
+            },
+        );
+
+        let profiles = profiles().get_many(receivers).await.unwrap_or_default();
+        for receiver in profiles {
+            Self::send_notification(None, notification.clone(), receiver);
+        }
+    }
+
+    /// Batch variant of `notification_remove_group_member`, every removed member gets its own removal
+    /// and every receiver a single `GroupNotice::MembersRemoved` with all removed members
+    pub async fn notification_remove_group_members(
+        members: Vec<JoinedMemberResponse>,
+        receivers: Vec<Principal>,
+    ) {
+        let Some(group_id) = members.first().map(|member| member.group_id) else {
+            return;
+        };
+
+        let member_principals = members
+            .iter()
+            .map(|member| member.principal)
+            .collect::<Vec<_>>();
+
+        let profiles = profiles()
+            .get_many([member_principals.clone(), receivers.clone()].concat())
+            .await
+            .unwrap_or_default();
+
+        let notice = Self::new_group_notice(
+            group_id,
+            &GroupNotice::MembersRemoved {
+                principals: member_principals,
+            },
+        );
+
+        for (principal, profile) in profiles {
+            if let Some(member) = members.iter().find(|member| member.principal == principal) {
+                Self::send_notification(
+                    None,
+                    Notification::new(
+                        NotificationType::Group(GroupNotificationType::RemoveMemberByOwner(
+                            member.clone(),
+                        )),
+                        false,
+                    ),
+                    (principal, profile.clone()),
+                );
+            }
+
+            if receivers.contains(&principal) {
+                Self::send_notification(None, notice.clone(), (principal, profile));
+            }
+        }
+    }
+
     pub async fn notification_remove_group_member(
         member: JoinedMemberResponse,
         receivers: Vec<Principal>,
@@ -381,6 +553,98 @@ impl NotificationCalls {
         }
     }
 
+    /// Warns a member of a gated group that they no longer meet the gate,
+    /// they are removed when the grace period ends
+    pub async fn notification_gated_membership_grace_period(
+        principal: Principal,
+        group_id: u64,
+        expires_at: u64,
+    ) -> CanisterResult<u64> {
+        Self::notification_group_notice(
+            vec![principal],
+            group_id,
+            GroupNotice::GatedGracePeriodStarted { expires_at },
+        )
+        .await
+    }
+
+    /// Resolves the join request or invite of a principal that was put on the waitlist of the full group,
+    /// the request or invite is marked as accepted without telling the group and the principal gets a notice
+    pub async fn notification_join_waitlisted(
+        principal: Principal,
+        group_id: u64,
+        invite: MemberInvite,
+    ) -> CanisterResult<u64> {
+        if let Some(notification_id) = invite.notification_id {
+            let (_, mut notification) = notifications().get(notification_id).await?;
+
+            let notification_type = match notification.notification_type.clone() {
+                NotificationType::Group(GroupNotificationType::JoinGroupUserRequest(
+                    invite_member_response,
+                )) => GroupNotificationType::JoinGroupUserRequestAccept(invite_member_response),
+                NotificationType::Group(GroupNotificationType::JoinGroupOwnerRequest(
+                    invite_member_response,
+                )) => GroupNotificationType::JoinGroupOwnerRequestAccept(invite_member_response),
+                _ => {
+                    return Err(ApiError::bad_request()
+                        .add_message("Notification is not a group join request or invite"))
+                }
+            };
+
+            notification.mark_as_accepted(true, NotificationType::Group(notification_type));
+            notifications()
+                .update(notification_id, notification)
+                .await?;
+        }
+
+        Self::notification_group_notice(vec![principal], group_id, GroupNotice::Waitlisted).await
+    }
+
+    pub async fn notification_waitlist_admitted(
+        principal: Principal,
+        group_id: u64,
+    ) -> CanisterResult<u64> {
+        let profile = profiles().get(principal).await?;
+
+        let (notification_id, _) = Self::add_and_send_notification(
+            vec![profile],
+            NotificationType::Group(GroupNotificationType::UserJoinGroup(group_id)),
+            false,
+        )
+        .await?;
+
+        Ok(notification_id)
+    }
+
+    /// Sends a notice that has no variant in the shared `GroupNotificationType`,
+    /// see `GroupNotice` for how it is stored
+    pub async fn notification_group_notice(
+        receivers: Vec<Principal>,
+        group_id: u64,
+        notice: GroupNotice,
+    ) -> CanisterResult<u64> {
+        let receivers = profiles().get_many(receivers).await?;
+
+        let (notification_id, notification) =
+            Self::insert_notification(receivers.clone(), Self::new_group_notice(group_id, &notice))
+                .await?;
+
+        for receiver in receivers {
+            Self::send_notification(Some(notification_id), notification.clone(), receiver);
+        }
+
+        Ok(notification_id)
+    }
+
+    fn new_group_notice(group_id: u64, notice: &GroupNotice) -> Notification {
+        let mut notification = Notification::new(
+            NotificationType::Group(GroupNotificationType::GroupReminder(group_id)),
+            false,
+        );
+        notification.metadata = Some(notice.to_metadata());
+        notification
+    }
+
     pub async fn notification_remove_group_invite(
         invite: InviteMemberResponse,
         receivers: Vec<Principal>,
@@ -672,8 +936,8 @@ impl NotificationCalls {
         receivers: Vec<Principal>,
         notification: MultisigNotificationType,
     ) -> bool {
-        // Only the multisig can call this function
-        if caller().to_string() != MULTISIG_INDEX {
+        // Only trusted notifiers like the multisig index can call this function
+        if !AdminCalls::has_role(caller(), &[AdminRole::TrustedNotifier]) {
             return false;
         }
 
@@ -690,8 +954,8 @@ impl NotificationCalls {
         receivers: Vec<Principal>,
         notification: MultisigNotificationType,
     ) -> bool {
-        // Only the multisig can call this function
-        if caller().to_string() != MULTISIG_INDEX {
+        // Only trusted notifiers like the multisig index can call this function
+        if !AdminCalls::has_role(caller(), &[AdminRole::TrustedNotifier]) {
             return false;
         }
 
@@ -801,8 +1065,18 @@ impl NotificationCalls {
         notification_type: NotificationType,
         is_actionable: bool,
     ) -> CanisterResult<(u64, Notification)> {
-        let notification = Notification::new(notification_type, is_actionable);
+        Self::insert_notification(
+            receivers,
+            Notification::new(notification_type, is_actionable),
+        )
+        .await
+    }
 
+    /// Stores the notification and adds it to the notifications of the caller and the receivers
+    async fn insert_notification(
+        receivers: Vec<ProfileEntry>,
+        notification: Notification,
+    ) -> CanisterResult<(u64, Notification)> {
         let (new_notification_id, new_notification) = notifications().insert(notification).await?;
 
         if let Ok((principal, mut profile)) = profiles().get(caller()).await {
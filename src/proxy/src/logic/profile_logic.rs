@@ -1,5 +1,9 @@
 use super::notification_logic::NotificationCalls;
-use crate::storage::{events, global, groups, profiles};
+use crate::{
+    helpers::env::{caller, time},
+    models::TokenSubaccounts,
+    storage::{events, global, groups, profiles, token_subaccounts},
+};
 use candid::Principal;
 use catalyze_shared::{
     api_error::ApiError,
@@ -15,7 +19,13 @@ use catalyze_shared::{
     wallet::{PostWallet, Wallet},
     CanisterResult, Filter, StorageClient, StorageClientInsertableByKey,
 };
-use ic_cdk::{api::time, caller};
+
+/// The maximum number of subaccounts a principal can register for token gating,
+/// every subaccount is one more balance call for each ICRC-1 condition
+pub const MAX_TOKEN_SUBACCOUNTS: usize = 5;
+
+/// The length of an ICRC-1 subaccount
+const SUBACCOUNT_LENGTH: usize = 32;
 
 pub struct ProfileCalls;
 pub struct ProfileValidation;
@@ -98,6 +108,41 @@ impl ProfileCalls {
         ProfileResponse::from(profiles().update(caller(), existing_profile).await?).to_result()
     }
 
+    /// Replaces the ICRC subaccounts of the caller that are checked for token gating,
+    /// an empty list removes them
+    pub fn set_token_subaccounts(subaccounts: Vec<Vec<u8>>) -> CanisterResult<Vec<Vec<u8>>> {
+        ProfileValidation::validate_token_subaccounts(&subaccounts)?;
+
+        let mut unique_subaccounts: Vec<Vec<u8>> = vec![];
+        for subaccount in subaccounts {
+            if !unique_subaccounts.contains(&subaccount) {
+                unique_subaccounts.push(subaccount);
+            }
+        }
+
+        if unique_subaccounts.is_empty() {
+            token_subaccounts().remove(caller());
+            return Ok(vec![]);
+        }
+
+        let (_, registered) = token_subaccounts().insert(
+            caller(),
+            TokenSubaccounts {
+                subaccounts: unique_subaccounts,
+                updated_at: time(),
+            },
+        );
+
+        Ok(registered.subaccounts)
+    }
+
+    pub fn get_token_subaccounts(principal: Principal) -> Vec<Vec<u8>> {
+        token_subaccounts()
+            .find(principal)
+            .map(|token_subaccounts| token_subaccounts.subaccounts)
+            .unwrap_or_default()
+    }
+
     pub async fn remove_wallet_from_profile(
         principal: Principal,
     ) -> CanisterResult<ProfileResponse> {
@@ -414,6 +459,25 @@ impl ProfileCalls {
 }
 
 impl ProfileValidation {
+    pub fn validate_token_subaccounts(subaccounts: &[Vec<u8>]) -> CanisterResult<()> {
+        if subaccounts.len() > MAX_TOKEN_SUBACCOUNTS {
+            return Err(ApiError::bad_request().add_message(format!(
+                "Can not register more than {} subaccounts",
+                MAX_TOKEN_SUBACCOUNTS
+            )));
+        }
+
+        if subaccounts
+            .iter()
+            .any(|subaccount| subaccount.len() != SUBACCOUNT_LENGTH)
+        {
+            return Err(ApiError::bad_request()
+                .add_message(format!("Subaccounts must be {} bytes", SUBACCOUNT_LENGTH)));
+        }
+
+        Ok(())
+    }
+
     pub fn validate_post_profile(post_profile: &PostProfile) -> Result<(), ApiError> {
         let validator_fields = vec![
             ValidateField(
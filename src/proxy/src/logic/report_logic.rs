@@ -4,9 +4,11 @@ use catalyze_shared::{
     report::{PostReport, Report, ReportFilter, ReportResponse, ReportSort},
     CanisterResult, StorageClient, StorageClientInsertable,
 };
-use ic_cdk::caller;
 
-use crate::storage::{groups, reports};
+use crate::{
+    helpers::env::caller,
+    storage::{groups, reports},
+};
 
 use super::profile_logic::ProfileCalls;
 
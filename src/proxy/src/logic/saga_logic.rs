@@ -0,0 +1,327 @@
+use candid::Principal;
+use catalyze_shared::{
+    api_error::ApiError, relation_type::RelationType, subject::Subject, CanisterResult,
+    StorageClient, StorageClientInsertable,
+};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+};
+
+use crate::{
+    helpers::env::caller,
+    models::{Saga, SagaKind, SagaStatus, SagaStep},
+    storage::{boosts, events, friend_requests, groups, notifications, profiles, reports, sagas},
+};
+
+/// A saga that is running or compensating without an update for this long is considered stuck
+const SAGA_STUCK_TIMEOUT: u64 = 10 * 60 * 1_000_000_000;
+
+thread_local! {
+    /// The sagas that are executing in this canister right now, an upgrade waits for outstanding
+    /// calls so a saga that is journaled as running but not in here was interrupted
+    static EXECUTING_SAGAS: RefCell<HashSet<u64>> = RefCell::new(HashSet::new());
+}
+
+pub struct SagaCalls;
+
+impl SagaCalls {
+    /// Journals and executes a saga
+    /// # Arguments
+    /// * `kind` - The operation the saga belongs to
+    /// * `completed` - Steps the caller already executed, only used for compensation
+    /// * `pending` - Steps to execute in order
+    /// # Errors
+    /// * `ApiError` - The error of the first failing step
+    /// # Note
+    /// On failure the completed steps are compensated in reverse order when all of them can be compensated,
+    /// otherwise the saga stays in the journal as `Failed` so it can be retried.
+    pub async fn run(
+        kind: SagaKind,
+        completed: Vec<SagaStep>,
+        pending: Vec<SagaStep>,
+    ) -> CanisterResult<()> {
+        let (id, _) = sagas().insert_next(Saga::new(kind, caller(), completed, pending));
+
+        if let Err(err) = Self::execute(id).await {
+            if sagas().get(id)?.can_compensate() {
+                // a failed compensation is kept in the journal, the original error is returned
+                let _ = Self::compensate(id).await;
+            }
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    pub fn get_stuck_sagas() -> Vec<(u64, Saga)> {
+        sagas().filter(|_, saga| saga.is_stuck(SAGA_STUCK_TIMEOUT))
+    }
+
+    /// Returns the first saga in the journal that matches the predicate
+    pub fn find_saga<P: Fn(&Saga) -> bool>(predicate: P) -> Option<(u64, Saga)> {
+        sagas().filter(|_, saga| predicate(saga)).into_iter().next()
+    }
+
+    pub async fn retry_saga(id: u64) -> CanisterResult<()> {
+        Self::get_stuck_saga(id, "retry_saga")?;
+        Self::execute(id).await
+    }
+
+    fn is_executing(id: u64) -> bool {
+        EXECUTING_SAGAS.with(|executing| executing.borrow().contains(&id))
+    }
+
+    /// Continues a saga with its next pending step, unlike `retry_saga` it does not wait for the
+    /// stuck timeout and only refuses a saga that is executing right now. A saga whose execution
+    /// trapped stays in `EXECUTING_SAGAS`, so it can be resumed again once it is stuck.
+    pub async fn resume_saga(id: u64) -> CanisterResult<()> {
+        let saga = sagas().get(id)?;
+
+        if Self::is_executing(id) && !saga.is_stuck(SAGA_STUCK_TIMEOUT) {
+            return Err(ApiError::bad_request()
+                .add_method_name("resume_saga")
+                .add_message("Saga is still in progress"));
+        }
+
+        Self::execute(id).await
+    }
+
+    pub async fn rollback_saga(id: u64) -> CanisterResult<()> {
+        let saga = Self::get_stuck_saga(id, "rollback_saga")?;
+
+        if !saga.can_compensate() {
+            return Err(ApiError::unsupported()
+                .add_method_name("rollback_saga")
+                .add_message("Saga contains steps that can't be compensated, retry it instead"));
+        }
+
+        EXECUTING_SAGAS.with(|executing| executing.borrow_mut().insert(id));
+        let result = Self::compensate(id).await;
+        EXECUTING_SAGAS.with(|executing| executing.borrow_mut().remove(&id));
+        result
+    }
+
+    /// A stuck saga that is not executing right now, a saga that only takes long to execute
+    /// is refused so its steps or compensations don't run twice
+    fn get_stuck_saga(id: u64, method_name: &str) -> CanisterResult<Saga> {
+        let saga = sagas().get(id)?;
+
+        if !saga.is_stuck(SAGA_STUCK_TIMEOUT) || Self::is_executing(id) {
+            return Err(ApiError::bad_request()
+                .add_method_name(method_name)
+                .add_message("Saga is still in progress"));
+        }
+
+        Ok(saga)
+    }
+
+    /// Executes the pending steps, the saga is removed from the journal once all steps succeeded
+    async fn execute(id: u64) -> CanisterResult<()> {
+        EXECUTING_SAGAS.with(|executing| executing.borrow_mut().insert(id));
+        let result = Self::execute_steps(id).await;
+        EXECUTING_SAGAS.with(|executing| executing.borrow_mut().remove(&id));
+        result
+    }
+
+    async fn execute_steps(id: u64) -> CanisterResult<()> {
+        let mut saga = sagas().get(id)?;
+        saga.set_status(SagaStatus::Running);
+        sagas().insert(id, saga.clone());
+
+        while let Some(step) = saga.next_step() {
+            if let Err(err) = Self::forward(step).await {
+                saga.set_error(SagaStatus::Failed, err.to_string());
+                sagas().insert(id, saga);
+                return Err(err);
+            }
+
+            saga.completed_steps += 1;
+            saga.set_status(SagaStatus::Running);
+            sagas().insert(id, saga.clone());
+        }
+
+        sagas().remove(id);
+        Ok(())
+    }
+
+    /// Compensates the completed steps in reverse order, the saga is removed from the journal once all steps are undone
+    async fn compensate(id: u64) -> CanisterResult<()> {
+        let mut saga = sagas().get(id)?;
+        saga.set_status(SagaStatus::Compensating);
+        sagas().insert(id, saga.clone());
+
+        while let Some(step) = saga.last_completed_step() {
+            if let Err(err) = Self::backward(step).await {
+                saga.set_error(SagaStatus::CompensationFailed, err.to_string());
+                sagas().insert(id, saga);
+                return Err(err);
+            }
+
+            saga.completed_steps -= 1;
+            saga.set_status(SagaStatus::Compensating);
+            sagas().insert(id, saga.clone());
+        }
+
+        sagas().remove(id);
+        Ok(())
+    }
+
+    async fn forward(step: SagaStep) -> CanisterResult<()> {
+        match step {
+            // executed before the saga is started
+            SagaStep::InsertEvent(_) => {}
+            SagaStep::AddEventToGroup { group_id, event_id } => {
+                let (_, mut group) = groups().get(group_id).await?;
+                if !group.events.contains(&event_id) {
+                    group.add_event(event_id);
+                    groups().update(group_id, group).await?;
+                }
+            }
+            SagaStep::AddEventToProfile {
+                principal,
+                event_id,
+            } => {
+                let (_, mut profile) = profiles().get(principal).await?;
+                profile.add_event(event_id);
+                profiles().update(principal, profile).await?;
+            }
+            SagaStep::AddFriend { principal, friend } => {
+                let (_, mut profile) = profiles().get(principal).await?;
+                profile
+                    .references
+                    .relations
+                    .insert(friend, RelationType::Friend.to_string());
+                profiles().update(principal, profile).await?;
+            }
+            SagaStep::RemoveFriendRequest(friend_request_id) => {
+                friend_requests().remove(friend_request_id).await?;
+            }
+            SagaStep::RemoveBoost(boost_id) => {
+                boosts().remove(boost_id).await?;
+            }
+            SagaStep::RemoveGroupFromProfiles {
+                group_id,
+                principals,
+            } => {
+                let subject = Subject::Group(group_id);
+
+                let profile_list = profiles()
+                    .get_many(principals)
+                    .await?
+                    .into_iter()
+                    .map(|(id, mut profile)| {
+                        if profile.is_starred(&subject) || profile.is_pinned(&subject) {
+                            profile.remove_starred(&subject);
+                            profile.remove_pinned(&subject);
+                        }
+
+                        profile.remove_group(group_id);
+                        (id, profile)
+                    })
+                    .collect::<Vec<_>>();
+
+                profiles().update_many(profile_list).await?;
+            }
+            SagaStep::RemoveEvents(event_ids) => {
+                events().remove_many(event_ids).await?;
+            }
+            SagaStep::RemoveGroup(group_id) => {
+                groups().remove(group_id).await?;
+            }
+            SagaStep::RemoveBoosts(boost_ids) => {
+                boosts().remove_many(boost_ids).await?;
+            }
+            SagaStep::RemoveEventsFromProfiles {
+                event_ids,
+                principals,
+            } => {
+                let profile_list = profiles()
+                    .get_many(principals)
+                    .await?
+                    .into_iter()
+                    .map(|(id, mut profile)| {
+                        for event_id in &event_ids {
+                            let subject = Subject::Event(*event_id);
+
+                            if profile.is_starred(&subject) || profile.is_pinned(&subject) {
+                                profile.remove_starred(&subject);
+                                profile.remove_pinned(&subject);
+                            }
+
+                            profile.remove_event(*event_id);
+                        }
+                        (id, profile)
+                    })
+                    .collect::<Vec<_>>();
+
+                profiles().update_many(profile_list).await?;
+            }
+            SagaStep::RemoveNotifications(receivers) => {
+                let mut notification_ids: HashMap<Principal, Vec<u64>> = HashMap::new();
+
+                for (principal, notification_id) in receivers {
+                    notification_ids
+                        .entry(principal)
+                        .or_default()
+                        .push(notification_id);
+                }
+
+                let profile_list = profiles()
+                    .get_many(notification_ids.keys().cloned().collect())
+                    .await?
+                    .into_iter()
+                    .map(|(id, mut profile)| {
+                        if let Some(ids) = notification_ids.get(&id) {
+                            profile.references.notifications.remove_many(ids.clone());
+                        }
+                        (id, profile)
+                    })
+                    .collect::<Vec<_>>();
+
+                profiles().update_many(profile_list).await?;
+                notifications()
+                    .remove_many(notification_ids.into_values().flatten().collect())
+                    .await?;
+            }
+            SagaStep::RemoveReports(report_ids) => {
+                reports().remove_many(report_ids).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn backward(step: SagaStep) -> CanisterResult<()> {
+        match step {
+            SagaStep::InsertEvent(event_id) => {
+                events().remove(event_id).await?;
+            }
+            SagaStep::AddEventToGroup { group_id, event_id } => {
+                let (_, mut group) = groups().get(group_id).await?;
+                group.remove_event(event_id);
+                groups().update(group_id, group).await?;
+            }
+            SagaStep::AddEventToProfile {
+                principal,
+                event_id,
+            } => {
+                let (_, mut profile) = profiles().get(principal).await?;
+                profile.remove_event(event_id);
+                profiles().update(principal, profile).await?;
+            }
+            SagaStep::AddFriend { principal, friend } => {
+                let (_, mut profile) = profiles().get(principal).await?;
+                profile.references.relations.remove(&friend);
+                profiles().update(principal, profile).await?;
+            }
+            step => {
+                return Err(ApiError::unsupported()
+                    .add_method_name("compensate")
+                    .add_message(format!("{:?} can't be compensated", step)))
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -8,6 +8,8 @@ use catalyze_shared::{
 
 use crate::storage::topics;
 
+use super::config_logic::ConfigCalls;
+
 pub struct TopicCalls;
 
 impl TopicCalls {
@@ -44,15 +46,18 @@ impl TopicCalls {
     }
 }
 
-const TOPIC_MAX_LENGTH: usize = 32;
-const TOPIC_MIN_LENGTH: usize = 1;
-
 async fn handle_topic(kind: TopicKind, topic: String) -> CanisterResult<String> {
     let topic = topic.to_lowercase();
     let topic = topic.trim().to_owned();
 
+    let config = ConfigCalls::get_platform_config();
+
     Validator::new(vec![ValidateField(
-        ValidationType::StringLength(topic.clone(), TOPIC_MIN_LENGTH, TOPIC_MAX_LENGTH),
+        ValidationType::StringLength(
+            topic.clone(),
+            config.topic_min_length as usize,
+            config.topic_max_length as usize,
+        ),
         kind.to_string(),
     )])
     .validate()?;
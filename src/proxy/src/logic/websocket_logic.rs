@@ -1,14 +1,15 @@
 use std::{cell::RefCell, collections::HashMap};
 
 use candid::Principal;
-use catalyze_shared::{websocket_message::WSMessage, StorageClient};
-use ic_cdk::api::time;
+use catalyze_shared::websocket_message::WSMessage;
 use ic_websocket_cdk::{
-    send as ws_send, OnCloseCallbackArgs, OnMessageCallbackArgs, OnOpenCallbackArgs, WsHandlers,
-    WsInitParams,
+    OnCloseCallbackArgs, OnMessageCallbackArgs, OnOpenCallbackArgs, WsHandlers, WsInitParams,
 };
 
-use crate::storage::{global, profiles};
+use crate::{
+    helpers::env::{spawn, time, ws_send},
+    storage::{global, profiles},
+};
 
 thread_local! {
    pub static CONNECTED_CLIENTS: RefCell<HashMap<Principal, u64>> = RefCell::new(HashMap::new());
@@ -33,11 +34,11 @@ impl Websocket {
     pub fn on_open(args: OnOpenCallbackArgs) {
         Self::add_connected_to_clients(args.client_principal);
 
-        ic_cdk::spawn(async move {
+        spawn(async move {
             let _ = global().notify_active_user(args.client_principal).await;
         });
 
-        ic_cdk::spawn(async move {
+        spawn(async move {
             let notifications = profiles()
                 .get(args.client_principal)
                 .await
@@ -0,0 +1,39 @@
+use candid::{CandidType, Deserialize, Principal};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CandidType, Deserialize)]
+pub enum AdminRole {
+    Developer,
+    /// Developers allowed to call the production only endpoints, like `_dev_create_canister`
+    ProdDeveloper,
+    Monitor,
+    Moderator,
+    /// Canisters that are allowed to push notifications, like the multisig index
+    TrustedNotifier,
+}
+
+/// The admin roles of a single principal
+#[derive(Clone, Debug, Default, CandidType, Deserialize)]
+pub struct AdminRoles {
+    pub roles: Vec<AdminRole>,
+    pub updated_at: u64,
+}
+
+impl_storable_for!(AdminRoles);
+
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub enum AdminAction {
+    Added,
+    Removed,
+}
+
+/// A change to the admin registry
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct AdminAuditEntry {
+    pub action: AdminAction,
+    pub principal: Principal,
+    pub role: AdminRole,
+    pub changed_by: Principal,
+    pub created_at: u64,
+}
+
+impl_storable_for!(AdminAuditEntry);
@@ -0,0 +1,34 @@
+use candid::{CandidType, Deserialize, Principal};
+
+/// A deleted group that can still be restored by its owner until `purge_at`
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct ArchivedGroup {
+    pub group_id: u64,
+    pub owner: Principal,
+    pub archived_by: Principal,
+    pub archived_at: u64,
+    /// The time after which the group and its data are removed for good
+    pub purge_at: u64,
+    /// Set once the group and its data are removed
+    pub purged: Option<GroupPurgeSummary>,
+}
+
+impl_storable_for!(ArchivedGroup);
+
+impl ArchivedGroup {
+    pub fn can_restore(&self, now: u64) -> bool {
+        self.purged.is_none() && now < self.purge_at
+    }
+}
+
+/// What was removed when an archived group was purged
+#[derive(Clone, Debug, Default, CandidType, Deserialize)]
+pub struct GroupPurgeSummary {
+    pub events: u64,
+    pub boosts: u64,
+    /// Profiles that referenced the group or one of its events
+    pub profiles: u64,
+    pub invite_notifications: u64,
+    pub reports: u64,
+    pub purged_at: u64,
+}
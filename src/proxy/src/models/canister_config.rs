@@ -0,0 +1,46 @@
+use candid::{CandidType, Deserialize, Principal};
+
+/// The storage canister topology the proxy talks to, one entry per cell in `storage_api`.
+/// Used as the `init` / `post_upgrade` argument and by the `_dev_*_canister_config` calls.
+#[derive(Clone, Debug, Default, CandidType, Deserialize)]
+pub struct CanisterConfig {
+    pub history: Option<Principal>,
+    pub profile: Option<Principal>,
+    pub group: Option<Principal>,
+    pub event: Option<Principal>,
+    pub boost: Option<Principal>,
+    pub topic: Option<Principal>,
+    pub friend_request: Option<Principal>,
+    pub global: Option<Principal>,
+    pub notification: Option<Principal>,
+    pub report: Option<Principal>,
+    pub transaction_handler: Option<Principal>,
+}
+
+impl CanisterConfig {
+    /// Returns every entry by name
+    pub fn entries(&self) -> Vec<(&'static str, Option<Principal>)> {
+        vec![
+            ("history", self.history),
+            ("profile", self.profile),
+            ("group", self.group),
+            ("event", self.event),
+            ("boost", self.boost),
+            ("topic", self.topic),
+            ("friend_request", self.friend_request),
+            ("global", self.global),
+            ("notification", self.notification),
+            ("report", self.report),
+            ("transaction_handler", self.transaction_handler),
+        ]
+    }
+
+    /// Returns the names of the entries that are not set
+    pub fn missing(&self) -> Vec<&'static str> {
+        self.entries()
+            .into_iter()
+            .filter(|(_, principal)| principal.is_none())
+            .map(|(name, _)| name)
+            .collect()
+    }
+}
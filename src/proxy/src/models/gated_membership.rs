@@ -0,0 +1,30 @@
+use candid::{CandidType, Deserialize, Principal};
+
+/// A member of a gated group that no longer meets the gate,
+/// they are removed when the gate is still not met after `expires_at`
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct GatedGracePeriod {
+    pub group_id: u64,
+    pub principal: Principal,
+    pub notification_id: Option<u64>,
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+impl_storable_for!(GatedGracePeriod);
+
+impl GatedGracePeriod {
+    pub fn key(group_id: u64, principal: Principal) -> String {
+        format!("{}-{}", group_id, principal)
+    }
+}
+
+/// The members a re-validation run removed from a gated group
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct GatedRemovalReport {
+    pub group_id: u64,
+    pub removed: Vec<Principal>,
+    pub created_at: u64,
+}
+
+impl_storable_for!(GatedRemovalReport);
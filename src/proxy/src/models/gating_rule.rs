@@ -0,0 +1,217 @@
+use candid::{CandidType, Deserialize, Principal};
+use catalyze_shared::privacy::{GatedType, NeuronGated, NeuronGatedRules, TokenGated};
+
+use super::TokenStandard;
+
+/// A requirement to join a gated group, rules can be combined into a tree
+/// like "hold 1 NFT of collection A and stake 10 ICP"
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub enum GatingRule {
+    Token(TokenGated),
+    /// A `NeuronGated` of the group privacy, checked against the NNS governance canister
+    Neuron(NeuronGated),
+    SnsNeuron(SnsNeuronGated),
+    /// Every rule has to be met
+    All(Vec<GatingRule>),
+    /// One of the rules has to be met
+    Any(Vec<GatingRule>),
+    /// At least `amount` of the rules have to be met
+    AtLeast {
+        amount: u64,
+        rules: Vec<GatingRule>,
+    },
+}
+
+impl GatingRule {
+    /// The rule of a group that is gated by a `GatedType` only,
+    /// at least `amount` (and at least one) of its canisters have to be met
+    pub fn from_gated_type(gated_type: GatedType, amount: Option<u64>) -> Self {
+        let rules = match gated_type {
+            GatedType::Token(tokens) => tokens.into_iter().map(GatingRule::Token).collect(),
+            GatedType::Neuron(neurons) => neurons.into_iter().map(GatingRule::Neuron).collect(),
+        };
+
+        GatingRule::AtLeast {
+            amount: amount.unwrap_or_default().max(1),
+            rules,
+        }
+    }
+
+    /// The rule with every EXT token counted as met, EXT balances need
+    /// the account identifier of the member which is only known when joining
+    pub fn with_ext_as_met(self) -> Self {
+        use GatingRule::*;
+        match self {
+            Token(token) if token.standard == TokenStandard::Ext.as_str() => All(vec![]),
+            Token(token) => Token(token),
+            Neuron(neuron) => Neuron(neuron),
+            SnsNeuron(neuron) => SnsNeuron(neuron),
+            All(rules) => All(rules.into_iter().map(Self::with_ext_as_met).collect()),
+            Any(rules) => Any(rules.into_iter().map(Self::with_ext_as_met).collect()),
+            AtLeast { amount, rules } => AtLeast {
+                amount,
+                rules: rules.into_iter().map(Self::with_ext_as_met).collect(),
+            },
+        }
+    }
+
+    /// The number of token and neuron conditions in the tree
+    pub fn conditions(&self) -> usize {
+        use GatingRule::*;
+        match self {
+            Token(_) | Neuron(_) | SnsNeuron(_) => 1,
+            All(rules) | Any(rules) | AtLeast { rules, .. } => {
+                rules.iter().map(Self::conditions).sum()
+            }
+        }
+    }
+
+    /// The token and neuron conditions of the tree, in the order `is_met_by` reads their results
+    pub fn get_conditions(&self) -> Vec<&GatingRule> {
+        use GatingRule::*;
+        match self {
+            Token(_) | Neuron(_) | SnsNeuron(_) => vec![self],
+            All(rules) | Any(rules) | AtLeast { rules, .. } => {
+                rules.iter().flat_map(Self::get_conditions).collect()
+            }
+        }
+    }
+
+    /// Evaluates the tree with the results of `get_conditions`, every result is read
+    pub fn is_met_by(&self, results: &mut dyn Iterator<Item = bool>) -> bool {
+        self.try_is_met_by(&mut results.map(Some))
+            .unwrap_or_default()
+    }
+
+    /// Evaluates the tree with the results of `get_conditions` where `None` is a condition that
+    /// could not be checked, `None` when the outcome depends on those conditions
+    pub fn try_is_met_by(&self, results: &mut dyn Iterator<Item = Option<bool>>) -> Option<bool> {
+        use GatingRule::*;
+        match self {
+            Token(_) | Neuron(_) | SnsNeuron(_) => results.next().unwrap_or(Some(false)),
+            All(rules) => {
+                let results = rules
+                    .iter()
+                    .map(|rule| rule.try_is_met_by(results))
+                    .collect::<Vec<_>>();
+
+                if results.contains(&Some(false)) {
+                    Some(false)
+                } else if results.contains(&None) {
+                    None
+                } else {
+                    Some(true)
+                }
+            }
+            Any(rules) => {
+                let results = rules
+                    .iter()
+                    .map(|rule| rule.try_is_met_by(results))
+                    .collect::<Vec<_>>();
+
+                if results.contains(&Some(true)) {
+                    Some(true)
+                } else if results.contains(&None) {
+                    None
+                } else {
+                    Some(false)
+                }
+            }
+            AtLeast { amount, rules } => {
+                let results = rules
+                    .iter()
+                    .map(|rule| rule.try_is_met_by(results))
+                    .collect::<Vec<_>>();
+
+                let met = results
+                    .iter()
+                    .filter(|result| **result == Some(true))
+                    .count() as u64;
+                let unknown = results.iter().filter(|result| result.is_none()).count() as u64;
+
+                if met >= *amount {
+                    Some(true)
+                } else if met + unknown < *amount {
+                    Some(false)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        use GatingRule::*;
+        match self {
+            Token(_) | Neuron(_) | SnsNeuron(_) => 1,
+            All(rules) | Any(rules) | AtLeast { rules, .. } => {
+                1 + rules.iter().map(Self::depth).max().unwrap_or_default()
+            }
+        }
+    }
+}
+
+/// A neuron of the principal on the SNS governance canister has to meet every rule
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct SnsNeuronGated {
+    pub governance_canister: Principal,
+    pub rules: Vec<NeuronRule>,
+}
+
+/// The stake, voting power and dissolve delay are in e8s and seconds
+#[derive(Clone, Debug, PartialEq, Eq, CandidType, Deserialize)]
+pub enum NeuronRule {
+    IsDissolving,
+    MinStake(u64),
+    MinAge(u64),
+    MinDissolveDelay(u64),
+    MinVotingPower(u64),
+}
+
+impl From<NeuronGatedRules> for NeuronRule {
+    fn from(rule: NeuronGatedRules) -> Self {
+        use NeuronGatedRules::*;
+        match rule {
+            IsDisolving(_) => NeuronRule::IsDissolving,
+            MinStake(min_stake) => NeuronRule::MinStake(min_stake),
+            MinAge(min_age) => NeuronRule::MinAge(min_age),
+            MinDissolveDelay(min_dissolve_delay) => {
+                NeuronRule::MinDissolveDelay(min_dissolve_delay)
+            }
+        }
+    }
+}
+
+/// The gating rule of a group
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct GroupGatingRule {
+    pub rule: GatingRule,
+    /// Set by the owner, otherwise it follows the `GatedType` of the group
+    pub is_custom: bool,
+    pub updated_at: u64,
+}
+
+impl_storable_for!(GroupGatingRule);
+
+/// The outcome of a single token or neuron condition of a gating rule
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct GatingConditionCheck {
+    /// The token standard, `NEURON` for neuron conditions
+    pub standard: String,
+    /// The token canister or the governance canister of the neuron
+    pub canister: Principal,
+    /// The balance to hold, or the number of neurons that meet the neuron rules
+    pub required_amount: u128,
+    /// `None` when it could not be read, like an EXT balance without account identifier
+    /// or a failed call to the token or governance canister
+    pub observed_amount: Option<u128>,
+    pub passed: bool,
+}
+
+/// Whether the caller can join a gated group and what they are missing
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct GroupEligibility {
+    pub group_id: u64,
+    pub eligible: bool,
+    pub checks: Vec<GatingConditionCheck>,
+}
@@ -0,0 +1,62 @@
+use candid::{CandidType, Deserialize};
+use catalyze_shared::{date_range::DateRange, profile_with_refs::ProfileWithRefs};
+
+/// Filters for the paged group member listing
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub enum GroupMemberFilter {
+    Role(String),
+    /// Case insensitive substring of the username or display name
+    Name(String),
+    JoinedOn(DateRange),
+}
+
+impl GroupMemberFilter {
+    pub fn is_match(
+        &self,
+        roles: &[String],
+        joined_at: u64,
+        profile: Option<&ProfileWithRefs>,
+    ) -> bool {
+        match self {
+            GroupMemberFilter::Role(role) => roles.contains(role),
+            GroupMemberFilter::Name(name) => {
+                let name = name.to_lowercase();
+                profile.is_some_and(|profile| {
+                    profile.metadata.username.to_lowercase().contains(&name)
+                        || profile.metadata.display_name.to_lowercase().contains(&name)
+                })
+            }
+            GroupMemberFilter::JoinedOn(range) => range.is_within(joined_at),
+        }
+    }
+
+    pub fn needs_profile(&self) -> bool {
+        matches!(self, GroupMemberFilter::Name(_))
+    }
+}
+
+#[derive(Clone, Debug, Default, CandidType, Deserialize)]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Sorting of the paged group member listing, the name sorts on the display name
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub enum GroupMemberSort {
+    JoinedOn(SortOrder),
+    Name(SortOrder),
+}
+
+impl Default for GroupMemberSort {
+    fn default() -> Self {
+        GroupMemberSort::JoinedOn(SortOrder::Asc)
+    }
+}
+
+impl GroupMemberSort {
+    pub fn needs_profile(&self) -> bool {
+        matches!(self, GroupMemberSort::Name(_))
+    }
+}
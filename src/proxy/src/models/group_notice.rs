@@ -0,0 +1,33 @@
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+
+/// Group notifications the shared `GroupNotificationType` has no variant for. They are stored
+/// as a `GroupReminder` of the group with the notice as JSON in the `metadata` of the
+/// notification, clients tell them apart from a plain reminder by the metadata.
+#[derive(Clone, Debug, PartialEq, CandidType, Serialize, Deserialize)]
+pub enum GroupNotice {
+    /// `from` proposed the receiver as the new owner of the group
+    OwnershipTransferProposed { from: Principal },
+    /// The roles of multiple members changed at once
+    MemberRolesChanged {
+        members: Vec<(Principal, Vec<String>)>,
+    },
+    /// Multiple members were removed at once
+    MembersRemoved { principals: Vec<Principal> },
+    /// The receiver no longer meets the gate of the group and is removed at `expires_at`
+    /// unless they meet it again by then
+    GatedGracePeriodStarted { expires_at: u64 },
+    /// The accepted join request or invite of the receiver put them on the waitlist of the full group,
+    /// they are notified again when they are admitted
+    Waitlisted,
+}
+
+impl GroupNotice {
+    pub fn to_metadata(&self) -> String {
+        serde_json::to_string(self).expect("Failed to serialize group notice")
+    }
+
+    pub fn from_metadata(metadata: &str) -> Option<Self> {
+        serde_json::from_str(metadata).ok()
+    }
+}
@@ -0,0 +1,32 @@
+use candid::{CandidType, Deserialize, Principal};
+
+/// The health of a single canister in the topology
+#[derive(Clone, Debug, Default, CandidType, Deserialize)]
+pub struct CanisterHealth {
+    pub name: String,
+    pub canister_id: Option<Principal>,
+    pub configured: bool,
+    pub reachable: bool,
+    pub icts_version: Option<String>,
+    /// Nanoseconds between sending the `icts_version` call and receiving its response
+    pub latency: Option<u64>,
+    /// Only set for the storage canisters
+    pub size: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl CanisterHealth {
+    pub fn is_healthy(&self) -> bool {
+        self.configured && self.reachable && self.error.is_none()
+    }
+}
+
+/// The health of the proxy and every canister it talks to
+#[derive(Clone, Debug, Default, CandidType, Deserialize)]
+pub struct HealthReport {
+    pub version: String,
+    pub cycles: u128,
+    pub canisters: Vec<CanisterHealth>,
+    pub healthy: bool,
+    pub checked_at: u64,
+}
@@ -0,0 +1,34 @@
+use candid::{CandidType, Deserialize, Principal};
+
+/// A shareable code that lets anyone join a group without an invite
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct InviteCode {
+    pub code: String,
+    pub group_id: u64,
+    /// The role the member gets when joining with the code
+    pub role: String,
+    pub expires_at: Option<u64>,
+    pub max_uses: Option<u64>,
+    pub uses: u64,
+    pub created_by: Principal,
+    pub created_at: u64,
+}
+
+impl_storable_for!(InviteCode);
+
+impl InviteCode {
+    pub fn is_usable(&self, now: u64) -> bool {
+        let not_expired = self.expires_at.map_or(true, |expires_at| now < expires_at);
+        let uses_left = self.max_uses.map_or(true, |max_uses| self.uses < max_uses);
+        not_expired && uses_left
+    }
+}
+
+#[derive(Clone, Debug, Default, CandidType, Deserialize)]
+pub struct PostInviteCode {
+    /// Nanoseconds since the epoch after which the code can no longer be used
+    pub expires_at: Option<u64>,
+    pub max_uses: Option<u64>,
+    /// Defaults to the `member` role
+    pub role: Option<String>,
+}
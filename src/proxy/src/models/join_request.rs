@@ -0,0 +1,24 @@
+use candid::{CandidType, Deserialize, Principal};
+use catalyze_shared::old_member::JoinedMemberResponse;
+
+/// A pending request to join a private group, keyed by the id of its notification
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct JoinRequest {
+    pub group_id: u64,
+    pub principal: Principal,
+    pub notification_id: u64,
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+impl_storable_for!(JoinRequest);
+
+/// The outcome of `join_group`, only `Joined` makes the caller a member right away
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub enum JoinGroupResult {
+    Joined(JoinedMemberResponse),
+    /// A join request was sent to the private group, the caller joins once it is accepted
+    Requested,
+    /// The group is full, the caller joins when a spot opens
+    Waitlisted,
+}
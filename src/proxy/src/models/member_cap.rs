@@ -0,0 +1,28 @@
+use candid::{CandidType, Deserialize, Principal};
+
+/// The maximum number of members of a group, joins beyond it are put on the waitlist
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct GroupMemberCap {
+    pub max_members: u64,
+    /// Admitted in order when a spot opens
+    pub waitlist: Vec<WaitlistEntry>,
+    pub updated_at: u64,
+}
+
+impl_storable_for!(GroupMemberCap);
+
+impl GroupMemberCap {
+    pub fn is_waitlisted(&self, principal: Principal) -> bool {
+        self.waitlist
+            .iter()
+            .any(|entry| entry.principal == principal)
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct WaitlistEntry {
+    pub principal: Principal,
+    /// The role of the invite code the principal joined with, the default role otherwise
+    pub role: Option<String>,
+    pub created_at: u64,
+}
@@ -0,0 +1,55 @@
+/// Implements `Storable` for a candid type so it can be kept in stable memory
+macro_rules! impl_storable_for {
+    ($t:ty) => {
+        impl ic_stable_structures::Storable for $t {
+            fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+                std::borrow::Cow::Owned(candid::Encode!(self).expect("Failed to encode"))
+            }
+
+            fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+                candid::Decode!(bytes.as_ref(), Self).expect("Failed to decode")
+            }
+
+            const BOUND: ic_stable_structures::storable::Bound =
+                ic_stable_structures::storable::Bound::Unbounded;
+        }
+    };
+}
+
+mod admin;
+mod archived_group;
+mod canister_config;
+mod gated_membership;
+mod gating_rule;
+mod group_member;
+mod group_notice;
+mod health;
+mod invite_code;
+mod join_request;
+mod member_cap;
+mod ownership_transfer;
+mod platform_config;
+mod role_rank;
+mod saga;
+mod task_state;
+mod token_standard;
+
+// Re-export models
+
+pub use admin::*;
+pub use archived_group::*;
+pub use canister_config::*;
+pub use gated_membership::*;
+pub use gating_rule::*;
+pub use group_member::*;
+pub use group_notice::*;
+pub use health::*;
+pub use invite_code::*;
+pub use join_request::*;
+pub use member_cap::*;
+pub use ownership_transfer::*;
+pub use platform_config::*;
+pub use role_rank::*;
+pub use saga::*;
+pub use task_state::*;
+pub use token_standard::*;
@@ -0,0 +1,12 @@
+use candid::{CandidType, Deserialize, Principal};
+
+/// A pending hand-over of a group, the ownership changes when the new owner accepts it
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct OwnershipTransfer {
+    pub group_id: u64,
+    pub from: Principal,
+    pub to: Principal,
+    pub created_at: u64,
+}
+
+impl_storable_for!(OwnershipTransfer);
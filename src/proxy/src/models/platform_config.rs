@@ -0,0 +1,149 @@
+use candid::{CandidType, Deserialize, Principal};
+
+const DEFAULT_GROUP_RESTORE_WINDOW: u64 = 14 * 24 * 60 * 60;
+const DEFAULT_JOIN_REQUEST_EXPIRY: u64 = 30 * 24 * 60 * 60;
+const DEFAULT_GATED_GRACE_PERIOD: u64 = 7 * 24 * 60 * 60;
+const DEFAULT_GATED_REVALIDATION_BATCH_SIZE: u64 = 50;
+
+fn default_boost_receiver() -> Principal {
+    // catalyze multisig
+    Principal::from_text("fcygz-gqaaa-aaaap-abpaa-cai").expect("Invalid principal")
+}
+
+/// Platform parameters that can be changed at runtime
+/// # Note
+/// The config is kept in stable memory, fields added after the first version are optional
+/// so a config stored by an earlier version still decodes, `None` falls back to the default.
+/// New fields have to be optional as well.
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct PlatformConfig {
+    /// Incremented on every change
+    pub version: u64,
+    pub e8s_per_day_boost_cost: u64,
+    pub user_group_creation_limit: u64,
+    pub topic_min_length: u64,
+    pub topic_max_length: u64,
+    /// Seconds after which an unanswered friend request is removed
+    pub friend_request_auto_remove_delay: u64,
+    /// The principal whose ledger account receives the boost payments
+    pub boost_receiver: Option<Principal>,
+    /// Seconds in which the owner can restore a deleted group before it is purged
+    pub group_restore_window: Option<u64>,
+    /// Seconds after which an unanswered request to join a private group is removed
+    pub join_request_expiry: Option<u64>,
+    /// Seconds a member of a gated group that no longer meets the gate keeps access
+    pub gated_grace_period: Option<u64>,
+    /// The number of gated group members that are checked per re-validation run
+    pub gated_revalidation_batch_size: Option<u64>,
+    pub updated_at: u64,
+}
+
+impl_storable_for!(PlatformConfig);
+
+impl Default for PlatformConfig {
+    fn default() -> Self {
+        Self {
+            version: 0,
+            e8s_per_day_boost_cost: 3_500_000,
+            user_group_creation_limit: 10,
+            topic_min_length: 1,
+            topic_max_length: 32,
+            friend_request_auto_remove_delay: 30 * 24 * 60 * 60,
+            boost_receiver: Some(default_boost_receiver()),
+            group_restore_window: Some(DEFAULT_GROUP_RESTORE_WINDOW),
+            join_request_expiry: Some(DEFAULT_JOIN_REQUEST_EXPIRY),
+            gated_grace_period: Some(DEFAULT_GATED_GRACE_PERIOD),
+            gated_revalidation_batch_size: Some(DEFAULT_GATED_REVALIDATION_BATCH_SIZE),
+            updated_at: 0,
+        }
+    }
+}
+
+/// Partial update of the `PlatformConfig`, fields that are `None` are left unchanged
+#[derive(Clone, Debug, Default, CandidType, Deserialize)]
+pub struct UpdatePlatformConfig {
+    pub e8s_per_day_boost_cost: Option<u64>,
+    pub user_group_creation_limit: Option<u64>,
+    pub topic_min_length: Option<u64>,
+    pub topic_max_length: Option<u64>,
+    pub friend_request_auto_remove_delay: Option<u64>,
+    pub boost_receiver: Option<Principal>,
+    pub group_restore_window: Option<u64>,
+    pub join_request_expiry: Option<u64>,
+    pub gated_grace_period: Option<u64>,
+    pub gated_revalidation_batch_size: Option<u64>,
+}
+
+impl PlatformConfig {
+    pub fn update(&mut self, update: UpdatePlatformConfig, now: u64) {
+        if let Some(value) = update.e8s_per_day_boost_cost {
+            self.e8s_per_day_boost_cost = value;
+        }
+        if let Some(value) = update.user_group_creation_limit {
+            self.user_group_creation_limit = value;
+        }
+        if let Some(value) = update.topic_min_length {
+            self.topic_min_length = value;
+        }
+        if let Some(value) = update.topic_max_length {
+            self.topic_max_length = value;
+        }
+        if let Some(value) = update.friend_request_auto_remove_delay {
+            self.friend_request_auto_remove_delay = value;
+        }
+        if let Some(value) = update.boost_receiver {
+            self.boost_receiver = Some(value);
+        }
+        if let Some(value) = update.group_restore_window {
+            self.group_restore_window = Some(value);
+        }
+        if let Some(value) = update.join_request_expiry {
+            self.join_request_expiry = Some(value);
+        }
+        if let Some(value) = update.gated_grace_period {
+            self.gated_grace_period = Some(value);
+        }
+        if let Some(value) = update.gated_revalidation_batch_size {
+            self.gated_revalidation_batch_size = Some(value);
+        }
+
+        self.version += 1;
+        self.updated_at = now;
+    }
+
+    /// The config with the defaults filled in for the fields a stored config did not have yet
+    pub fn with_defaults(self) -> Self {
+        Self {
+            boost_receiver: Some(self.boost_receiver()),
+            group_restore_window: Some(self.group_restore_window()),
+            join_request_expiry: Some(self.join_request_expiry()),
+            gated_grace_period: Some(self.gated_grace_period()),
+            gated_revalidation_batch_size: Some(self.gated_revalidation_batch_size()),
+            ..self
+        }
+    }
+
+    pub fn boost_receiver(&self) -> Principal {
+        self.boost_receiver.unwrap_or_else(default_boost_receiver)
+    }
+
+    pub fn group_restore_window(&self) -> u64 {
+        self.group_restore_window
+            .unwrap_or(DEFAULT_GROUP_RESTORE_WINDOW)
+    }
+
+    pub fn join_request_expiry(&self) -> u64 {
+        self.join_request_expiry
+            .unwrap_or(DEFAULT_JOIN_REQUEST_EXPIRY)
+    }
+
+    pub fn gated_grace_period(&self) -> u64 {
+        self.gated_grace_period
+            .unwrap_or(DEFAULT_GATED_GRACE_PERIOD)
+    }
+
+    pub fn gated_revalidation_batch_size(&self) -> u64 {
+        self.gated_revalidation_batch_size
+            .unwrap_or(DEFAULT_GATED_REVALIDATION_BATCH_SIZE)
+    }
+}
@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use candid::{CandidType, Deserialize, Principal};
+use catalyze_shared::group_with_members::GroupWithMembers;
+
+/// The rank of a role in a group, members can only manage members and roles ranked below them.
+/// The default roles have fixed ranks, custom roles are ranked when they are added.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, CandidType, Deserialize)]
+pub struct RoleRank(pub u64);
+
+impl RoleRank {
+    pub const MEMBER: RoleRank = RoleRank(0);
+    /// The rank of custom roles that were added without one
+    pub const CUSTOM: RoleRank = RoleRank(100);
+    pub const MODERATOR: RoleRank = RoleRank(200);
+    pub const ADMIN: RoleRank = RoleRank(300);
+    pub const OWNER: RoleRank = RoleRank(400);
+
+    /// The fixed rank of a default role, `None` for custom roles
+    pub fn of_default_role(role: &str) -> Option<Self> {
+        match role {
+            "owner" => Some(RoleRank::OWNER),
+            "admin" => Some(RoleRank::ADMIN),
+            "moderator" => Some(RoleRank::MODERATOR),
+            "member" => Some(RoleRank::MEMBER),
+            _ => None,
+        }
+    }
+}
+
+/// The ranks of the custom roles of a group by role name
+#[derive(Clone, Debug, Default, CandidType, Deserialize)]
+pub struct GroupRoleRanks {
+    pub ranks: HashMap<String, RoleRank>,
+    pub updated_at: u64,
+}
+
+impl_storable_for!(GroupRoleRanks);
+
+impl GroupRoleRanks {
+    /// Custom roles without a stored rank, like the ones added before roles were ranked, rank as `CUSTOM`
+    pub fn rank_of(&self, role: &str) -> RoleRank {
+        RoleRank::of_default_role(role)
+            .or_else(|| self.ranks.get(role).copied())
+            .unwrap_or(RoleRank::CUSTOM)
+    }
+
+    /// The highest rank of the roles of the principal, non-members rank as `member`
+    pub fn rank_of_member(&self, group: &GroupWithMembers, principal: Principal) -> RoleRank {
+        if group.owner == principal {
+            return RoleRank::OWNER;
+        }
+
+        group
+            .members
+            .members
+            .get(&principal)
+            .and_then(|member| member.roles.iter().map(|role| self.rank_of(role)).max())
+            .unwrap_or(RoleRank::MEMBER)
+    }
+}
@@ -0,0 +1,148 @@
+use crate::helpers::env::time;
+use candid::{CandidType, Deserialize, Principal};
+
+/// The multi-store operations that run as a saga
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub enum SagaKind {
+    AddEvent,
+    DeleteGroup,
+    AcceptFriendRequest,
+}
+
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub enum SagaStatus {
+    /// Steps are being executed
+    Running,
+    /// A step failed and the completed steps can't be compensated, the saga can be retried
+    Failed,
+    /// Completed steps are being compensated
+    Compensating,
+    /// A compensating action failed, the saga can be rolled back again
+    CompensationFailed,
+}
+
+/// A single write to a storage canister
+/// # Note
+/// Steps that only remove data have no compensating action,
+/// a saga containing those can only be retried, not rolled back.
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub enum SagaStep {
+    /// An event that is inserted before the saga started, compensated by removing it
+    InsertEvent(u64),
+    AddEventToGroup {
+        group_id: u64,
+        event_id: u64,
+    },
+    AddEventToProfile {
+        principal: Principal,
+        event_id: u64,
+    },
+    AddFriend {
+        principal: Principal,
+        friend: Principal,
+    },
+    RemoveFriendRequest(u64),
+    RemoveBoost(u64),
+    RemoveGroupFromProfiles {
+        group_id: u64,
+        principals: Vec<Principal>,
+    },
+    RemoveEvents(Vec<u64>),
+    RemoveGroup(u64),
+    RemoveBoosts(Vec<u64>),
+    /// Removes the events and the starred and pinned entries of the events from the profiles
+    RemoveEventsFromProfiles {
+        event_ids: Vec<u64>,
+        principals: Vec<Principal>,
+    },
+    /// Removes the notifications and their references from the profile they were sent to
+    RemoveNotifications(Vec<(Principal, u64)>),
+    RemoveReports(Vec<u64>),
+}
+
+impl SagaStep {
+    pub fn is_compensable(&self) -> bool {
+        matches!(
+            self,
+            SagaStep::InsertEvent(_)
+                | SagaStep::AddEventToGroup { .. }
+                | SagaStep::AddEventToProfile { .. }
+                | SagaStep::AddFriend { .. }
+        )
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct Saga {
+    pub kind: SagaKind,
+    pub status: SagaStatus,
+    pub steps: Vec<SagaStep>,
+    /// The number of steps, counted from the start, that are executed
+    pub completed_steps: u64,
+    pub error: Option<String>,
+    pub created_by: Principal,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+impl_storable_for!(Saga);
+
+impl Saga {
+    pub fn new(
+        kind: SagaKind,
+        created_by: Principal,
+        completed: Vec<SagaStep>,
+        pending: Vec<SagaStep>,
+    ) -> Self {
+        Self {
+            kind,
+            status: SagaStatus::Running,
+            completed_steps: completed.len() as u64,
+            steps: completed.into_iter().chain(pending).collect(),
+            error: None,
+            created_by,
+            created_at: time(),
+            updated_at: time(),
+        }
+    }
+
+    /// The next step to execute
+    pub fn next_step(&self) -> Option<SagaStep> {
+        self.steps.get(self.completed_steps as usize).cloned()
+    }
+
+    /// The last executed step, which is the next one to compensate
+    pub fn last_completed_step(&self) -> Option<SagaStep> {
+        match self.completed_steps {
+            0 => None,
+            n => self.steps.get(n as usize - 1).cloned(),
+        }
+    }
+
+    pub fn can_compensate(&self) -> bool {
+        self.steps
+            .iter()
+            .take(self.completed_steps as usize)
+            .all(SagaStep::is_compensable)
+    }
+
+    pub fn set_status(&mut self, status: SagaStatus) {
+        self.status = status;
+        self.updated_at = time();
+    }
+
+    pub fn set_error(&mut self, status: SagaStatus, error: String) {
+        self.error = Some(error);
+        self.set_status(status);
+    }
+
+    /// Whether the saga is finished with an error or has not been updated for `timeout` nanoseconds
+    pub fn is_stuck(&self, timeout: u64) -> bool {
+        match self.status {
+            SagaStatus::Failed | SagaStatus::CompensationFailed => true,
+            SagaStatus::Running | SagaStatus::Compensating => {
+                time().saturating_sub(self.updated_at) > timeout
+            }
+        }
+    }
+}
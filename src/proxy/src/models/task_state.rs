@@ -0,0 +1,19 @@
+use candid::{CandidType, Deserialize, Principal};
+
+/// The progress of the background tasks that has to survive an upgrade
+/// # Note
+/// The state is kept in stable memory, every field is optional so a state stored by an
+/// earlier version still decodes.
+#[derive(Clone, Debug, Default, CandidType, Deserialize)]
+pub struct TaskState {
+    /// Set once the pending requests made before join requests expired are stored as join requests
+    pub join_requests_backfilled_at: Option<u64>,
+    /// Set once every gated group made before gating rules were stored has a stored rule,
+    /// the stored rules are the index of gated groups the re-validation pages through
+    pub gating_rules_backfilled_at: Option<u64>,
+    /// The last gated group member checked by the previous re-validation run,
+    /// `None` starts the next run at the first gated group
+    pub gated_revalidation_cursor: Option<(u64, Principal)>,
+}
+
+impl_storable_for!(TaskState);
@@ -0,0 +1,65 @@
+use std::{fmt, str::FromStr};
+
+use candid::{CandidType, Deserialize};
+use catalyze_shared::api_error::ApiError;
+
+/// The token standards a group can be gated by,
+/// `TokenGated::standard` holds the name of one of them
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CandidType, Deserialize)]
+pub enum TokenStandard {
+    Ext,
+    Dip20,
+    Dip721,
+    Dip721Legacy,
+    Icrc1,
+    Icrc7,
+}
+
+impl TokenStandard {
+    pub fn as_str(&self) -> &'static str {
+        use TokenStandard::*;
+        match self {
+            Ext => "EXT",
+            Dip20 => "DIP20",
+            Dip721 => "DIP721",
+            Dip721Legacy => "DIP721_LEGACY",
+            Icrc1 => "ICRC",
+            Icrc7 => "ICRC7",
+        }
+    }
+}
+
+impl FromStr for TokenStandard {
+    type Err = ApiError;
+
+    /// "ICRC1" is accepted next to "ICRC", which existing groups use for ICRC-1 tokens
+    fn from_str(standard: &str) -> Result<Self, Self::Err> {
+        use TokenStandard::*;
+        match standard {
+            "EXT" => Ok(Ext),
+            "DIP20" => Ok(Dip20),
+            "DIP721" => Ok(Dip721),
+            "DIP721_LEGACY" => Ok(Dip721Legacy),
+            "ICRC" | "ICRC1" => Ok(Icrc1),
+            "ICRC7" => Ok(Icrc7),
+            _ => Err(ApiError::bad_request()
+                .add_message(format!("Unknown token standard \"{}\"", standard))),
+        }
+    }
+}
+
+impl fmt::Display for TokenStandard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The subaccounts a principal holds ICRC tokens on,
+/// they are checked next to the default subaccount of the principal
+#[derive(Clone, Debug, Default, CandidType, Deserialize)]
+pub struct TokenSubaccounts {
+    pub subaccounts: Vec<Vec<u8>>,
+    pub updated_at: u64,
+}
+
+impl_storable_for!(TokenSubaccounts);
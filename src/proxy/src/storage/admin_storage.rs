@@ -0,0 +1,16 @@
+use candid::Principal;
+
+use crate::models::{AdminAuditEntry, AdminRoles};
+
+use super::{
+    stable_storage::StableStorage,
+    storage_api::{ADMINS, ADMIN_AUDIT_LOG},
+};
+
+pub fn admins() -> StableStorage<Principal, AdminRoles> {
+    StableStorage::new("admin", &ADMINS)
+}
+
+pub fn admin_audit_log() -> StableStorage<u64, AdminAuditEntry> {
+    StableStorage::new("admin_audit_log", &ADMIN_AUDIT_LOG)
+}
@@ -0,0 +1,8 @@
+use crate::models::ArchivedGroup;
+
+use super::{stable_storage::StableStorage, storage_api::ARCHIVED_GROUPS};
+
+/// Deleted groups that are waiting for their final purge, by group id
+pub fn archived_groups() -> StableStorage<u64, ArchivedGroup> {
+    StableStorage::new("archived_group", &ARCHIVED_GROUPS)
+}
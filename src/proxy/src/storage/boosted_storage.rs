@@ -20,6 +20,12 @@ impl StorageClient<u64, Boost, BoostedFilter, BoostedSort> for BoostedStorageCli
 
 impl StorageClientInsertable<Boost, BoostedFilter, BoostedSort> for BoostedStorageClient {}
 
+#[cfg(not(test))]
 pub fn boosts() -> impl StorageClientInsertable<Boost, BoostedFilter, BoostedSort> {
     BoostedStorageClient
 }
+
+#[cfg(test)]
+pub fn boosts() -> impl StorageClientInsertable<Boost, BoostedFilter, BoostedSort> {
+    super::memory_storage::MemoryBoostedStorageClient
+}
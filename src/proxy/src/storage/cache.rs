@@ -0,0 +1,152 @@
+use std::{cell::RefCell, collections::HashMap, future::Future, hash::Hash, thread::LocalKey};
+
+use crate::helpers::env::time;
+use candid::Principal;
+use catalyze_shared::{
+    group_with_members::GroupWithMembers, profile_with_refs::ProfileWithRefs, CanisterResult,
+};
+
+/// How long a cached entry is served before it is fetched again, in nanoseconds
+const CACHE_TTL: u64 = 30 * 1_000_000_000;
+const PROFILE_CACHE_CAPACITY: usize = 1_000;
+const GROUP_CACHE_CAPACITY: usize = 250;
+
+thread_local! {
+    pub static PROFILE_CACHE: RefCell<HeapCache<Principal, ProfileWithRefs>> =
+        RefCell::new(HeapCache::new(PROFILE_CACHE_CAPACITY, CACHE_TTL));
+    pub static GROUP_CACHE: RefCell<HeapCache<u64, GroupWithMembers>> =
+        RefCell::new(HeapCache::new(GROUP_CACHE_CAPACITY, CACHE_TTL));
+}
+
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub size: usize,
+}
+
+/// A bounded cache on the heap where every entry expires after the ttl
+/// # Note
+/// The cache is not persisted over upgrades and changes made during a query call are discarded,
+/// so it is only filled and counted by update calls.
+pub struct HeapCache<K, V> {
+    entries: HashMap<K, (u64, V)>,
+    capacity: usize,
+    ttl: u64,
+    // incremented on every invalidation, used to drop fetches that raced with a write
+    version: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> HeapCache<K, V> {
+    pub fn new(capacity: usize, ttl: u64) -> Self {
+        Self {
+            entries: HashMap::new(),
+            capacity,
+            ttl,
+            version: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    pub fn get(&mut self, key: &K, now: u64) -> Option<V> {
+        match self.entries.get(key) {
+            Some((expires_at, value)) if *expires_at > now => {
+                self.hits += 1;
+                Some(value.clone())
+            }
+            _ => {
+                self.entries.remove(key);
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Caches the value when nothing was invalidated since `version` was read
+    pub fn insert(&mut self, key: K, value: V, version: u64, now: u64) {
+        if version != self.version {
+            return;
+        }
+
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(&key) {
+            self.entries.retain(|_, (expires_at, _)| *expires_at > now);
+        }
+
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(&key) {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (expires_at, _))| *expires_at)
+                .map(|(key, _)| key.clone());
+
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+
+        self.entries.insert(key, (now + self.ttl, value));
+    }
+
+    pub fn invalidate(&mut self, key: &K) {
+        self.version += 1;
+        self.entries.remove(key);
+    }
+
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            size: self.entries.len(),
+        }
+    }
+}
+
+/// Serves the entry from the cache or fetches and caches it
+pub async fn read_through<K, V, F, Fut>(
+    cache: &'static LocalKey<RefCell<HeapCache<K, V>>>,
+    key: K,
+    fetch: F,
+) -> CanisterResult<(K, V)>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    F: FnOnce(K) -> Fut,
+    Fut: Future<Output = CanisterResult<(K, V)>>,
+{
+    if let Some(value) = cache.with(|cache| cache.borrow_mut().get(&key, time())) {
+        return Ok((key, value));
+    }
+
+    let version = cache.with(|cache| cache.borrow().version());
+    let (key, value) = fetch(key).await?;
+
+    cache.with(|cache| {
+        cache
+            .borrow_mut()
+            .insert(key.clone(), value.clone(), version, time())
+    });
+
+    Ok((key, value))
+}
+
+pub fn invalidate<K, V>(cache: &'static LocalKey<RefCell<HeapCache<K, V>>>, key: &K)
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    cache.with(|cache| cache.borrow_mut().invalidate(key));
+}
+
+pub fn profile_cache_stats() -> CacheStats {
+    PROFILE_CACHE.with(|cache| cache.borrow().stats())
+}
+
+pub fn group_cache_stats() -> CacheStats {
+    GROUP_CACHE.with(|cache| cache.borrow().stats())
+}
@@ -1,9 +1,12 @@
 use candid::Principal;
-use catalyze_shared::{CellStorage, GenericCellStorage};
+use catalyze_shared::{api_error::ApiError, CanisterResult, CellStorage, GenericCellStorage};
+
+use crate::models::{CanisterConfig, PlatformConfig, TaskState};
 
 use super::{
     storage_api::{
-        GLOBAL_CANISTER, HISTORY_CANISTER, PROFILE_CANISTER, REPORT_CANISTER, TOPIC_CANISTER,
+        GLOBAL_CANISTER, HISTORY_CANISTER, PLATFORM_CONFIG, PROFILE_CANISTER, REPORT_CANISTER,
+        TASK_STATE, TOPIC_CANISTER,
     },
     BOOSTED_CANISTER, EVENT_CANISTER, FRIEND_REQUEST_CANISTER, GROUP_CANISTER,
     NOTIFICATION_CANISTER, TRANSACTION_HANDLER_CANISTER,
@@ -55,3 +58,67 @@ pub fn transaction_handler_canister() -> impl CellStorage<Principal> {
         &TRANSACTION_HANDLER_CANISTER,
     )
 }
+
+pub fn platform_config() -> impl CellStorage<PlatformConfig> {
+    GenericCellStorage::new("platform_config", &PLATFORM_CONFIG)
+}
+
+pub fn task_state() -> impl CellStorage<TaskState> {
+    GenericCellStorage::new("task_state", &TASK_STATE)
+}
+
+/// Reads all storage canister cells at once
+/// # Returns
+/// * `CanisterConfig` - The current topology, cells that are not set are `None`
+pub fn get_canister_config() -> CanisterConfig {
+    CanisterConfig {
+        history: history_canister().get().ok(),
+        profile: profile_canister().get().ok(),
+        group: group_canister().get().ok(),
+        event: event_canister().get().ok(),
+        boost: boost_canister().get().ok(),
+        topic: topic_canister().get().ok(),
+        friend_request: friend_request_canister().get().ok(),
+        global: global_canister().get().ok(),
+        notification: notification_canister().get().ok(),
+        report: report_canister().get().ok(),
+        transaction_handler: transaction_handler_canister().get().ok(),
+    }
+}
+
+/// Writes all storage canister cells at once
+/// # Arguments
+/// * `config` - The topology to store, every entry is required
+/// # Returns
+/// * `CanisterConfig` - The stored topology
+/// # Errors
+/// * `ApiError` - If one or more entries are missing, in that case no cell is written
+pub fn set_canister_config(config: CanisterConfig) -> CanisterResult<CanisterConfig> {
+    let missing = config.missing();
+    if !missing.is_empty() {
+        return Err(ApiError::bad_request()
+            .add_method_name("set_canister_config")
+            .add_message(format!("Missing canister(s): {}", missing.join(", "))));
+    }
+
+    set_cell(history_canister(), config.history)?;
+    set_cell(profile_canister(), config.profile)?;
+    set_cell(group_canister(), config.group)?;
+    set_cell(event_canister(), config.event)?;
+    set_cell(boost_canister(), config.boost)?;
+    set_cell(topic_canister(), config.topic)?;
+    set_cell(friend_request_canister(), config.friend_request)?;
+    set_cell(global_canister(), config.global)?;
+    set_cell(notification_canister(), config.notification)?;
+    set_cell(report_canister(), config.report)?;
+    set_cell(transaction_handler_canister(), config.transaction_handler)?;
+
+    Ok(get_canister_config())
+}
+
+fn set_cell(cell: impl CellStorage<Principal>, principal: Option<Principal>) -> CanisterResult<()> {
+    if let Some(principal) = principal {
+        cell.set(principal)?;
+    }
+    Ok(())
+}
@@ -20,6 +20,12 @@ impl StorageClient<u64, EventWithAttendees, EventFilter, EventSort> for EventSto
 
 impl StorageClientInsertable<EventWithAttendees, EventFilter, EventSort> for EventStorageClient {}
 
+#[cfg(not(test))]
 pub fn events() -> EventStorageClient {
     EventStorageClient
 }
+
+#[cfg(test)]
+pub fn events() -> super::memory_storage::MemoryEventStorageClient {
+    super::memory_storage::MemoryEventStorageClient
+}
@@ -25,7 +25,14 @@ impl StorageClientInsertable<FriendRequest, FriendRequestFilter, FriendRequestSo
 {
 }
 
+#[cfg(not(test))]
 pub fn friend_requests(
 ) -> impl StorageClientInsertable<FriendRequest, FriendRequestFilter, FriendRequestSort> {
     FriendRequestStorageClient
 }
+
+#[cfg(test)]
+pub fn friend_requests(
+) -> impl StorageClientInsertable<FriendRequest, FriendRequestFilter, FriendRequestSort> {
+    super::memory_storage::MemoryFriendRequestStorageClient
+}
@@ -0,0 +1,16 @@
+use crate::models::{GatedGracePeriod, GatedRemovalReport};
+
+use super::{
+    stable_storage::StableStorage,
+    storage_api::{GATED_GRACE_PERIODS, GATED_REMOVAL_REPORTS},
+};
+
+/// Grace periods of gated group members that no longer meet the gate, by group id and principal
+pub fn gated_grace_periods() -> StableStorage<String, GatedGracePeriod> {
+    StableStorage::new("gated_grace_period", &GATED_GRACE_PERIODS)
+}
+
+/// Members removed from gated groups by the re-validation timer
+pub fn gated_removal_reports() -> StableStorage<u64, GatedRemovalReport> {
+    StableStorage::new("gated_removal_report", &GATED_REMOVAL_REPORTS)
+}
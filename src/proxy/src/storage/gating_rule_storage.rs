@@ -0,0 +1,8 @@
+use crate::models::GroupGatingRule;
+
+use super::{stable_storage::StableStorage, storage_api::GATING_RULES};
+
+/// Gating rules of gated groups by group id, groups without one use their `GatedType`
+pub fn gating_rules() -> StableStorage<u64, GroupGatingRule> {
+    StableStorage::new("gating_rule", &GATING_RULES)
+}
@@ -1,9 +1,8 @@
 use candid::Principal;
-use catalyze_shared::{
-    ic_call::ic_call, reward::RewardableActivityResponse, CanisterResult, CellStorage,
-};
+use catalyze_shared::{reward::RewardableActivityResponse, CanisterResult};
 
 use super::global_canister;
+use catalyze_shared::{ic_call::ic_call, CellStorage};
 
 #[derive(Clone)]
 pub struct GlobalStorageClient;
@@ -57,6 +56,12 @@ impl GlobalStorageClient {
     }
 }
 
+#[cfg(not(test))]
 pub fn global() -> GlobalStorageClient {
     GlobalStorageClient
 }
+
+#[cfg(test)]
+pub fn global() -> super::memory_storage::MemoryGlobalStorageClient {
+    super::memory_storage::MemoryGlobalStorageClient
+}
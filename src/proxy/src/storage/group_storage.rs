@@ -1,9 +1,118 @@
-use super::storage_api::GROUP_CANISTER;
+use super::{
+    cache::{invalidate, read_through, GROUP_CACHE},
+    storage_api::GROUP_CANISTER,
+};
+use candid::Principal;
 use catalyze_shared::{
     group_with_members::{GroupFilter, GroupSort, GroupWithMembers},
-    storage_clients, StorageClientInsertable,
+    CanisterResult, StorageClient, StorageClientInsertable,
 };
 
-pub fn groups() -> impl StorageClientInsertable<GroupWithMembers, GroupFilter, GroupSort> {
-    storage_clients::groups(&GROUP_CANISTER)
+#[derive(Default)]
+pub struct GroupStorageClient;
+
+impl StorageClient<u64, GroupWithMembers, GroupFilter, GroupSort> for GroupStorageClient {
+    fn name(&self) -> String {
+        "group".to_string()
+    }
+
+    fn storage_canister_id(&self) -> catalyze_shared::StaticCellStorageRef<Principal> {
+        &GROUP_CANISTER
+    }
+}
+
+impl StorageClientInsertable<GroupWithMembers, GroupFilter, GroupSort> for GroupStorageClient {}
+
+/// Cached reads and invalidating writes, these take precedence over the `StorageClient` methods
+impl GroupStorageClient {
+    pub async fn get(&self, key: u64) -> CanisterResult<(u64, GroupWithMembers)> {
+        read_through(&GROUP_CACHE, key, |key| {
+            StorageClient::<u64, GroupWithMembers, GroupFilter, GroupSort>::get(self, key)
+        })
+        .await
+    }
+
+    pub async fn update(
+        &self,
+        key: u64,
+        value: GroupWithMembers,
+    ) -> CanisterResult<(u64, GroupWithMembers)> {
+        // invalidated before and after the write so reads overlapping with it are not cached
+        invalidate(&GROUP_CACHE, &key);
+        let result = StorageClient::<u64, GroupWithMembers, GroupFilter, GroupSort>::update(
+            self, key, value,
+        )
+        .await;
+        invalidate(&GROUP_CACHE, &key);
+        result
+    }
+
+    pub async fn insert(&self, value: GroupWithMembers) -> CanisterResult<(u64, GroupWithMembers)> {
+        let result = StorageClientInsertable::<GroupWithMembers, GroupFilter, GroupSort>::insert(
+            self, value,
+        )
+        .await;
+        if let Ok((key, _)) = &result {
+            invalidate(&GROUP_CACHE, key);
+        }
+        result
+    }
+
+    pub async fn insert_many(
+        &self,
+        values: Vec<GroupWithMembers>,
+    ) -> CanisterResult<Vec<(u64, GroupWithMembers)>> {
+        let result =
+            StorageClientInsertable::<GroupWithMembers, GroupFilter, GroupSort>::insert_many(
+                self, values,
+            )
+            .await;
+        if let Ok(list) = &result {
+            list.iter()
+                .for_each(|(key, _)| invalidate(&GROUP_CACHE, key));
+        }
+        result
+    }
+
+    pub async fn update_many(
+        &self,
+        list: Vec<(u64, GroupWithMembers)>,
+    ) -> CanisterResult<Vec<(u64, GroupWithMembers)>> {
+        let keys = list.iter().map(|(key, _)| *key).collect::<Vec<_>>();
+        keys.iter().for_each(|key| invalidate(&GROUP_CACHE, key));
+        let result =
+            StorageClient::<u64, GroupWithMembers, GroupFilter, GroupSort>::update_many(self, list)
+                .await;
+        keys.iter().for_each(|key| invalidate(&GROUP_CACHE, key));
+        result
+    }
+
+    pub async fn remove_many(&self, keys: Vec<u64>) -> CanisterResult<()> {
+        keys.iter().for_each(|key| invalidate(&GROUP_CACHE, key));
+        let result = StorageClient::<u64, GroupWithMembers, GroupFilter, GroupSort>::remove_many(
+            self,
+            keys.clone(),
+        )
+        .await;
+        keys.iter().for_each(|key| invalidate(&GROUP_CACHE, key));
+        result
+    }
+
+    pub async fn remove(&self, key: u64) -> CanisterResult<bool> {
+        invalidate(&GROUP_CACHE, &key);
+        let result =
+            StorageClient::<u64, GroupWithMembers, GroupFilter, GroupSort>::remove(self, key).await;
+        invalidate(&GROUP_CACHE, &key);
+        result
+    }
+}
+
+#[cfg(not(test))]
+pub fn groups() -> GroupStorageClient {
+    GroupStorageClient
+}
+
+#[cfg(test)]
+pub fn groups() -> super::memory_storage::MemoryGroupStorageClient {
+    super::memory_storage::MemoryGroupStorageClient
 }
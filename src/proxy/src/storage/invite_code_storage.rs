@@ -0,0 +1,8 @@
+use crate::models::InviteCode;
+
+use super::{stable_storage::StableStorage, storage_api::INVITE_CODES};
+
+/// Group invite codes by code
+pub fn invite_codes() -> StableStorage<String, InviteCode> {
+    StableStorage::new("invite_code", &INVITE_CODES)
+}
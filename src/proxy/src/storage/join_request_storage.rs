@@ -0,0 +1,8 @@
+use crate::models::JoinRequest;
+
+use super::{stable_storage::StableStorage, storage_api::JOIN_REQUESTS};
+
+/// Pending join requests of private groups by notification id
+pub fn join_requests() -> StableStorage<u64, JoinRequest> {
+    StableStorage::new("join_request", &JOIN_REQUESTS)
+}
@@ -0,0 +1,8 @@
+use crate::models::GroupMemberCap;
+
+use super::{stable_storage::StableStorage, storage_api::GROUP_MEMBER_CAPS};
+
+/// Member caps and waitlists of groups by group id
+pub fn member_caps() -> StableStorage<u64, GroupMemberCap> {
+    StableStorage::new("member_cap", &GROUP_MEMBER_CAPS)
+}
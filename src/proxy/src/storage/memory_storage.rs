@@ -0,0 +1,443 @@
+//! In-memory replacements for the storage canister clients, returned by the
+//! storage accessors under `cfg(test)` so the logic layer can run natively.
+//! Each store is a thread local, `cargo test` runs every test on its own thread
+//! so every test starts with empty stores.
+use std::{cell::RefCell, collections::BTreeMap};
+
+use super::storage_api::{
+    BOOSTED_CANISTER, EVENT_CANISTER, FRIEND_REQUEST_CANISTER, GROUP_CANISTER,
+    NOTIFICATION_CANISTER, PROFILE_CANISTER, REPORT_CANISTER, TOPIC_CANISTER,
+};
+use candid::Principal;
+use catalyze_shared::{
+    api_error::ApiError,
+    boosted::{Boost, BoostedFilter, BoostedSort},
+    event_with_attendees::{EventFilter, EventSort, EventWithAttendees},
+    friend_request::{FriendRequest, FriendRequestFilter, FriendRequestSort},
+    group_with_members::{GroupFilter, GroupSort, GroupWithMembers},
+    notification::{Notification, NotificationFilter, NotificationSort},
+    paged_response::PagedResponse,
+    profile_with_refs::{ProfileEntry, ProfileFilter, ProfileSort, ProfileWithRefs},
+    report::{Report, ReportFilter, ReportSort},
+    reward::RewardableActivityResponse,
+    topic::{Topic, TopicFilter, TopicSort},
+    CanisterResult, StaticCellStorageRef, StorageClient, StorageClientInsertable,
+    StorageClientInsertableByKey,
+};
+
+/// Generates an in-memory `StorageClient` backed by a thread local `BTreeMap`
+/// # Arguments
+/// * `$client` - The name of the generated client
+/// * `$store` - The name of the generated thread local
+/// * `$canister` - The canister cell of the client it replaces
+/// * `$matches` - Decides if an entry matches a single filter, filters that are
+/// not relevant for the tests can match everything
+/// # Note
+/// Sorting is not applied, entries are returned in key order.
+macro_rules! memory_storage_client {
+    ($client:ident, $store:ident, $canister:ident, $key:ty, $value:ty, $filter:ty, $sort:ty, $matches:expr) => {
+        thread_local! {
+            pub static $store: RefCell<BTreeMap<$key, $value>> = RefCell::new(BTreeMap::new());
+        }
+
+        #[derive(Default)]
+        pub struct $client;
+
+        impl $client {
+            fn matches(filters: &[$filter], key: &$key, value: &$value) -> bool {
+                let matches: fn(&$filter, &$key, &$value) -> bool = $matches;
+                filters.iter().all(|filter| matches(filter, key, value))
+            }
+
+            fn not_found(&self) -> ApiError {
+                ApiError::not_found().add_message(format!("{} not found", self.name()).as_str())
+            }
+        }
+
+        impl StorageClient<$key, $value, $filter, $sort> for $client {
+            fn name(&self) -> String {
+                stringify!($store).to_lowercase()
+            }
+
+            fn storage_canister_id(&self) -> StaticCellStorageRef<Principal> {
+                &$canister
+            }
+
+            async fn get(&self, key: $key) -> CanisterResult<($key, $value)> {
+                $store
+                    .with(|store| store.borrow().get(&key).cloned())
+                    .map(|value| (key, value))
+                    .ok_or_else(|| self.not_found())
+            }
+
+            async fn get_many(&self, keys: Vec<$key>) -> CanisterResult<Vec<($key, $value)>> {
+                Ok($store.with(|store| {
+                    let store = store.borrow();
+                    keys.into_iter()
+                        .filter_map(|key| store.get(&key).cloned().map(|value| (key, value)))
+                        .collect()
+                }))
+            }
+
+            async fn get_all(&self) -> CanisterResult<Vec<($key, $value)>> {
+                Ok($store.with(|store| {
+                    store
+                        .borrow()
+                        .iter()
+                        .map(|(key, value)| (*key, value.clone()))
+                        .collect()
+                }))
+            }
+
+            async fn find(&self, filters: Vec<$filter>) -> CanisterResult<Option<($key, $value)>> {
+                Ok($store.with(|store| {
+                    store
+                        .borrow()
+                        .iter()
+                        .find(|(key, value)| Self::matches(&filters, key, value))
+                        .map(|(key, value)| (*key, value.clone()))
+                }))
+            }
+
+            async fn filter(&self, filters: Vec<$filter>) -> CanisterResult<Vec<($key, $value)>> {
+                Ok($store.with(|store| {
+                    store
+                        .borrow()
+                        .iter()
+                        .filter(|(key, value)| Self::matches(&filters, key, value))
+                        .map(|(key, value)| (*key, value.clone()))
+                        .collect()
+                }))
+            }
+
+            async fn filter_paginated(
+                &self,
+                limit: usize,
+                page: usize,
+                _sort: $sort,
+                filters: Vec<$filter>,
+            ) -> CanisterResult<PagedResponse<($key, $value)>> {
+                let entries = self.filter(filters).await?;
+                Ok(PagedResponse::new(page, limit, entries))
+            }
+
+            async fn update(&self, key: $key, value: $value) -> CanisterResult<($key, $value)> {
+                $store.with(|store| {
+                    let mut store = store.borrow_mut();
+                    if !store.contains_key(&key) {
+                        return Err(self.not_found());
+                    }
+                    store.insert(key, value.clone());
+                    Ok((key, value))
+                })
+            }
+
+            async fn update_many(
+                &self,
+                list: Vec<($key, $value)>,
+            ) -> CanisterResult<Vec<($key, $value)>> {
+                let mut result = vec![];
+                for (key, value) in list {
+                    result.push(self.update(key, value).await?);
+                }
+                Ok(result)
+            }
+
+            async fn remove(&self, key: $key) -> CanisterResult<bool> {
+                Ok($store.with(|store| store.borrow_mut().remove(&key).is_some()))
+            }
+
+            async fn remove_many(&self, keys: Vec<$key>) -> CanisterResult<()> {
+                $store.with(|store| {
+                    let mut store = store.borrow_mut();
+                    keys.iter().for_each(|key| {
+                        store.remove(key);
+                    });
+                });
+                Ok(())
+            }
+
+            async fn size(&self) -> CanisterResult<u64> {
+                Ok($store.with(|store| store.borrow().len() as u64))
+            }
+        }
+    };
+}
+
+/// Implements `StorageClientInsertable` for a client generated with `memory_storage_client!`,
+/// the ids are incremented from the highest id in the store
+macro_rules! memory_storage_insertable {
+    ($client:ident, $store:ident, $value:ty, $filter:ty, $sort:ty) => {
+        impl StorageClientInsertable<$value, $filter, $sort> for $client {
+            async fn insert(&self, value: $value) -> CanisterResult<(u64, $value)> {
+                $store.with(|store| {
+                    let mut store = store.borrow_mut();
+                    let id = store.keys().last().map(|id| id + 1).unwrap_or_default();
+                    store.insert(id, value.clone());
+                    Ok((id, value))
+                })
+            }
+
+            async fn insert_many(&self, values: Vec<$value>) -> CanisterResult<Vec<(u64, $value)>> {
+                let mut result = vec![];
+                for value in values {
+                    result.push(self.insert(value).await?);
+                }
+                Ok(result)
+            }
+        }
+    };
+}
+
+memory_storage_client!(
+    MemoryProfileStorageClient,
+    PROFILES,
+    PROFILE_CANISTER,
+    Principal,
+    ProfileWithRefs,
+    ProfileFilter,
+    ProfileSort,
+    |filter, _, profile| match filter {
+        ProfileFilter::Username(username) => &profile.metadata.username == username,
+        _ => true,
+    }
+);
+
+impl StorageClientInsertableByKey<Principal, ProfileWithRefs, ProfileFilter, ProfileSort>
+    for MemoryProfileStorageClient
+{
+    async fn insert(&self, key: Principal, value: ProfileWithRefs) -> CanisterResult<ProfileEntry> {
+        PROFILES.with(|store| {
+            let mut store = store.borrow_mut();
+            if store.contains_key(&key) {
+                return Err(ApiError::duplicate().add_message("profile already exists"));
+            }
+            store.insert(key, value.clone());
+            Ok((key, value))
+        })
+    }
+}
+
+/// Mirrors the cached methods of `ProfileStorageClient`
+impl MemoryProfileStorageClient {
+    pub async fn get(&self, key: Principal) -> CanisterResult<ProfileEntry> {
+        StorageClient::<Principal, ProfileWithRefs, ProfileFilter, ProfileSort>::get(self, key)
+            .await
+    }
+
+    pub async fn update(
+        &self,
+        key: Principal,
+        value: ProfileWithRefs,
+    ) -> CanisterResult<ProfileEntry> {
+        StorageClient::<Principal, ProfileWithRefs, ProfileFilter, ProfileSort>::update(
+            self, key, value,
+        )
+        .await
+    }
+
+    pub async fn update_many(&self, list: Vec<ProfileEntry>) -> CanisterResult<Vec<ProfileEntry>> {
+        StorageClient::<Principal, ProfileWithRefs, ProfileFilter, ProfileSort>::update_many(
+            self, list,
+        )
+        .await
+    }
+}
+
+memory_storage_client!(
+    MemoryGroupStorageClient,
+    GROUPS,
+    GROUP_CANISTER,
+    u64,
+    GroupWithMembers,
+    GroupFilter,
+    GroupSort,
+    |filter, id, group| match filter {
+        GroupFilter::Name(name) => group.name.to_lowercase().contains(&name.to_lowercase()),
+        GroupFilter::Owner(owner) => &group.owner == owner,
+        GroupFilter::Ids(ids) => ids.contains(id),
+        _ => true,
+    }
+);
+memory_storage_insertable!(
+    MemoryGroupStorageClient,
+    GROUPS,
+    GroupWithMembers,
+    GroupFilter,
+    GroupSort
+);
+
+/// Mirrors the cached methods of `GroupStorageClient`
+impl MemoryGroupStorageClient {
+    pub async fn get(&self, key: u64) -> CanisterResult<(u64, GroupWithMembers)> {
+        StorageClient::<u64, GroupWithMembers, GroupFilter, GroupSort>::get(self, key).await
+    }
+
+    pub async fn update(
+        &self,
+        key: u64,
+        value: GroupWithMembers,
+    ) -> CanisterResult<(u64, GroupWithMembers)> {
+        StorageClient::<u64, GroupWithMembers, GroupFilter, GroupSort>::update(self, key, value)
+            .await
+    }
+
+    pub async fn remove(&self, key: u64) -> CanisterResult<bool> {
+        StorageClient::<u64, GroupWithMembers, GroupFilter, GroupSort>::remove(self, key).await
+    }
+}
+
+memory_storage_client!(
+    MemoryEventStorageClient,
+    EVENTS,
+    EVENT_CANISTER,
+    u64,
+    EventWithAttendees,
+    EventFilter,
+    EventSort,
+    |_, _, _| true
+);
+memory_storage_insertable!(
+    MemoryEventStorageClient,
+    EVENTS,
+    EventWithAttendees,
+    EventFilter,
+    EventSort
+);
+
+memory_storage_client!(
+    MemoryBoostedStorageClient,
+    BOOSTS,
+    BOOSTED_CANISTER,
+    u64,
+    Boost,
+    BoostedFilter,
+    BoostedSort,
+    |filter, _, boost| {
+        if let BoostedFilter::Subject(subject) = filter {
+            return &boost.subject == subject;
+        }
+        if let BoostedFilter::SubjectType(subject_type) = filter {
+            return &boost.subject.get_type() == subject_type;
+        }
+        true
+    }
+);
+memory_storage_insertable!(
+    MemoryBoostedStorageClient,
+    BOOSTS,
+    Boost,
+    BoostedFilter,
+    BoostedSort
+);
+
+memory_storage_client!(
+    MemoryNotificationStorageClient,
+    NOTIFICATIONS,
+    NOTIFICATION_CANISTER,
+    u64,
+    Notification,
+    NotificationFilter,
+    NotificationSort,
+    |_, _, _| true
+);
+memory_storage_insertable!(
+    MemoryNotificationStorageClient,
+    NOTIFICATIONS,
+    Notification,
+    NotificationFilter,
+    NotificationSort
+);
+
+memory_storage_client!(
+    MemoryFriendRequestStorageClient,
+    FRIEND_REQUESTS,
+    FRIEND_REQUEST_CANISTER,
+    u64,
+    FriendRequest,
+    FriendRequestFilter,
+    FriendRequestSort,
+    |_, _, _| true
+);
+memory_storage_insertable!(
+    MemoryFriendRequestStorageClient,
+    FRIEND_REQUESTS,
+    FriendRequest,
+    FriendRequestFilter,
+    FriendRequestSort
+);
+
+memory_storage_client!(
+    MemoryReportStorageClient,
+    REPORTS,
+    REPORT_CANISTER,
+    u64,
+    Report,
+    ReportFilter,
+    ReportSort,
+    |_, _, _| true
+);
+memory_storage_insertable!(
+    MemoryReportStorageClient,
+    REPORTS,
+    Report,
+    ReportFilter,
+    ReportSort
+);
+
+memory_storage_client!(
+    MemoryTopicStorageClient,
+    TOPICS,
+    TOPIC_CANISTER,
+    u64,
+    Topic,
+    TopicFilter,
+    TopicSort,
+    |_, _, _| true
+);
+memory_storage_insertable!(
+    MemoryTopicStorageClient,
+    TOPICS,
+    Topic,
+    TopicFilter,
+    TopicSort
+);
+
+/// Mirrors `GlobalStorageClient`, the global canister only receives notifications
+/// from the proxy so these are accepted without storing anything
+#[derive(Clone)]
+pub struct MemoryGlobalStorageClient;
+
+impl MemoryGlobalStorageClient {
+    pub async fn notify_group_member_count_changed(&self, _group_id: u64) -> CanisterResult<()> {
+        Ok(())
+    }
+
+    pub async fn notify_referral_accepted(&self, _referrer: Principal) -> CanisterResult<()> {
+        Ok(())
+    }
+
+    pub async fn notify_active_user(&self, _principal: Principal) -> CanisterResult<()> {
+        Ok(())
+    }
+
+    pub async fn read_reward_buffer(&self) -> CanisterResult<Vec<RewardableActivityResponse>> {
+        Ok(vec![])
+    }
+
+    pub async fn reward_timer_next_trigger(&self) -> CanisterResult<u64> {
+        Ok(0)
+    }
+
+    pub async fn get_history_point(&self) -> CanisterResult<u64> {
+        Ok(0)
+    }
+
+    pub async fn next_history_point(&self) -> CanisterResult<u64> {
+        Ok(0)
+    }
+
+    pub async fn notify_profile_filled(&self, _principal: Principal) -> CanisterResult<()> {
+        Ok(())
+    }
+}
@@ -1,25 +1,51 @@
+mod admin_storage;
+mod archived_group_storage;
 mod boosted_storage;
+pub mod cache;
 pub mod cells;
 mod event_storage;
 mod friend_request_storage;
+mod gated_membership_storage;
+mod gating_rule_storage;
 mod global_storage;
 mod group_storage;
+mod invite_code_storage;
+mod join_request_storage;
+mod member_cap_storage;
+#[cfg(test)]
+pub mod memory_storage;
 mod notification_storage;
+mod ownership_transfer_storage;
 mod profile_storage;
 mod report_storage;
+mod role_rank_storage;
+mod saga_storage;
+pub mod stable_storage;
 pub mod storage_api;
+mod token_subaccount_storage;
 mod topic_storage;
 
 // Re-export stores
 
+pub use admin_storage::*;
+pub use archived_group_storage::*;
 pub use boosted_storage::*;
 pub use event_storage::*;
 pub use friend_request_storage::*;
+pub use gated_membership_storage::*;
+pub use gating_rule_storage::*;
 pub use group_storage::*;
+pub use invite_code_storage::*;
+pub use join_request_storage::*;
+pub use member_cap_storage::*;
 pub use notification_storage::*;
+pub use ownership_transfer_storage::*;
 pub use profile_storage::*;
 pub use report_storage::*;
+pub use role_rank_storage::*;
+pub use saga_storage::*;
 pub use storage_api::*;
+pub use token_subaccount_storage::*;
 pub use topic_storage::*;
 
 pub use cells::*;
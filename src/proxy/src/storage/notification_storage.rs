@@ -25,6 +25,12 @@ impl StorageClientInsertable<Notification, NotificationFilter, NotificationSort>
 {
 }
 
+#[cfg(not(test))]
 pub fn notifications() -> NotificationStorageClient {
     NotificationStorageClient
 }
+
+#[cfg(test)]
+pub fn notifications() -> super::memory_storage::MemoryNotificationStorageClient {
+    super::memory_storage::MemoryNotificationStorageClient
+}
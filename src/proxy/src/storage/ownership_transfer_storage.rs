@@ -0,0 +1,8 @@
+use crate::models::OwnershipTransfer;
+
+use super::{stable_storage::StableStorage, storage_api::OWNERSHIP_TRANSFERS};
+
+/// Pending group ownership transfers by group id
+pub fn ownership_transfers() -> StableStorage<u64, OwnershipTransfer> {
+    StableStorage::new("ownership_transfer", &OWNERSHIP_TRANSFERS)
+}
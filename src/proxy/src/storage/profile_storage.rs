@@ -1,8 +1,11 @@
-use super::storage_api::PROFILE_CANISTER;
+use super::{
+    cache::{invalidate, read_through, PROFILE_CACHE},
+    storage_api::PROFILE_CANISTER,
+};
 use candid::Principal;
 use catalyze_shared::{
-    profile_with_refs::{ProfileFilter, ProfileSort, ProfileWithRefs},
-    StorageClient, StorageClientInsertableByKey,
+    profile_with_refs::{ProfileEntry, ProfileFilter, ProfileSort, ProfileWithRefs},
+    CanisterResult, StorageClient, StorageClientInsertableByKey,
 };
 
 #[derive(Default)]
@@ -25,6 +28,91 @@ impl StorageClientInsertableByKey<Principal, ProfileWithRefs, ProfileFilter, Pro
 {
 }
 
+/// Cached reads and invalidating writes, these take precedence over the `StorageClient` methods
+impl ProfileStorageClient {
+    pub async fn get(&self, key: Principal) -> CanisterResult<ProfileEntry> {
+        read_through(&PROFILE_CACHE, key, |key| {
+            StorageClient::<Principal, ProfileWithRefs, ProfileFilter, ProfileSort>::get(self, key)
+        })
+        .await
+    }
+
+    pub async fn update(
+        &self,
+        key: Principal,
+        value: ProfileWithRefs,
+    ) -> CanisterResult<ProfileEntry> {
+        // invalidated before and after the write so reads overlapping with it are not cached
+        invalidate(&PROFILE_CACHE, &key);
+        let result =
+            StorageClient::<Principal, ProfileWithRefs, ProfileFilter, ProfileSort>::update(
+                self, key, value,
+            )
+            .await;
+        invalidate(&PROFILE_CACHE, &key);
+        result
+    }
+
+    pub async fn insert(
+        &self,
+        key: Principal,
+        value: ProfileWithRefs,
+    ) -> CanisterResult<ProfileEntry> {
+        invalidate(&PROFILE_CACHE, &key);
+        let result = StorageClientInsertableByKey::<
+            Principal,
+            ProfileWithRefs,
+            ProfileFilter,
+            ProfileSort,
+        >::insert(self, key, value)
+        .await;
+        invalidate(&PROFILE_CACHE, &key);
+        result
+    }
+
+    pub async fn update_many(&self, list: Vec<ProfileEntry>) -> CanisterResult<Vec<ProfileEntry>> {
+        list.iter()
+            .for_each(|(key, _)| invalidate(&PROFILE_CACHE, key));
+        let keys = list.iter().map(|(key, _)| *key).collect::<Vec<_>>();
+        let result =
+            StorageClient::<Principal, ProfileWithRefs, ProfileFilter, ProfileSort>::update_many(
+                self, list,
+            )
+            .await;
+        keys.iter().for_each(|key| invalidate(&PROFILE_CACHE, key));
+        result
+    }
+
+    pub async fn remove(&self, key: Principal) -> CanisterResult<bool> {
+        invalidate(&PROFILE_CACHE, &key);
+        let result =
+            StorageClient::<Principal, ProfileWithRefs, ProfileFilter, ProfileSort>::remove(
+                self, key,
+            )
+            .await;
+        invalidate(&PROFILE_CACHE, &key);
+        result
+    }
+
+    pub async fn remove_many(&self, keys: Vec<Principal>) -> CanisterResult<()> {
+        keys.iter().for_each(|key| invalidate(&PROFILE_CACHE, key));
+        let result =
+            StorageClient::<Principal, ProfileWithRefs, ProfileFilter, ProfileSort>::remove_many(
+                self,
+                keys.clone(),
+            )
+            .await;
+        keys.iter().for_each(|key| invalidate(&PROFILE_CACHE, key));
+        result
+    }
+}
+
+#[cfg(not(test))]
 pub fn profiles() -> ProfileStorageClient {
     ProfileStorageClient
 }
+
+#[cfg(test)]
+pub fn profiles() -> super::memory_storage::MemoryProfileStorageClient {
+    super::memory_storage::MemoryProfileStorageClient
+}
@@ -20,6 +20,12 @@ impl StorageClient<u64, Report, ReportFilter, ReportSort> for ReportStorageClien
 
 impl StorageClientInsertable<Report, ReportFilter, ReportSort> for ReportStorageClient {}
 
+#[cfg(not(test))]
 pub fn reports() -> impl StorageClientInsertable<Report, ReportFilter, ReportSort> {
     ReportStorageClient
 }
+
+#[cfg(test)]
+pub fn reports() -> impl StorageClientInsertable<Report, ReportFilter, ReportSort> {
+    super::memory_storage::MemoryReportStorageClient
+}
@@ -0,0 +1,8 @@
+use crate::models::GroupRoleRanks;
+
+use super::{stable_storage::StableStorage, storage_api::GROUP_ROLE_RANKS};
+
+/// The ranks of the custom roles of groups by group id
+pub fn role_ranks() -> StableStorage<u64, GroupRoleRanks> {
+    StableStorage::new("role_rank", &GROUP_ROLE_RANKS)
+}
@@ -0,0 +1,7 @@
+use crate::models::Saga;
+
+use super::{stable_storage::StableStorage, storage_api::SAGAS};
+
+pub fn sagas() -> StableStorage<u64, Saga> {
+    StableStorage::new("saga", &SAGAS)
+}
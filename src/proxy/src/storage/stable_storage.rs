@@ -0,0 +1,94 @@
+use std::thread::LocalKey;
+
+use catalyze_shared::{api_error::ApiError, CanisterResult, StorageRef};
+use ic_stable_structures::Storable;
+
+use super::storage_api::IDS;
+
+/// A store that lives in the stable memory of the proxy itself,
+/// used for state that has no storage canister of its own.
+pub struct StableStorage<K, V>
+where
+    K: Storable + Ord + Clone + 'static,
+    V: Storable + Clone + 'static,
+{
+    name: &'static str,
+    storage: &'static LocalKey<StorageRef<K, V>>,
+}
+
+impl<K, V> StableStorage<K, V>
+where
+    K: Storable + Ord + Clone + 'static,
+    V: Storable + Clone + 'static,
+{
+    pub fn new(name: &'static str, storage: &'static LocalKey<StorageRef<K, V>>) -> Self {
+        Self { name, storage }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn get(&self, key: K) -> CanisterResult<V> {
+        self.storage
+            .with(|data| data.borrow().get(&key))
+            .ok_or_else(|| {
+                ApiError::not_found()
+                    .add_method_name("get")
+                    .add_message(format!("{} not found", self.name))
+            })
+    }
+
+    pub fn find(&self, key: K) -> Option<V> {
+        self.storage.with(|data| data.borrow().get(&key))
+    }
+
+    pub fn contains_key(&self, key: K) -> bool {
+        self.storage.with(|data| data.borrow().contains_key(&key))
+    }
+
+    pub fn get_all(&self) -> Vec<(K, V)> {
+        self.storage.with(|data| data.borrow().iter().collect())
+    }
+
+    pub fn filter<P: Fn(&K, &V) -> bool>(&self, predicate: P) -> Vec<(K, V)> {
+        self.storage.with(|data| {
+            data.borrow()
+                .iter()
+                .filter(|(key, value)| predicate(key, value))
+                .collect()
+        })
+    }
+
+    pub fn size(&self) -> u64 {
+        self.storage.with(|data| data.borrow().len())
+    }
+
+    /// Inserts or replaces the value for the given key
+    pub fn insert(&self, key: K, value: V) -> (K, V) {
+        self.storage
+            .with(|data| data.borrow_mut().insert(key.clone(), value.clone()));
+        (key, value)
+    }
+
+    pub fn remove(&self, key: K) -> Option<V> {
+        self.storage.with(|data| data.borrow_mut().remove(&key))
+    }
+}
+
+impl<V> StableStorage<u64, V>
+where
+    V: Storable + Clone + 'static,
+{
+    /// Inserts the value under the next id of this store
+    pub fn insert_next(&self, value: V) -> (u64, V) {
+        let id = IDS.with(|ids| {
+            let mut ids = ids.borrow_mut();
+            let id = ids.get(&self.name.to_string()).unwrap_or_default() + 1;
+            ids.insert(self.name.to_string(), id);
+            id
+        });
+
+        self.insert(id, value)
+    }
+}
@@ -5,6 +5,12 @@ use catalyze_shared::{
 };
 use ic_stable_structures::memory_manager::MemoryId;
 
+use crate::models::{
+    AdminAuditEntry, AdminRoles, ArchivedGroup, GatedGracePeriod, GatedRemovalReport,
+    GroupGatingRule, GroupMemberCap, GroupRoleRanks, InviteCode, JoinRequest, OwnershipTransfer,
+    PlatformConfig, Saga, TaskState, TokenSubaccounts,
+};
+
 /// The memory IDs for the different stores.
 /// # Note
 /// These IDs are used to identify the different stores in the `MemoryManager`.
@@ -26,6 +32,22 @@ pub static GLOBAL_CANISTER_MEMORY_ID: MemoryId = MemoryId::new(13);
 pub static NOTIFICATION_CANISTER_MEMORY_ID: MemoryId = MemoryId::new(14);
 pub static TRANSACTION_HANDLER_CANISTER_MEMORY_ID: MemoryId = MemoryId::new(15);
 
+pub static SAGAS_MEMORY_ID: MemoryId = MemoryId::new(16);
+pub static PLATFORM_CONFIG_MEMORY_ID: MemoryId = MemoryId::new(17);
+pub static ADMINS_MEMORY_ID: MemoryId = MemoryId::new(18);
+pub static ADMIN_AUDIT_LOG_MEMORY_ID: MemoryId = MemoryId::new(19);
+pub static OWNERSHIP_TRANSFERS_MEMORY_ID: MemoryId = MemoryId::new(20);
+pub static ARCHIVED_GROUPS_MEMORY_ID: MemoryId = MemoryId::new(21);
+pub static JOIN_REQUESTS_MEMORY_ID: MemoryId = MemoryId::new(22);
+pub static INVITE_CODES_MEMORY_ID: MemoryId = MemoryId::new(23);
+pub static GATED_GRACE_PERIODS_MEMORY_ID: MemoryId = MemoryId::new(24);
+pub static GATED_REMOVAL_REPORTS_MEMORY_ID: MemoryId = MemoryId::new(25);
+pub static GATING_RULES_MEMORY_ID: MemoryId = MemoryId::new(26);
+pub static TOKEN_SUBACCOUNTS_MEMORY_ID: MemoryId = MemoryId::new(27);
+pub static GROUP_MEMBER_CAPS_MEMORY_ID: MemoryId = MemoryId::new(28);
+pub static GROUP_ROLE_RANKS_MEMORY_ID: MemoryId = MemoryId::new(29);
+pub static TASK_STATE_MEMORY_ID: MemoryId = MemoryId::new(30);
+
 thread_local! {
     pub static MEMORY_MANAGER: MemoryManagerStorage = init_memory_manager();
 
@@ -42,4 +64,20 @@ thread_local! {
     pub static FRIEND_REQUEST_CANISTER: CellStorageRef<Principal> = init_cell(&MEMORY_MANAGER, "friend_request_canister_id", FRIEND_REQUEST_CANISTER_MEMORY_ID);
     pub static GLOBAL_CANISTER: CellStorageRef<Principal> = init_cell(&MEMORY_MANAGER, "global_canister_id", GLOBAL_CANISTER_MEMORY_ID);
     pub static TRANSACTION_HANDLER_CANISTER: CellStorageRef<Principal> = init_cell(&MEMORY_MANAGER, "transaction_handler_canister_id", TRANSACTION_HANDLER_CANISTER_MEMORY_ID);
+
+    pub static SAGAS: StorageRef<u64, Saga> = init_btree(&MEMORY_MANAGER, SAGAS_MEMORY_ID);
+    pub static PLATFORM_CONFIG: CellStorageRef<PlatformConfig> = init_cell(&MEMORY_MANAGER, "platform_config", PLATFORM_CONFIG_MEMORY_ID);
+    pub static ADMINS: StorageRef<Principal, AdminRoles> = init_btree(&MEMORY_MANAGER, ADMINS_MEMORY_ID);
+    pub static ADMIN_AUDIT_LOG: StorageRef<u64, AdminAuditEntry> = init_btree(&MEMORY_MANAGER, ADMIN_AUDIT_LOG_MEMORY_ID);
+    pub static OWNERSHIP_TRANSFERS: StorageRef<u64, OwnershipTransfer> = init_btree(&MEMORY_MANAGER, OWNERSHIP_TRANSFERS_MEMORY_ID);
+    pub static ARCHIVED_GROUPS: StorageRef<u64, ArchivedGroup> = init_btree(&MEMORY_MANAGER, ARCHIVED_GROUPS_MEMORY_ID);
+    pub static JOIN_REQUESTS: StorageRef<u64, JoinRequest> = init_btree(&MEMORY_MANAGER, JOIN_REQUESTS_MEMORY_ID);
+    pub static INVITE_CODES: StorageRef<String, InviteCode> = init_btree(&MEMORY_MANAGER, INVITE_CODES_MEMORY_ID);
+    pub static GATED_GRACE_PERIODS: StorageRef<String, GatedGracePeriod> = init_btree(&MEMORY_MANAGER, GATED_GRACE_PERIODS_MEMORY_ID);
+    pub static GATED_REMOVAL_REPORTS: StorageRef<u64, GatedRemovalReport> = init_btree(&MEMORY_MANAGER, GATED_REMOVAL_REPORTS_MEMORY_ID);
+    pub static GATING_RULES: StorageRef<u64, GroupGatingRule> = init_btree(&MEMORY_MANAGER, GATING_RULES_MEMORY_ID);
+    pub static TOKEN_SUBACCOUNTS: StorageRef<Principal, TokenSubaccounts> = init_btree(&MEMORY_MANAGER, TOKEN_SUBACCOUNTS_MEMORY_ID);
+    pub static GROUP_MEMBER_CAPS: StorageRef<u64, GroupMemberCap> = init_btree(&MEMORY_MANAGER, GROUP_MEMBER_CAPS_MEMORY_ID);
+    pub static GROUP_ROLE_RANKS: StorageRef<u64, GroupRoleRanks> = init_btree(&MEMORY_MANAGER, GROUP_ROLE_RANKS_MEMORY_ID);
+    pub static TASK_STATE: CellStorageRef<TaskState> = init_cell(&MEMORY_MANAGER, "task_state", TASK_STATE_MEMORY_ID);
 }
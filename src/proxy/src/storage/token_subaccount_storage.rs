@@ -0,0 +1,10 @@
+use candid::Principal;
+
+use crate::models::TokenSubaccounts;
+
+use super::{stable_storage::StableStorage, storage_api::TOKEN_SUBACCOUNTS};
+
+/// ICRC subaccounts used for token gating by principal
+pub fn token_subaccounts() -> StableStorage<Principal, TokenSubaccounts> {
+    StableStorage::new("token_subaccounts", &TOKEN_SUBACCOUNTS)
+}
@@ -20,6 +20,12 @@ impl StorageClient<u64, Topic, TopicFilter, TopicSort> for TopicStorageClient {
 
 impl StorageClientInsertable<Topic, TopicFilter, TopicSort> for TopicStorageClient {}
 
+#[cfg(not(test))]
 pub fn topics() -> impl StorageClientInsertable<Topic, TopicFilter, TopicSort> {
     TopicStorageClient
 }
+
+#[cfg(test)]
+pub fn topics() -> impl StorageClientInsertable<Topic, TopicFilter, TopicSort> {
+    super::memory_storage::MemoryTopicStorageClient
+}
@@ -0,0 +1,38 @@
+use candid::Principal;
+
+use crate::{
+    helpers::{env::set_caller, guards::is_prod_developer},
+    logic::admin_logic::AdminCalls,
+    models::AdminRole,
+};
+
+const PRODUCTION: &str = "ledm3-52ncq-rffuv-6ed44-hg5uo-iicyu-pwkzj-syfva-heo4k-p7itq-aqe";
+const STAGING: &str = "syzio-xu6ca-burmx-4afo2-ojpcw-e75j3-m67o5-s5bes-5vvsv-du3t4-wae";
+
+#[test]
+fn test_prod_developer_is_production_only() {
+    AdminCalls::seed_legacy_admins();
+
+    let staging = Principal::from_text(STAGING).unwrap();
+    assert!(AdminCalls::has_role(staging, &[AdminRole::Developer]));
+    set_caller(staging);
+    assert!(is_prod_developer().is_err());
+
+    set_caller(Principal::from_text(PRODUCTION).unwrap());
+    assert!(is_prod_developer().is_ok());
+}
+
+#[test]
+fn test_seed_legacy_admins_runs_once() {
+    AdminCalls::seed_legacy_admins();
+
+    let staging = Principal::from_text(STAGING).unwrap();
+    AdminCalls::remove_role(staging, AdminRole::Developer).unwrap();
+    assert!(!AdminCalls::get_admins()
+        .iter()
+        .any(|(principal, _)| principal == &staging));
+
+    // an upgrade seeds again, the revoked principal must not come back
+    AdminCalls::seed_legacy_admins();
+    assert!(!AdminCalls::has_role(staging, &[AdminRole::Developer]));
+}
@@ -0,0 +1,99 @@
+use std::cell::RefCell;
+
+use catalyze_shared::{api_error::ApiError, CanisterResult};
+
+use crate::{
+    helpers::env::set_time,
+    storage::cache::{invalidate, read_through, HeapCache},
+};
+
+const TTL: u64 = 10;
+
+thread_local! {
+    static CACHE: RefCell<HeapCache<u64, String>> = RefCell::new(HeapCache::new(2, TTL));
+}
+
+#[test]
+fn test_heap_cache_expires_entries() {
+    let mut cache = HeapCache::new(2, TTL);
+
+    assert!(cache.get(&1, 0).is_none());
+    cache.insert(1, "one", cache.version(), 0);
+
+    assert_eq!(cache.get(&1, TTL - 1), Some("one"));
+    assert!(cache.get(&1, TTL).is_none());
+
+    let stats = cache.stats();
+    assert_eq!(stats.hits, 1);
+    assert_eq!(stats.misses, 2);
+    assert_eq!(stats.size, 0);
+}
+
+#[test]
+fn test_heap_cache_evicts_oldest_entry() {
+    let mut cache = HeapCache::new(2, TTL);
+
+    cache.insert(1, "one", cache.version(), 0);
+    cache.insert(2, "two", cache.version(), 1);
+    cache.insert(3, "three", cache.version(), 2);
+
+    assert_eq!(cache.stats().size, 2);
+    assert!(cache.get(&1, 2).is_none());
+    assert_eq!(cache.get(&2, 2), Some("two"));
+    assert_eq!(cache.get(&3, 2), Some("three"));
+}
+
+#[test]
+fn test_heap_cache_invalidation() {
+    let mut cache = HeapCache::new(2, TTL);
+
+    cache.insert(1, "one", cache.version(), 0);
+    let version = cache.version();
+    cache.invalidate(&1);
+    assert!(cache.get(&1, 0).is_none());
+
+    // a value fetched before the invalidation is not cached
+    cache.insert(1, "stale", version, 0);
+    assert!(cache.get(&1, 0).is_none());
+
+    cache.insert(1, "fresh", cache.version(), 0);
+    assert_eq!(cache.get(&1, 0), Some("fresh"));
+}
+
+#[tokio::test]
+async fn test_read_through() {
+    set_time(0);
+
+    let fetched = read_through(&CACHE, 1, |key| async move {
+        CanisterResult::Ok((key, "one".to_string()))
+    })
+    .await
+    .unwrap();
+    assert_eq!(fetched.1, "one");
+
+    // served from the cache, the fetch is not called
+    let cached = read_through(&CACHE, 1, |_| async move {
+        CanisterResult::<(u64, String)>::Err(ApiError::unexpected())
+    })
+    .await
+    .unwrap();
+    assert_eq!(cached.1, "one");
+
+    // a write during the fetch keeps the fetched value out of the cache
+    let raced = read_through(&CACHE, 2, |key| async move {
+        invalidate(&CACHE, &key);
+        CanisterResult::Ok((key, "stale".to_string()))
+    })
+    .await
+    .unwrap();
+    assert_eq!(raced.1, "stale");
+    assert!(CACHE.with(|cache| cache.borrow_mut().get(&2, 0)).is_none());
+
+    // errors are not cached
+    assert!(read_through(&CACHE, 3, |_| async move {
+        CanisterResult::<(u64, String)>::Err(ApiError::not_found())
+    })
+    .await
+    .is_err());
+    assert!(CACHE.with(|cache| cache.borrow_mut().get(&3, 0)).is_none());
+}
@@ -0,0 +1,49 @@
+use std::borrow::Cow;
+
+use candid::{CandidType, Deserialize};
+use ic_stable_structures::Storable;
+
+use crate::models::PlatformConfig;
+
+/// The fields of the first stored version of the `PlatformConfig`
+#[derive(CandidType, Deserialize)]
+struct PlatformConfigV1 {
+    version: u64,
+    e8s_per_day_boost_cost: u64,
+    user_group_creation_limit: u64,
+    topic_min_length: u64,
+    topic_max_length: u64,
+    friend_request_auto_remove_delay: u64,
+    updated_at: u64,
+}
+
+#[test]
+fn test_platform_config_decodes_first_version() {
+    let stored = PlatformConfigV1 {
+        version: 3,
+        e8s_per_day_boost_cost: 1,
+        user_group_creation_limit: 2,
+        topic_min_length: 3,
+        topic_max_length: 4,
+        friend_request_auto_remove_delay: 5,
+        updated_at: 6,
+    };
+
+    let bytes = candid::Encode!(&stored).unwrap();
+    let config = PlatformConfig::from_bytes(Cow::Owned(bytes));
+
+    assert_eq!(config.version, 3);
+    assert_eq!(config.topic_max_length, 4);
+    assert!(config.group_restore_window.is_none());
+
+    let defaults = PlatformConfig::default();
+    let config = config.with_defaults();
+    assert_eq!(config.boost_receiver, defaults.boost_receiver);
+    assert_eq!(config.group_restore_window, defaults.group_restore_window);
+    assert_eq!(config.join_request_expiry, defaults.join_request_expiry);
+    assert_eq!(config.gated_grace_period, defaults.gated_grace_period);
+    assert_eq!(
+        config.gated_revalidation_batch_size,
+        defaults.gated_revalidation_batch_size
+    );
+}
@@ -0,0 +1,695 @@
+use candid::Principal;
+use catalyze_shared::{
+    group_with_members::UpdateGroup,
+    privacy::{GatedType, PrivacyType, TokenGated},
+    wallet::Wallet,
+    CellStorage, StorageClient,
+};
+
+use super::utils::{principal, seed_group, seed_member, seed_profile};
+use crate::{
+    helpers::env::{set_caller, set_time},
+    helpers::{
+        sns_governance::{DissolveState, NervousSystemParameters, Neuron, NeuronId},
+        token_balance::Account,
+    },
+    logic::{
+        config_logic::ConfigCalls,
+        group_logic::{
+            GroupCalls, GroupValidation, MAX_GATING_RULE_CONDITIONS, MAX_GATING_RULE_DEPTH,
+        },
+        profile_logic::{ProfileCalls, MAX_TOKEN_SUBACCOUNTS},
+    },
+    models::{GatingRule, GroupNotice, NeuronRule, TokenStandard, UpdatePlatformConfig},
+    storage::{groups, notifications, profiles, task_state},
+};
+
+fn token_gated(standard: &str) -> TokenGated {
+    TokenGated {
+        name: "token".to_string(),
+        description: "".to_string(),
+        principal: Principal::anonymous(),
+        amount: 1,
+        standard: standard.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_ext_gate_requires_account_identifier() {
+    let is_valid =
+        GroupValidation::validate_nft_gated(&principal(1), &None, &token_gated("EXT")).await;
+
+    assert!(!is_valid);
+}
+
+#[tokio::test]
+async fn test_unknown_token_standard() {
+    let is_valid = GroupValidation::validate_nft_gated(
+        &principal(1),
+        &Some("account".to_string()),
+        &token_gated("UNKNOWN"),
+    )
+    .await;
+
+    assert!(!is_valid);
+}
+
+#[tokio::test]
+async fn test_join_token_gated_group_without_token() {
+    let owner = principal(1);
+    let member = principal(2);
+
+    let privacy = PrivacyType::Gated(GatedType::Token(vec![
+        token_gated("EXT"),
+        token_gated("UNKNOWN"),
+    ]));
+
+    let group_id = seed_group(owner, privacy).await;
+    seed_profile(member).await;
+
+    set_caller(member);
+    assert!(GroupCalls::join_group(group_id, None).await.is_err());
+
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert!(!group.is_member(member));
+
+    let (_, profile) = profiles().get(member).await.unwrap();
+    assert!(!profile.is_group_member(group_id));
+}
+
+#[tokio::test]
+async fn test_revalidate_gated_members() {
+    let owner = principal(1);
+    let lapsed = principal(2);
+    let holder = principal(3);
+
+    let gated = |standard| PrivacyType::Gated(GatedType::Token(vec![token_gated(standard)]));
+
+    let group_id = seed_group(owner, gated("UNKNOWN")).await;
+    seed_member(group_id, lapsed).await;
+
+    // EXT gates can't be checked without the account identifier
+    let ext_group_id = seed_group(owner, gated("EXT")).await;
+    seed_member(ext_group_id, holder).await;
+
+    let reports = GroupCalls::revalidate_gated_members().await.unwrap();
+    assert!(reports.is_empty());
+
+    let grace_periods = GroupCalls::get_gated_grace_periods_for(lapsed);
+    assert_eq!(grace_periods.len(), 1);
+    assert_eq!(grace_periods[0].group_id, group_id);
+
+    let notification_id = grace_periods[0]
+        .notification_id
+        .expect("No grace period notice");
+    let (_, notification) = notifications().get(notification_id).await.unwrap();
+    assert_eq!(
+        notification
+            .metadata
+            .as_deref()
+            .and_then(GroupNotice::from_metadata),
+        Some(GroupNotice::GatedGracePeriodStarted {
+            expires_at: grace_periods[0].expires_at
+        })
+    );
+    assert!(GroupCalls::get_gated_grace_periods_for(holder).is_empty());
+    assert!(GroupCalls::get_gated_grace_periods_for(owner).is_empty());
+
+    // still a member during the grace period
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert!(group.is_member(lapsed));
+
+    set_time(grace_periods[0].expires_at);
+    let reports = GroupCalls::revalidate_gated_members().await.unwrap();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].removed, vec![lapsed]);
+
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert!(!group.is_member(lapsed));
+    assert!(group.is_member(owner));
+
+    let (_, profile) = profiles().get(lapsed).await.unwrap();
+    assert!(!profile.is_group_member(group_id));
+
+    let (_, ext_group) = groups().get(ext_group_id).await.unwrap();
+    assert!(ext_group.is_member(holder));
+
+    assert_eq!(GroupCalls::get_gated_removal_reports(group_id).len(), 1);
+    assert!(GroupCalls::get_gated_grace_periods_for(lapsed).is_empty());
+}
+
+#[tokio::test]
+async fn test_revalidate_gated_members_in_batches() {
+    let owner = principal(1);
+    let gated = PrivacyType::Gated(GatedType::Token(vec![token_gated("UNKNOWN")]));
+
+    let mut members = vec![];
+    for n in 2..5 {
+        let group_id = seed_group(owner, gated.clone()).await;
+        seed_member(group_id, principal(n)).await;
+        members.push((group_id, principal(n)));
+    }
+
+    ConfigCalls::update_platform_config(UpdatePlatformConfig {
+        gated_revalidation_batch_size: Some(1),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let has_grace_period = |(group_id, member): (u64, Principal)| {
+        GroupCalls::get_gated_grace_periods_for(member)
+            .iter()
+            .any(|grace_period| grace_period.group_id == group_id)
+    };
+
+    // every run checks the member after the stored cursor
+    for (run, member) in members.iter().enumerate() {
+        GroupCalls::revalidate_gated_members().await.unwrap();
+        assert!(has_grace_period(*member));
+
+        if let Some(next) = members.get(run + 1) {
+            assert!(!has_grace_period(*next));
+            assert_eq!(
+                task_state().get().unwrap().gated_revalidation_cursor,
+                Some(*member)
+            );
+        }
+    }
+
+    // the run that reached the last member starts the next run over
+    assert!(task_state()
+        .get()
+        .unwrap()
+        .gated_revalidation_cursor
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_meets_gating_rule() {
+    use GatingRule::*;
+
+    let member = principal(1);
+    let met = || All(vec![]);
+    let not_met = || Token(token_gated("UNKNOWN"));
+
+    let cases = [
+        (Any(vec![not_met(), met()]), true),
+        (All(vec![not_met(), met()]), false),
+        (All(vec![met(), Any(vec![not_met(), met()])]), true),
+        (
+            AtLeast {
+                amount: 2,
+                rules: vec![met(), not_met(), met()],
+            },
+            true,
+        ),
+        (
+            AtLeast {
+                amount: 3,
+                rules: vec![met(), not_met(), met()],
+            },
+            false,
+        ),
+    ];
+
+    for (gating_rule, expected) in cases {
+        let is_met = GroupValidation::meets_gating_rule(member, &None, &gating_rule).await;
+        assert_eq!(is_met, expected, "{:?}", gating_rule);
+    }
+}
+
+#[test]
+fn test_gating_rule_with_unchecked_conditions() {
+    use GatingRule::*;
+
+    let condition = || Token(token_gated("ICRC"));
+    let at_least = |amount| AtLeast {
+        amount,
+        rules: vec![condition(), condition(), condition()],
+    };
+
+    // `None` is a condition whose ledger or governance call failed
+    let cases = [
+        (
+            All(vec![condition(), condition()]),
+            vec![None, Some(true)],
+            None,
+        ),
+        (
+            All(vec![condition(), condition()]),
+            vec![None, Some(false)],
+            Some(false),
+        ),
+        (
+            Any(vec![condition(), condition()]),
+            vec![None, Some(true)],
+            Some(true),
+        ),
+        (
+            Any(vec![condition(), condition()]),
+            vec![None, Some(false)],
+            None,
+        ),
+        (at_least(2), vec![Some(true), None, Some(true)], Some(true)),
+        (at_least(2), vec![Some(true), None, Some(false)], None),
+        (
+            at_least(2),
+            vec![None, Some(false), Some(false)],
+            Some(false),
+        ),
+    ];
+
+    for (gating_rule, results, expected) in cases {
+        assert_eq!(
+            gating_rule.try_is_met_by(&mut results.into_iter()),
+            expected,
+            "{:?}",
+            gating_rule
+        );
+    }
+}
+
+#[test]
+fn test_validate_gating_rule() {
+    use GatingRule::*;
+
+    let token = || Token(token_gated("ICRC"));
+
+    assert!(GroupValidation::validate_gating_rule(&All(vec![token(), token()])).is_ok());
+    assert!(GroupValidation::validate_gating_rule(&Any(vec![])).is_err());
+    assert!(GroupValidation::validate_gating_rule(&All(vec![token(), Any(vec![])])).is_err());
+
+    for amount in [0, 3] {
+        let gating_rule = AtLeast {
+            amount,
+            rules: vec![token(), token()],
+        };
+        assert!(GroupValidation::validate_gating_rule(&gating_rule).is_err());
+    }
+
+    let too_deep = (0..MAX_GATING_RULE_DEPTH).fold(token(), |rule, _| All(vec![rule]));
+    assert!(GroupValidation::validate_gating_rule(&too_deep).is_err());
+
+    let too_many = Any(vec![token(); MAX_GATING_RULE_CONDITIONS + 1]);
+    assert!(GroupValidation::validate_gating_rule(&too_many).is_err());
+}
+
+#[tokio::test]
+async fn test_join_with_custom_gating_rule() {
+    let owner = principal(1);
+    let member = principal(2);
+
+    let privacy = PrivacyType::Gated(GatedType::Token(vec![token_gated("UNKNOWN")]));
+    let group_id = seed_group(owner, privacy).await;
+    seed_profile(member).await;
+
+    // the gated type is the rule until a custom rule is set
+    let gating_rule = GroupCalls::get_group_gating_rule(group_id).await.unwrap();
+    assert!(matches!(
+        gating_rule,
+        Some(GatingRule::AtLeast { amount: 1, .. })
+    ));
+
+    let custom_rule = GatingRule::All(vec![
+        GatingRule::Token(token_gated("EXT")),
+        GatingRule::Token(token_gated("ICRC")),
+    ]);
+
+    set_caller(owner);
+    GroupCalls::set_group_gating_rule(group_id, Some(custom_rule))
+        .await
+        .expect("Failed to set gating rule");
+
+    let gating_rule = GroupCalls::get_group_gating_rule(group_id).await.unwrap();
+    assert!(matches!(gating_rule, Some(GatingRule::All(rules)) if rules.len() == 2));
+
+    set_caller(member);
+    assert!(GroupCalls::join_group(group_id, None).await.is_err());
+
+    let public_group_id = seed_group(owner, PrivacyType::Public).await;
+    set_caller(owner);
+    assert!(GroupCalls::set_group_gating_rule(
+        public_group_id,
+        Some(GatingRule::Token(token_gated("ICRC")))
+    )
+    .await
+    .is_err());
+    assert!(GroupCalls::get_group_gating_rule(public_group_id)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_gating_rule_keeps_the_gated_type_amount() {
+    let owner = principal(1);
+
+    let privacy = PrivacyType::Gated(GatedType::Token(vec![
+        token_gated("ICRC"),
+        token_gated("ICRC"),
+        token_gated("ICRC"),
+    ]));
+    let group_id = seed_group(owner, privacy).await;
+
+    // groups without a stored rule use the amount of the group
+    let (_, mut group) = groups().get(group_id).await.unwrap();
+    group.privacy_gated_type_amount = Some(2);
+    groups().update(group_id, group.clone()).await.unwrap();
+
+    let gating_rule = GroupCalls::get_group_gating_rule(group_id).await.unwrap();
+    assert!(matches!(
+        gating_rule,
+        Some(GatingRule::AtLeast { amount: 2, .. })
+    ));
+
+    // an update without an amount keeps the stored amount
+    let update_group = UpdateGroup {
+        name: "edited group".to_string(),
+        description: group.description,
+        website: group.website,
+        privacy: group.privacy,
+        image: group.image,
+        banner_image: group.banner_image,
+        location: group.location,
+        tags: group.tags,
+        privacy_gated_type_amount: None,
+    };
+
+    GroupCalls::edit_group(group_id, update_group)
+        .await
+        .expect("Failed to edit group");
+
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert_eq!(group.privacy_gated_type_amount, Some(2));
+
+    let gating_rule = GroupCalls::get_group_gating_rule(group_id).await.unwrap();
+    assert!(matches!(
+        gating_rule,
+        Some(GatingRule::AtLeast { amount: 2, .. })
+    ));
+}
+
+#[test]
+fn test_gating_rule_is_met_by() {
+    use GatingRule::*;
+
+    let token = || Token(token_gated("ICRC"));
+    let gating_rule = All(vec![
+        token(),
+        AtLeast {
+            amount: 2,
+            rules: vec![token(), token(), token()],
+        },
+    ]);
+
+    assert_eq!(gating_rule.get_conditions().len(), 4);
+
+    let cases = [
+        ([true, true, false, true], true),
+        ([true, false, false, true], false),
+        ([false, true, true, true], false),
+    ];
+
+    for (results, expected) in cases {
+        let mut results = results.into_iter();
+        assert_eq!(gating_rule.is_met_by(&mut results), expected);
+        // every result is read, also when the outcome is known early
+        assert!(results.next().is_none());
+    }
+}
+
+#[tokio::test]
+async fn test_check_group_eligibility() {
+    let owner = principal(1);
+    let member = principal(2);
+
+    let privacy = PrivacyType::Gated(GatedType::Token(vec![
+        token_gated("EXT"),
+        token_gated("UNKNOWN"),
+    ]));
+    let group_id = seed_group(owner, privacy).await;
+    seed_profile(member).await;
+
+    set_caller(member);
+    let eligibility = GroupCalls::check_group_eligibility(group_id, None)
+        .await
+        .expect("Failed to check eligibility");
+
+    assert!(!eligibility.eligible);
+    assert_eq!(eligibility.checks.len(), 2);
+    assert_eq!(eligibility.checks[0].standard, "EXT");
+    assert_eq!(eligibility.checks[0].required_amount, 1);
+    assert!(eligibility.checks[0].observed_amount.is_none());
+    assert!(eligibility.checks.iter().all(|check| !check.passed));
+
+    // checking does not join the group
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert!(!group.is_member(member));
+
+    let public_group_id = seed_group(owner, PrivacyType::Public).await;
+    assert!(GroupCalls::check_group_eligibility(public_group_id, None)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_token_standards() {
+    for standard in ["EXT", "DIP20", "DIP721", "DIP721_LEGACY", "ICRC", "ICRC7"] {
+        let token_standard = standard.parse::<TokenStandard>().unwrap();
+        assert_eq!(token_standard.as_str(), standard);
+    }
+    assert_eq!(
+        "ICRC1".parse::<TokenStandard>().unwrap(),
+        TokenStandard::Icrc1
+    );
+    assert!("ICRC-7".parse::<TokenStandard>().is_err());
+
+    let misspelled = GatingRule::Any(vec![
+        GatingRule::Token(token_gated("ICRC7")),
+        GatingRule::Token(token_gated("icrc7")),
+    ]);
+    assert!(GroupValidation::validate_gating_rule(&misspelled).is_err());
+
+    let owner = principal(1);
+    let privacy = PrivacyType::Gated(GatedType::Token(vec![token_gated("EXT")]));
+    let group_id = seed_group(owner, privacy).await;
+
+    set_caller(owner);
+    assert!(
+        GroupCalls::set_group_gating_rule(group_id, Some(misspelled))
+            .await
+            .is_err()
+    );
+    assert!(GroupCalls::set_group_gating_rule(
+        group_id,
+        Some(GatingRule::Token(token_gated("ICRC7")))
+    )
+    .await
+    .is_ok());
+}
+
+#[tokio::test]
+async fn test_token_accounts() {
+    let member = principal(1);
+    let wallet = principal(2);
+    seed_profile(member).await;
+
+    let (_, mut profile) = profiles().get(member).await.unwrap();
+    profile.references.wallets.insert(
+        wallet.to_string(),
+        Wallet {
+            provider: "plug".to_string(),
+            is_primary: true,
+        },
+    );
+    profiles().update(member, profile).await.unwrap();
+
+    set_caller(member);
+    assert!(ProfileCalls::set_token_subaccounts(vec![vec![1; 31]]).is_err());
+    assert!(ProfileCalls::set_token_subaccounts(
+        (0..=MAX_TOKEN_SUBACCOUNTS as u8)
+            .map(|i| vec![i; 32])
+            .collect()
+    )
+    .is_err());
+
+    // duplicates are registered once
+    let subaccount = vec![1; 32];
+    let subaccounts = ProfileCalls::set_token_subaccounts(vec![subaccount.clone(); 2]).unwrap();
+    assert_eq!(subaccounts, vec![subaccount.clone()]);
+    assert_eq!(ProfileCalls::get_token_subaccounts(member), subaccounts);
+
+    let accounts = GroupValidation::get_token_accounts(member);
+    assert_eq!(
+        accounts,
+        vec![
+            Account {
+                owner: member,
+                subaccount: None,
+            },
+            Account {
+                owner: member,
+                subaccount: Some(subaccount.clone()),
+            },
+        ]
+    );
+
+    // a linked wallet is not proven to belong to the member
+    assert!(!accounts.iter().any(|account| account.owner == wallet));
+
+    ProfileCalls::set_token_subaccounts(vec![]).unwrap();
+    assert!(ProfileCalls::get_token_subaccounts(member).is_empty());
+    assert_eq!(GroupValidation::get_token_accounts(member).len(), 1);
+}
+
+const NOW_SECONDS: u64 = 1_000_000;
+
+fn locked_neuron() -> Neuron {
+    Neuron {
+        id: Some(NeuronId { id: vec![1] }),
+        cached_neuron_stake_e8s: 1_000_000_000,
+        neuron_fees_e8s: 0,
+        staked_maturity_e8s_equivalent: None,
+        created_timestamp_seconds: NOW_SECONDS - 1_000,
+        aging_since_timestamp_seconds: NOW_SECONDS - 1_000,
+        dissolve_state: Some(DissolveState::DissolveDelaySeconds(500)),
+        voting_power_percentage_multiplier: 100,
+    }
+}
+
+fn dissolving_neuron() -> Neuron {
+    Neuron {
+        aging_since_timestamp_seconds: u64::MAX,
+        dissolve_state: Some(DissolveState::WhenDissolvedTimestampSeconds(
+            NOW_SECONDS + 100,
+        )),
+        ..locked_neuron()
+    }
+}
+
+fn nervous_system_parameters() -> NervousSystemParameters {
+    NervousSystemParameters {
+        neuron_minimum_dissolve_delay_to_vote_seconds: Some(100),
+        max_dissolve_delay_seconds: Some(1_000),
+        max_dissolve_delay_bonus_percentage: Some(100),
+        max_neuron_age_for_age_bonus: Some(1_000),
+        max_age_bonus_percentage: Some(25),
+    }
+}
+
+#[test]
+fn test_neuron_meets_rules() {
+    use NeuronRule::*;
+
+    let cases = [
+        (IsDissolving, false, true),
+        (MinAge(1_000), true, true),
+        (MinAge(1_001), false, false),
+        (MinStake(1_000_000_000), true, true),
+        (MinStake(1_100_000_000), false, false),
+        (MinDissolveDelay(500), true, false),
+        (MinDissolveDelay(501), false, false),
+        // voting power can't be checked without the parameters of the SNS
+        (MinVotingPower(1), false, false),
+    ];
+
+    for (rule, locked_meets, dissolving_meets) in cases {
+        let rules = [rule.clone()];
+        assert_eq!(
+            GroupValidation::neuron_meets_rules(&locked_neuron(), &rules, None, NOW_SECONDS),
+            locked_meets,
+            "{:?}",
+            rule
+        );
+        assert_eq!(
+            GroupValidation::neuron_meets_rules(&dissolving_neuron(), &rules, None, NOW_SECONDS),
+            dissolving_meets,
+            "{:?}",
+            rule
+        );
+    }
+
+    let rules = [MinStake(1_000_000_000), MinDissolveDelay(501)];
+    assert!(!GroupValidation::neuron_meets_rules(
+        &locked_neuron(),
+        &rules,
+        None,
+        NOW_SECONDS
+    ));
+}
+
+#[test]
+fn test_neuron_min_age() {
+    let rules = [NeuronRule::MinAge(1_000)];
+
+    // the age counts, not the creation timestamp which is always larger than the minimum age
+    let new_neuron = Neuron {
+        created_timestamp_seconds: NOW_SECONDS - 10,
+        ..locked_neuron()
+    };
+    assert!(!GroupValidation::neuron_meets_rules(
+        &new_neuron,
+        &rules,
+        None,
+        NOW_SECONDS
+    ));
+
+    let old_neuron = Neuron {
+        created_timestamp_seconds: NOW_SECONDS - 2_000,
+        ..locked_neuron()
+    };
+    assert!(GroupValidation::neuron_meets_rules(
+        &old_neuron,
+        &rules,
+        None,
+        NOW_SECONDS
+    ));
+
+    // a neuron from the future has no age
+    let future_neuron = Neuron {
+        created_timestamp_seconds: NOW_SECONDS + 10,
+        ..locked_neuron()
+    };
+    assert!(!GroupValidation::neuron_meets_rules(
+        &future_neuron,
+        &rules,
+        None,
+        NOW_SECONDS
+    ));
+}
+
+#[test]
+fn test_neuron_voting_power() {
+    let parameters = nervous_system_parameters();
+
+    // 10 tokens with a 50% dissolve delay bonus and a 25% age bonus
+    assert_eq!(
+        locked_neuron().voting_power(&parameters, NOW_SECONDS),
+        1_875_000_000
+    );
+    // dissolving neurons don't age
+    assert_eq!(
+        dissolving_neuron().voting_power(&parameters, NOW_SECONDS),
+        1_100_000_000
+    );
+
+    let too_short = Neuron {
+        dissolve_state: Some(DissolveState::DissolveDelaySeconds(99)),
+        ..locked_neuron()
+    };
+    assert_eq!(too_short.voting_power(&parameters, NOW_SECONDS), 0);
+
+    let rules = [NeuronRule::MinVotingPower(1_875_000_000)];
+    assert!(GroupValidation::neuron_meets_rules(
+        &locked_neuron(),
+        &rules,
+        Some(&parameters),
+        NOW_SECONDS
+    ));
+    assert!(!GroupValidation::neuron_meets_rules(
+        &dissolving_neuron(),
+        &rules,
+        Some(&parameters),
+        NOW_SECONDS
+    ));
+}
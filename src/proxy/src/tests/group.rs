@@ -0,0 +1,1464 @@
+use candid::Principal;
+use catalyze_shared::{
+    date_range::DateRange, group_with_members::GroupSort, old_member::JoinedMemberResponse,
+    privacy::PrivacyType, StorageClient,
+};
+
+use super::utils::{principal, seed_group, seed_member, seed_profile};
+use crate::{
+    helpers::env::{
+        intervals, pending_timers, run_due_timers, run_intervals, set_caller, set_time,
+        take_ws_messages,
+    },
+    logic::{
+        config_logic::ConfigCalls,
+        group_logic::{GroupCalls, MEMBER_BATCH_LIMIT},
+    },
+    models::{
+        GatedGracePeriod, GroupMemberFilter, GroupMemberSort, GroupNotice, JoinGroupResult,
+        PostInviteCode, Saga, SagaKind, SagaStep, SortOrder,
+    },
+    storage::{
+        gated_grace_periods, groups, invite_codes, join_requests, notifications,
+        ownership_transfers, profiles, sagas,
+    },
+};
+
+#[tokio::test]
+async fn test_join_public_group() {
+    let owner = principal(1);
+    let member = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+    seed_profile(member).await;
+
+    set_caller(member);
+    let JoinGroupResult::Joined(joined) = GroupCalls::join_group(group_id, None)
+        .await
+        .expect("Failed to join group")
+    else {
+        panic!("Expected to join the group");
+    };
+
+    assert_eq!(joined.principal, member);
+    assert_eq!(joined.group_id, group_id);
+
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert!(group.is_member(member));
+
+    let (_, profile) = profiles().get(member).await.unwrap();
+    assert!(profile.is_group_member(group_id));
+}
+
+#[tokio::test]
+async fn test_join_group_twice() {
+    let owner = principal(1);
+    let member = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+    seed_member(group_id, member).await;
+
+    set_caller(member);
+    assert!(GroupCalls::join_group(group_id, None).await.is_err());
+}
+
+#[tokio::test]
+async fn test_join_invite_only_group() {
+    let owner = principal(1);
+    let member = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::InviteOnly).await;
+    seed_profile(member).await;
+
+    set_caller(member);
+    assert!(GroupCalls::join_group(group_id, None).await.is_err());
+
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert!(!group.is_member(member));
+}
+
+#[tokio::test]
+async fn test_join_unknown_group() {
+    let member = principal(2);
+    seed_profile(member).await;
+
+    set_caller(member);
+    assert!(GroupCalls::join_group(42, None).await.is_err());
+}
+
+#[tokio::test]
+async fn test_invite_and_accept() {
+    let owner = principal(1);
+    let invitee = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::Private).await;
+    seed_profile(invitee).await;
+
+    set_caller(owner);
+    GroupCalls::invite_to_group(invitee, group_id)
+        .await
+        .expect("Failed to invite member");
+
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert!(group.is_invited(invitee));
+    assert!(GroupCalls::has_pending_invite(group, invitee));
+
+    set_caller(invitee);
+    GroupCalls::accept_or_decline_owner_request_group_invite(group_id, true)
+        .await
+        .expect("Failed to accept invite");
+
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert!(group.is_member(invitee));
+    assert!(!group.is_invited(invitee));
+
+    let (_, profile) = profiles().get(invitee).await.unwrap();
+    assert!(profile.is_group_member(group_id));
+}
+
+#[tokio::test]
+async fn test_invite_and_decline() {
+    let owner = principal(1);
+    let invitee = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::Private).await;
+    seed_profile(invitee).await;
+
+    set_caller(owner);
+    GroupCalls::invite_to_group(invitee, group_id)
+        .await
+        .expect("Failed to invite member");
+
+    set_caller(invitee);
+    GroupCalls::accept_or_decline_owner_request_group_invite(group_id, false)
+        .await
+        .expect("Failed to decline invite");
+
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert!(!group.is_member(invitee));
+    assert!(!group.is_invited(invitee));
+
+    let (_, profile) = profiles().get(invitee).await.unwrap();
+    assert!(!profile.is_group_member(group_id));
+}
+
+#[tokio::test]
+async fn test_invite_existing_member() {
+    let owner = principal(1);
+    let member = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::Private).await;
+    seed_member(group_id, member).await;
+
+    set_caller(owner);
+    assert!(GroupCalls::invite_to_group(member, group_id).await.is_err());
+}
+
+#[tokio::test]
+async fn test_invite_twice() {
+    let owner = principal(1);
+    let invitee = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::Private).await;
+    seed_profile(invitee).await;
+
+    set_caller(owner);
+    GroupCalls::invite_to_group(invitee, group_id)
+        .await
+        .expect("Failed to invite member");
+
+    assert!(GroupCalls::invite_to_group(invitee, group_id)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_accept_without_invite() {
+    let owner = principal(1);
+    let member = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::Private).await;
+    seed_profile(member).await;
+
+    set_caller(member);
+    assert!(
+        GroupCalls::accept_or_decline_owner_request_group_invite(group_id, true)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_add_role_to_member() {
+    let owner = principal(1);
+    let member = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+    seed_member(group_id, member).await;
+
+    set_caller(owner);
+    GroupCalls::add_group_role_to_member("admin".to_string(), member, group_id)
+        .await
+        .expect("Failed to add role");
+
+    let roles = GroupCalls::get_group_member(member, group_id)
+        .await
+        .unwrap()
+        .roles;
+    assert!(roles.contains(&"admin".to_string()));
+}
+
+#[tokio::test]
+async fn test_remove_role_from_member() {
+    let owner = principal(1);
+    let member = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+    seed_member(group_id, member).await;
+
+    set_caller(owner);
+    GroupCalls::add_group_role_to_member("admin".to_string(), member, group_id)
+        .await
+        .expect("Failed to add role");
+    GroupCalls::remove_group_role_from_member("admin".to_string(), member, group_id)
+        .await
+        .expect("Failed to remove role");
+
+    let roles = GroupCalls::get_group_member(member, group_id)
+        .await
+        .unwrap()
+        .roles;
+    assert!(!roles.contains(&"admin".to_string()));
+}
+
+#[tokio::test]
+async fn test_add_unknown_role() {
+    let owner = principal(1);
+    let member = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+    seed_member(group_id, member).await;
+
+    set_caller(owner);
+    assert!(
+        GroupCalls::add_group_role_to_member("unknown".to_string(), member, group_id)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_add_role_to_non_member() {
+    let owner = principal(1);
+    let outsider = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+    seed_profile(outsider).await;
+
+    set_caller(owner);
+    assert!(
+        GroupCalls::add_group_role_to_member("admin".to_string(), outsider, group_id)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_transfer_and_accept_ownership() {
+    let owner = principal(1);
+    let member = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+    seed_member(group_id, member).await;
+
+    set_caller(owner);
+    GroupCalls::transfer_group_ownership(group_id, member)
+        .await
+        .expect("Failed to transfer ownership");
+
+    // the proposed owner is notified
+    let (_, profile) = profiles().get(member).await.unwrap();
+    let notification_id = profile.references.notifications.ids().into_iter().max();
+    let (_, notification) = notifications()
+        .get(notification_id.expect("No notification"))
+        .await
+        .unwrap();
+    assert_eq!(
+        notification
+            .metadata
+            .as_deref()
+            .and_then(GroupNotice::from_metadata),
+        Some(GroupNotice::OwnershipTransferProposed { from: owner })
+    );
+
+    // the ownership only changes after the new owner accepts
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert_eq!(group.owner, owner);
+
+    set_caller(member);
+    let group = GroupCalls::accept_group_ownership_transfer(group_id)
+        .await
+        .expect("Failed to accept ownership");
+    assert_eq!(group.owner, member);
+
+    let roles = GroupCalls::get_group_member(member, group_id)
+        .await
+        .unwrap()
+        .roles;
+    assert_eq!(roles, vec!["owner".to_string()]);
+
+    let roles = GroupCalls::get_group_member(owner, group_id)
+        .await
+        .unwrap()
+        .roles;
+    assert_eq!(roles, vec!["admin".to_string()]);
+
+    assert!(GroupCalls::get_group_ownership_transfer(group_id).is_err());
+}
+
+#[tokio::test]
+async fn test_transfer_ownership_to_non_member() {
+    let owner = principal(1);
+    let outsider = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+    seed_profile(outsider).await;
+
+    set_caller(owner);
+    assert!(GroupCalls::transfer_group_ownership(group_id, outsider)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_transfer_ownership_as_non_owner() {
+    let owner = principal(1);
+    let member = principal(2);
+    let other = principal(3);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+    seed_member(group_id, member).await;
+    seed_member(group_id, other).await;
+
+    set_caller(member);
+    assert!(GroupCalls::transfer_group_ownership(group_id, other)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_accept_ownership_as_other_member() {
+    let owner = principal(1);
+    let member = principal(2);
+    let other = principal(3);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+    seed_member(group_id, member).await;
+    seed_member(group_id, other).await;
+
+    set_caller(owner);
+    GroupCalls::transfer_group_ownership(group_id, member)
+        .await
+        .expect("Failed to transfer ownership");
+
+    set_caller(other);
+    assert!(GroupCalls::accept_group_ownership_transfer(group_id)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_decline_ownership_transfer() {
+    let owner = principal(1);
+    let member = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+    seed_member(group_id, member).await;
+
+    set_caller(owner);
+    GroupCalls::transfer_group_ownership(group_id, member)
+        .await
+        .expect("Failed to transfer ownership");
+
+    set_caller(member);
+    GroupCalls::decline_group_ownership_transfer(group_id).expect("Failed to decline");
+
+    assert!(GroupCalls::accept_group_ownership_transfer(group_id)
+        .await
+        .is_err());
+
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert_eq!(group.owner, owner);
+}
+
+#[tokio::test]
+async fn test_delete_and_restore_group() {
+    let owner = principal(1);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+
+    set_caller(owner);
+    GroupCalls::delete_group(group_id)
+        .await
+        .expect("Failed to delete group");
+
+    assert_eq!(GroupCalls::get_groups_count(None).await.unwrap().total, 0);
+    assert_eq!(GroupCalls::get_archived_groups_for(owner).len(), 1);
+
+    GroupCalls::restore_group(group_id)
+        .await
+        .expect("Failed to restore group");
+
+    assert_eq!(GroupCalls::get_groups_count(None).await.unwrap().total, 1);
+    assert!(GroupCalls::get_archived_groups_for(owner).is_empty());
+}
+
+#[tokio::test]
+async fn test_archived_groups_are_left_out_of_the_page() {
+    let owner = principal(1);
+
+    let archived = seed_group(owner, PrivacyType::Public).await;
+    seed_group(owner, PrivacyType::Public).await;
+    seed_group(owner, PrivacyType::Public).await;
+
+    set_caller(owner);
+    GroupCalls::delete_group(archived)
+        .await
+        .expect("Failed to delete group");
+
+    let resp = GroupCalls::get_groups(2, 1, vec![], GroupSort::default())
+        .await
+        .expect("Failed to get groups");
+    assert_eq!(resp.total, 2);
+    assert_eq!(resp.data.len(), 1);
+    assert!(resp.data.iter().all(|group| group.id != archived));
+}
+
+#[tokio::test]
+async fn test_restore_group_as_non_owner() {
+    let owner = principal(1);
+    let member = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+    seed_member(group_id, member).await;
+
+    set_caller(owner);
+    GroupCalls::delete_group(group_id)
+        .await
+        .expect("Failed to delete group");
+
+    set_caller(member);
+    assert!(GroupCalls::restore_group(group_id).await.is_err());
+}
+
+#[tokio::test]
+async fn test_archived_group_is_read_only() {
+    let owner = principal(1);
+    let member = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+    seed_profile(member).await;
+
+    set_caller(owner);
+    GroupCalls::delete_group(group_id)
+        .await
+        .expect("Failed to delete group");
+
+    assert!(GroupCalls::delete_group(group_id).await.is_err());
+    assert!(GroupCalls::invite_to_group(member, group_id).await.is_err());
+
+    set_caller(member);
+    assert!(GroupCalls::join_group(group_id, None).await.is_err());
+}
+
+#[tokio::test]
+async fn test_purge_group_after_restore_window() {
+    let owner = principal(1);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+
+    set_caller(owner);
+    GroupCalls::delete_group(group_id)
+        .await
+        .expect("Failed to delete group");
+
+    // a purge within the restore window leaves the group alone
+    let summary = GroupCalls::purge_group(group_id)
+        .await
+        .expect("Failed to purge group");
+    assert!(summary.is_none());
+    assert!(groups().get(group_id).await.is_ok());
+
+    let restore_window = ConfigCalls::get_platform_config().group_restore_window();
+    set_time(restore_window * 1_000_000_000);
+
+    assert!(GroupCalls::restore_group(group_id).await.is_err());
+
+    let summary = GroupCalls::purge_group(group_id)
+        .await
+        .expect("Failed to purge group")
+        .expect("Group is not purged");
+    assert_eq!(summary.profiles, 1);
+
+    assert!(groups().get(group_id).await.is_err());
+    assert!(GroupCalls::get_archived_groups_for(owner)[0]
+        .purged
+        .is_some());
+
+    let (_, profile) = profiles().get(owner).await.unwrap();
+    assert!(!profile.is_group_member(group_id));
+}
+
+#[tokio::test]
+async fn test_purge_group_timer() {
+    let owner = principal(1);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+
+    set_caller(owner);
+    GroupCalls::delete_group(group_id)
+        .await
+        .expect("Failed to delete group");
+    assert_eq!(pending_timers(), 1);
+
+    // the timer does not fire within the restore window
+    run_due_timers().await;
+    assert_eq!(pending_timers(), 1);
+    assert!(groups().get(group_id).await.is_ok());
+
+    let restore_window = ConfigCalls::get_platform_config().group_restore_window();
+    set_time(restore_window * 1_000_000_000);
+
+    run_due_timers().await;
+    assert_eq!(pending_timers(), 0);
+    assert!(groups().get(group_id).await.is_err());
+}
+
+#[tokio::test]
+async fn test_purge_group_resumes_interrupted_saga() {
+    let owner = principal(1);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+
+    set_caller(owner);
+    GroupCalls::delete_group(group_id)
+        .await
+        .expect("Failed to delete group");
+
+    // a purge saga that was running when the canister was upgraded, it is not stuck yet
+    let restore_window = ConfigCalls::get_platform_config().group_restore_window();
+    set_time(restore_window * 1_000_000_000);
+    sagas().insert_next(Saga::new(
+        SagaKind::DeleteGroup,
+        owner,
+        vec![],
+        vec![
+            SagaStep::RemoveGroupFromProfiles {
+                group_id,
+                principals: vec![owner],
+            },
+            SagaStep::RemoveGroup(group_id),
+        ],
+    ));
+
+    let summary = GroupCalls::purge_group(group_id)
+        .await
+        .expect("Failed to purge group")
+        .expect("Group is not purged");
+    assert_eq!(summary.profiles, 1);
+    assert_eq!(sagas().size(), 0);
+    assert!(groups().get(group_id).await.is_err());
+
+    // purged groups are not scheduled again
+    run_due_timers().await;
+    GroupCalls::schedule_archived_group_purges();
+    assert_eq!(pending_timers(), 0);
+}
+
+#[tokio::test]
+async fn test_purge_group_removes_invite_notifications() {
+    let owner = principal(1);
+    let invitee = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::Private).await;
+    seed_profile(invitee).await;
+
+    set_caller(owner);
+    GroupCalls::invite_to_group(invitee, group_id)
+        .await
+        .expect("Failed to invite member");
+
+    let (_, group) = groups().get(group_id).await.unwrap();
+    let notification_id = group.members.invites[&invitee]
+        .notification_id
+        .expect("Invite without notification");
+
+    GroupCalls::delete_group(group_id)
+        .await
+        .expect("Failed to delete group");
+
+    let restore_window = ConfigCalls::get_platform_config().group_restore_window();
+    set_time(restore_window * 1_000_000_000);
+
+    let summary = GroupCalls::purge_group(group_id)
+        .await
+        .expect("Failed to purge group")
+        .expect("Group is not purged");
+
+    assert_eq!(summary.invite_notifications, 1);
+    assert_eq!(summary.profiles, 2);
+    assert!(notifications().get(notification_id).await.is_err());
+
+    let (_, profile) = profiles().get(invitee).await.unwrap();
+    assert!(profile
+        .references
+        .notifications
+        .get(&notification_id)
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_invite_many_to_group() {
+    let owner = principal(1);
+    let member = principal(2);
+    let invitee = principal(3);
+
+    let group_id = seed_group(owner, PrivacyType::Private).await;
+    seed_member(group_id, member).await;
+    seed_profile(invitee).await;
+
+    set_caller(owner);
+    let results = GroupCalls::invite_many_to_group(vec![member, invitee, invitee], group_id)
+        .await
+        .expect("Failed to invite members");
+
+    assert_eq!(results.len(), 2);
+    assert!(results[0].1.is_err());
+    assert!(results[1].1.is_ok());
+
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert!(group.is_invited(invitee));
+    assert!(GroupCalls::has_pending_invite(group, invitee));
+
+    set_caller(invitee);
+    GroupCalls::accept_or_decline_owner_request_group_invite(group_id, true)
+        .await
+        .expect("Failed to accept invite");
+}
+
+#[tokio::test]
+async fn test_invite_many_without_profile() {
+    let owner = principal(1);
+    let invitee = principal(2);
+    let unknown = principal(3);
+
+    let group_id = seed_group(owner, PrivacyType::Private).await;
+    seed_profile(invitee).await;
+
+    set_caller(owner);
+    let results = GroupCalls::invite_many_to_group(vec![invitee, unknown], group_id)
+        .await
+        .expect("Failed to invite members");
+
+    assert!(results[0].1.is_ok());
+    assert!(results[1].1.is_err());
+
+    // only the invitee with a profile has a stored notification
+    assert_eq!(notifications().get_all().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_remove_many_members_from_group() {
+    let owner = principal(1);
+    let first = principal(2);
+    let second = principal(3);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+    seed_member(group_id, first).await;
+    seed_member(group_id, second).await;
+
+    set_caller(owner);
+    take_ws_messages();
+    let results = GroupCalls::remove_many_members_from_group(vec![owner, first, second], group_id)
+        .await
+        .expect("Failed to remove members");
+
+    // the owner gets a single notification for both removals
+    let sent_to_owner = take_ws_messages()
+        .into_iter()
+        .filter(|(receiver, _)| receiver == &owner)
+        .count();
+    assert_eq!(sent_to_owner, 1);
+
+    assert!(results[0].1.is_err());
+    assert!(results[1].1.is_ok());
+    assert!(results[2].1.is_ok());
+
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert!(group.is_member(owner));
+    assert!(!group.is_member(first));
+    assert!(!group.is_member(second));
+
+    let (_, profile) = profiles().get(first).await.unwrap();
+    assert!(!profile.is_group_member(group_id));
+}
+
+#[tokio::test]
+async fn test_add_role_to_many_members() {
+    let owner = principal(1);
+    let member = principal(2);
+    let outsider = principal(3);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+    seed_member(group_id, member).await;
+    seed_profile(outsider).await;
+
+    set_caller(owner);
+    let results = GroupCalls::add_group_role_to_many_members(
+        "admin".to_string(),
+        vec![member, outsider],
+        group_id,
+    )
+    .await
+    .expect("Failed to add roles");
+
+    assert!(results[0].1.is_ok());
+    assert!(results[1].1.is_err());
+
+    let roles = GroupCalls::get_group_member(member, group_id)
+        .await
+        .unwrap()
+        .roles;
+    assert!(roles.contains(&"admin".to_string()));
+}
+
+#[tokio::test]
+async fn test_member_batch_limit() {
+    let owner = principal(1);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+
+    set_caller(owner);
+    let principals = (0..=MEMBER_BATCH_LIMIT)
+        .map(|id| Principal::from_slice(&(id as u64).to_be_bytes()))
+        .collect();
+
+    assert!(
+        GroupCalls::remove_many_members_from_group(principals, group_id)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_filter_and_sort_group_members() {
+    let owner = principal(1);
+    let first = principal(2);
+    let second = principal(3);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+
+    for (member, display_name) in [(first, "Zoe"), (second, "adam")] {
+        seed_member(group_id, member).await;
+
+        let (_, mut profile) = profiles().get(member).await.unwrap();
+        profile.metadata.display_name = display_name.to_string();
+        profiles().update(member, profile).await.unwrap();
+    }
+
+    let (_, mut group) = groups().get(group_id).await.unwrap();
+    for (member, joined_at) in [(owner, 100), (first, 20), (second, 10)] {
+        group.members.members.get_mut(&member).unwrap().created_at = joined_at;
+    }
+    groups().update(group_id, group).await.unwrap();
+
+    let principals = |members: Vec<JoinedMemberResponse>| {
+        members.iter().map(|m| m.principal).collect::<Vec<_>>()
+    };
+
+    let members = GroupCalls::get_group_members_filtered(
+        group_id,
+        vec![GroupMemberFilter::Role("owner".to_string())],
+        GroupMemberSort::default(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(principals(members), vec![owner]);
+
+    let members = GroupCalls::get_group_members_filtered(
+        group_id,
+        vec![GroupMemberFilter::JoinedOn(DateRange::new(5, 30))],
+        GroupMemberSort::JoinedOn(SortOrder::Desc),
+    )
+    .await
+    .unwrap();
+    assert_eq!(principals(members), vec![first, second]);
+
+    let members = GroupCalls::get_group_members_filtered(
+        group_id,
+        vec![GroupMemberFilter::Name("A".to_string())],
+        GroupMemberSort::Name(SortOrder::Asc),
+    )
+    .await
+    .unwrap();
+    assert_eq!(principals(members), vec![second]);
+
+    let members = GroupCalls::get_group_members_filtered(
+        group_id,
+        vec![GroupMemberFilter::JoinedOn(DateRange::new(5, 30))],
+        GroupMemberSort::Name(SortOrder::Asc),
+    )
+    .await
+    .unwrap();
+    assert_eq!(principals(members), vec![second, first]);
+}
+
+#[tokio::test]
+async fn test_join_private_group_creates_request() {
+    let owner = principal(1);
+    let member = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::Private).await;
+    seed_profile(member).await;
+
+    set_caller(member);
+    let joined = GroupCalls::join_group(group_id, None)
+        .await
+        .expect("Failed to request to join");
+    assert!(matches!(joined, JoinGroupResult::Requested));
+
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert!(!group.is_member(member));
+    assert!(group.is_invited(member));
+
+    // a second request is refused while the first one is pending
+    assert!(GroupCalls::join_group(group_id, None).await.is_err());
+
+    set_caller(owner);
+    GroupCalls::accept_or_decline_user_request_group_invite(member, group_id, true)
+        .await
+        .expect("Failed to accept join request");
+
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert!(group.is_member(member));
+    assert!(join_requests().get_all().is_empty());
+}
+
+#[tokio::test]
+async fn test_expire_join_request() {
+    let owner = principal(1);
+    let member = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::Private).await;
+    seed_profile(member).await;
+
+    set_caller(member);
+    GroupCalls::join_group(group_id, None)
+        .await
+        .expect("Failed to request to join");
+
+    let (notification_id, _) = join_requests().get_all()[0].clone();
+
+    // nothing happens before the request expires
+    GroupCalls::expire_join_request(notification_id)
+        .await
+        .expect("Failed to expire join request");
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert!(group.is_invited(member));
+
+    let expiry = ConfigCalls::get_platform_config().join_request_expiry();
+    set_time(expiry * 1_000_000_000);
+
+    GroupCalls::expire_join_request(notification_id)
+        .await
+        .expect("Failed to expire join request");
+
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert!(!group.is_invited(member));
+    assert!(!group.is_member(member));
+    assert!(join_requests().get_all().is_empty());
+    assert!(notifications().get(notification_id).await.is_err());
+
+    let (_, profile) = profiles().get(member).await.unwrap();
+    assert!(!profile.is_group_member(group_id));
+
+    let (_, owner_profile) = profiles().get(owner).await.unwrap();
+    assert!(!owner_profile
+        .references
+        .notifications
+        .ids()
+        .contains(&notification_id));
+}
+
+#[tokio::test]
+async fn test_backfill_join_requests() {
+    let owner = principal(1);
+    let member = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::Private).await;
+    seed_profile(member).await;
+
+    set_caller(member);
+    GroupCalls::join_group(group_id, None)
+        .await
+        .expect("Failed to request to join");
+
+    // a request made before join requests expired has no record
+    let (notification_id, _) = join_requests().get_all()[0].clone();
+    join_requests().remove(notification_id);
+
+    GroupCalls::backfill_join_requests()
+        .await
+        .expect("Failed to backfill join requests");
+
+    let join_request = join_requests()
+        .find(notification_id)
+        .expect("Join request was not backfilled");
+    assert_eq!(join_request.group_id, group_id);
+    assert_eq!(join_request.principal, member);
+
+    // the backfill runs once, a second run reads no groups
+    join_requests().remove(notification_id);
+    GroupCalls::backfill_join_requests()
+        .await
+        .expect("Failed to backfill join requests");
+    assert!(join_requests().find(notification_id).is_none());
+
+    // and is not scheduled again after an upgrade
+    let pending = pending_timers();
+    GroupCalls::schedule_join_request_expiries();
+    assert_eq!(pending_timers(), pending);
+}
+
+#[tokio::test]
+async fn test_join_request_expiry_timer() {
+    let owner = principal(1);
+    let member = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::Private).await;
+    seed_profile(member).await;
+
+    set_caller(member);
+    GroupCalls::join_group(group_id, None)
+        .await
+        .expect("Failed to request to join");
+    assert_eq!(pending_timers(), 1);
+
+    run_due_timers().await;
+    assert_eq!(join_requests().get_all().len(), 1);
+
+    let expiry = ConfigCalls::get_platform_config().join_request_expiry();
+    set_time(expiry * 1_000_000_000);
+
+    run_due_timers().await;
+    assert!(join_requests().get_all().is_empty());
+
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert!(!group.is_invited(member));
+}
+
+#[tokio::test]
+async fn test_gated_revalidation_timer() {
+    GroupCalls::start_gated_revalidation_timer();
+    assert_eq!(intervals(), vec![std::time::Duration::from_secs(60 * 60)]);
+
+    // without gated groups a run leaves the interval in place
+    run_intervals().await;
+    assert_eq!(intervals().len(), 1);
+}
+
+#[tokio::test]
+async fn test_join_invite_only_group_with_code() {
+    let owner = principal(1);
+    let member = principal(2);
+    let other = principal(3);
+
+    let group_id = seed_group(owner, PrivacyType::InviteOnly).await;
+    seed_profile(member).await;
+    seed_profile(other).await;
+
+    set_caller(owner);
+    let invite_code = GroupCalls::create_invite_code(
+        group_id,
+        PostInviteCode {
+            max_uses: Some(1),
+            role: Some("moderator".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to create invite code");
+
+    set_caller(member);
+    let JoinGroupResult::Joined(joined) =
+        GroupCalls::join_group_with_code(invite_code.code.clone())
+            .await
+            .expect("Failed to join group with code")
+    else {
+        panic!("Expected to join the group");
+    };
+    assert_eq!(joined.roles, vec!["moderator".to_string()]);
+
+    let (_, profile) = profiles().get(member).await.unwrap();
+    assert!(profile.is_group_member(group_id));
+    assert_eq!(
+        invite_codes().get(invite_code.code.clone()).unwrap().uses,
+        1
+    );
+
+    // the code is used up
+    set_caller(other);
+    assert!(GroupCalls::join_group_with_code(invite_code.code)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_join_full_group_with_code() {
+    let owner = principal(1);
+    let member = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::InviteOnly).await;
+    seed_profile(member).await;
+
+    set_caller(owner);
+    GroupCalls::set_group_member_cap(group_id, Some(1))
+        .await
+        .expect("Failed to set member cap");
+    let invite_code = GroupCalls::create_invite_code(group_id, PostInviteCode::default())
+        .await
+        .expect("Failed to create invite code");
+
+    set_caller(member);
+    let joined = GroupCalls::join_group_with_code(invite_code.code)
+        .await
+        .expect("Failed to join the waitlist");
+    assert!(matches!(joined, JoinGroupResult::Waitlisted));
+
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert!(!group.is_member(member));
+    assert_eq!(GroupCalls::get_group_waitlist(group_id).len(), 1);
+}
+
+#[tokio::test]
+async fn test_failed_join_with_code_releases_the_use() {
+    let owner = principal(1);
+    let member = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::InviteOnly).await;
+    seed_profile(member).await;
+
+    set_caller(owner);
+    let invite_code = GroupCalls::create_invite_code(
+        group_id,
+        PostInviteCode {
+            max_uses: Some(2),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to create invite code");
+
+    set_caller(member);
+    GroupCalls::join_group_with_code(invite_code.code.clone())
+        .await
+        .expect("Failed to join group with code");
+
+    // the reserved use is given back when the join fails
+    assert!(GroupCalls::join_group_with_code(invite_code.code.clone())
+        .await
+        .is_err());
+    assert_eq!(
+        invite_codes().get(invite_code.code.clone()).unwrap().uses,
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_expired_and_revoked_invite_codes() {
+    let owner = principal(1);
+    let member = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::InviteOnly).await;
+    seed_profile(member).await;
+
+    set_time(1_000);
+    set_caller(owner);
+    let expiring = GroupCalls::create_invite_code(
+        group_id,
+        PostInviteCode {
+            expires_at: Some(2_000),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let revoked = GroupCalls::create_invite_code(group_id, PostInviteCode::default())
+        .await
+        .unwrap();
+
+    assert_eq!(GroupCalls::get_invite_codes(group_id).len(), 2);
+    assert!(GroupCalls::revoke_invite_code(group_id + 1, revoked.code.clone()).is_err());
+    GroupCalls::revoke_invite_code(group_id, revoked.code.clone()).unwrap();
+    assert_eq!(GroupCalls::get_invite_codes(group_id).len(), 1);
+
+    set_time(2_000);
+    set_caller(member);
+    assert!(GroupCalls::join_group_with_code(expiring.code)
+        .await
+        .is_err());
+    assert!(GroupCalls::join_group_with_code(revoked.code)
+        .await
+        .is_err());
+
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert!(!group.is_member(member));
+}
+
+#[tokio::test]
+async fn test_invite_code_can_not_grant_owner() {
+    let owner = principal(1);
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+
+    set_caller(owner);
+    for role in ["owner", "unknown"] {
+        let post_invite_code = PostInviteCode {
+            role: Some(role.to_string()),
+            ..Default::default()
+        };
+        assert!(GroupCalls::create_invite_code(group_id, post_invite_code)
+            .await
+            .is_err());
+    }
+}
+
+#[tokio::test]
+async fn test_members_can_only_manage_lower_ranks() {
+    let owner = principal(1);
+    let admin = principal(2);
+    let moderator = principal(3);
+    let member = principal(4);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+    for principal in [admin, moderator, member] {
+        seed_member(group_id, principal).await;
+    }
+
+    set_caller(owner);
+    GroupCalls::add_group_role_to_member("admin".to_string(), admin, group_id)
+        .await
+        .unwrap();
+    GroupCalls::add_group_role_to_member("moderator".to_string(), moderator, group_id)
+        .await
+        .unwrap();
+
+    set_caller(moderator);
+    assert!(GroupCalls::remove_member_from_group(admin, group_id)
+        .await
+        .is_err());
+    assert!(GroupCalls::remove_member_from_group(owner, group_id)
+        .await
+        .is_err());
+    assert!(
+        GroupCalls::add_group_role_to_member("admin".to_string(), member, group_id)
+            .await
+            .is_err()
+    );
+    assert!(
+        GroupCalls::edit_role_permissions(group_id, "admin".to_string(), vec![])
+            .await
+            .is_err()
+    );
+
+    let results = GroupCalls::remove_many_members_from_group(vec![admin, member], group_id)
+        .await
+        .unwrap();
+    assert!(results[0].1.is_err());
+    assert!(results[1].1.is_ok());
+
+    set_caller(admin);
+    GroupCalls::remove_group_role_from_member("moderator".to_string(), moderator, group_id)
+        .await
+        .expect("Failed to demote the moderator");
+    assert!(
+        GroupCalls::remove_group_role_from_member("owner".to_string(), owner, group_id)
+            .await
+            .is_err()
+    );
+
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert!(group.is_member(admin));
+    assert!(!group.is_member(member));
+}
+
+#[tokio::test]
+async fn test_custom_role_ranks() {
+    let owner = principal(1);
+    let lead = principal(2);
+    let moderator = principal(3);
+    let helper = principal(4);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+    for principal in [lead, moderator, helper] {
+        seed_member(group_id, principal).await;
+    }
+
+    set_caller(owner);
+    for rank in [0, 400] {
+        assert!(GroupCalls::add_role_to_group(
+            group_id,
+            "invalid".to_string(),
+            "#000000".to_string(),
+            0,
+            Some(rank)
+        )
+        .await
+        .is_err());
+    }
+
+    // ranked between the moderator and admin roles
+    GroupCalls::add_role_to_group(
+        group_id,
+        "lead".to_string(),
+        "#000000".to_string(),
+        0,
+        Some(250),
+    )
+    .await
+    .expect("Failed to add the lead role");
+    GroupCalls::add_role_to_group(
+        group_id,
+        "helper".to_string(),
+        "#000000".to_string(),
+        1,
+        None,
+    )
+    .await
+    .expect("Failed to add the helper role");
+
+    for (role, principal) in [("lead", lead), ("moderator", moderator), ("helper", helper)] {
+        GroupCalls::add_group_role_to_member(role.to_string(), principal, group_id)
+            .await
+            .unwrap();
+    }
+
+    set_caller(moderator);
+    assert!(
+        GroupCalls::add_group_role_to_member("lead".to_string(), helper, group_id)
+            .await
+            .is_err()
+    );
+    assert!(GroupCalls::add_role_to_group(
+        group_id,
+        "senior".to_string(),
+        "#000000".to_string(),
+        2,
+        Some(250)
+    )
+    .await
+    .is_err());
+
+    set_caller(lead);
+    GroupCalls::remove_group_role_from_member("moderator".to_string(), moderator, group_id)
+        .await
+        .expect("Failed to demote the moderator");
+
+    let higher_role_members = GroupCalls::get_higher_role_members(group_id).await;
+    assert!(higher_role_members.contains(&owner));
+    assert!(higher_role_members.contains(&lead));
+    assert!(!higher_role_members.contains(&helper));
+}
+
+#[tokio::test]
+async fn test_owner_role_can_not_be_removed() {
+    let owner = principal(1);
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+
+    set_caller(owner);
+    assert!(GroupCalls::remove_group_role(group_id, "owner".to_string())
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_member_cap_waitlist() {
+    let owner = principal(1);
+    let member = principal(2);
+    let first = principal(3);
+    let second = principal(4);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+    for principal in [member, first, second] {
+        seed_profile(principal).await;
+    }
+
+    set_caller(owner);
+    assert!(GroupCalls::set_group_member_cap(group_id, Some(0))
+        .await
+        .is_err());
+    GroupCalls::set_group_member_cap(group_id, Some(2))
+        .await
+        .expect("Failed to set member cap");
+
+    set_caller(member);
+    GroupCalls::join_group(group_id, None)
+        .await
+        .expect("Failed to join group");
+
+    for principal in [second, first] {
+        set_caller(principal);
+        let joined = GroupCalls::join_group(group_id, None)
+            .await
+            .expect("Failed to join the waitlist");
+        assert!(matches!(joined, JoinGroupResult::Waitlisted));
+    }
+
+    set_caller(first);
+    assert!(GroupCalls::join_group(group_id, None).await.is_err());
+
+    // leaving the waitlist gives up the place
+    set_caller(second);
+    GroupCalls::leave_group_waitlist(group_id).expect("Failed to leave waitlist");
+    GroupCalls::join_group(group_id, None)
+        .await
+        .expect("Failed to join the waitlist");
+
+    let waitlist = GroupCalls::get_group_waitlist(group_id);
+    let waitlisted = waitlist
+        .iter()
+        .map(|entry| entry.principal)
+        .collect::<Vec<_>>();
+    assert_eq!(waitlisted, vec![first, second]);
+
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert_eq!(group.members.members.len(), 2);
+    assert!(!group.is_member(first));
+
+    let (_, profile) = profiles().get(first).await.unwrap();
+    assert!(!profile.is_group_member(group_id));
+
+    set_caller(member);
+    GroupCalls::leave_group(group_id)
+        .await
+        .expect("Failed to leave group");
+
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert!(group.is_member(first));
+    assert!(!group.is_member(second));
+
+    let (_, profile) = profiles().get(first).await.unwrap();
+    assert!(profile.is_group_member(group_id));
+
+    set_caller(owner);
+    GroupCalls::remove_member_from_group(first, group_id)
+        .await
+        .expect("Failed to remove member");
+
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert!(group.is_member(second));
+    assert!(GroupCalls::get_group_waitlist(group_id).is_empty());
+}
+
+#[tokio::test]
+async fn test_member_cap_invite_acceptance() {
+    let owner = principal(1);
+    let invitee = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+    seed_profile(invitee).await;
+
+    set_caller(owner);
+    GroupCalls::set_group_member_cap(group_id, Some(1))
+        .await
+        .expect("Failed to set member cap");
+    GroupCalls::invite_to_group(invitee, group_id)
+        .await
+        .expect("Failed to invite");
+
+    set_caller(invitee);
+    GroupCalls::accept_or_decline_owner_request_group_invite(group_id, true)
+        .await
+        .expect("Failed to accept invite");
+
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert!(!group.is_member(invitee));
+    assert!(!group.is_invited(invitee));
+    assert_eq!(GroupCalls::get_group_waitlist(group_id).len(), 1);
+
+    // the invitee is told they are on the waitlist instead of in the group
+    let (_, profile) = profiles().get(invitee).await.unwrap();
+    let notification_id = profile.references.notifications.ids().into_iter().max();
+    let (_, notification) = notifications()
+        .get(notification_id.expect("No notification"))
+        .await
+        .unwrap();
+    assert_eq!(
+        notification
+            .metadata
+            .as_deref()
+            .and_then(GroupNotice::from_metadata),
+        Some(GroupNotice::Waitlisted)
+    );
+
+    // removing the cap admits the waitlist
+    set_caller(owner);
+    assert_eq!(
+        GroupCalls::set_group_member_cap(group_id, None)
+            .await
+            .unwrap(),
+        None
+    );
+
+    let (_, group) = groups().get(group_id).await.unwrap();
+    assert!(group.is_member(invitee));
+    assert!(GroupCalls::get_group_member_cap(group_id).is_none());
+    assert!(GroupCalls::get_group_waitlist(group_id).is_empty());
+}
+
+#[tokio::test]
+async fn test_purge_group_removes_pending_group_state() {
+    let owner = principal(1);
+    let member = principal(2);
+    let requester = principal(3);
+
+    let group_id = seed_group(owner, PrivacyType::Private).await;
+    seed_member(group_id, member).await;
+    seed_profile(requester).await;
+
+    set_caller(requester);
+    GroupCalls::join_group(group_id, None)
+        .await
+        .expect("Failed to request to join");
+
+    set_caller(owner);
+    GroupCalls::transfer_group_ownership(group_id, member)
+        .await
+        .expect("Failed to propose the transfer");
+
+    gated_grace_periods().insert(
+        GatedGracePeriod::key(group_id, member),
+        GatedGracePeriod {
+            group_id,
+            principal: member,
+            notification_id: None,
+            created_at: 0,
+            expires_at: 0,
+        },
+    );
+
+    GroupCalls::delete_group(group_id)
+        .await
+        .expect("Failed to delete group");
+
+    let restore_window = ConfigCalls::get_platform_config().group_restore_window();
+    set_time(restore_window * 1_000_000_000);
+
+    GroupCalls::purge_group(group_id)
+        .await
+        .expect("Failed to purge group")
+        .expect("Group is not purged");
+
+    assert!(ownership_transfers().find(group_id).is_none());
+    assert_eq!(join_requests().size(), 0);
+    assert_eq!(gated_grace_periods().size(), 0);
+}
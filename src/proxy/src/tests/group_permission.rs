@@ -0,0 +1,108 @@
+use catalyze_shared::{
+    permission::{PermissionActionType, PermissionType},
+    privacy::PrivacyType,
+};
+
+use super::utils::{principal, seed_group, seed_member};
+use crate::{
+    helpers::{
+        env::set_caller,
+        group_permission::{get_group_permission_guard, group_guard, GroupPermissionGuard},
+    },
+    GROUP_PERMISSION_GUARDS,
+};
+
+const DELETE_GROUP: GroupPermissionGuard = GroupPermissionGuard {
+    method: "delete_group",
+    permission: PermissionType::Group(None),
+    action: PermissionActionType::Delete,
+};
+
+#[test]
+fn test_group_permission_guards_are_unique() {
+    assert!(!GROUP_PERMISSION_GUARDS.is_empty());
+
+    for guard in GROUP_PERMISSION_GUARDS {
+        let count = GROUP_PERMISSION_GUARDS
+            .iter()
+            .filter(|other| other.method == guard.method)
+            .count();
+        assert_eq!(count, 1, "`{}` is declared more than once", guard.method);
+    }
+}
+
+#[test]
+fn test_group_permission_guards_list_every_endpoint() {
+    let sources = [
+        include_str!("../calls/event_calls.rs"),
+        include_str!("../calls/group_calls.rs"),
+        include_str!("../calls/report_calls.rs"),
+    ];
+
+    let mut count = 0;
+    for source in sources {
+        for declaration in source.split("#[group_permission(").skip(1) {
+            let method = declaration
+                .split("pub async fn ")
+                .nth(1)
+                .and_then(|rest| rest.split('(').next())
+                .expect("Endpoint not found");
+
+            assert!(
+                get_group_permission_guard(method).is_some(),
+                "`{}` is missing from `group_permission_guards!`",
+                method
+            );
+            count += 1;
+        }
+    }
+
+    assert_eq!(count, GROUP_PERMISSION_GUARDS.len());
+}
+
+#[test]
+fn test_group_permission_table() {
+    use PermissionActionType::*;
+    use PermissionType::*;
+
+    let expected = [
+        ("delete_group", Group(None), Delete),
+        ("edit_group", Group(None), Edit),
+        ("ban_group_member", Member(None), Edit),
+        ("remove_member_from_group", Member(None), Delete),
+        ("invite_to_group", Invite(None), Edit),
+        ("add_event", Event(None), Edit),
+        ("delete_event", Event(None), Delete),
+        ("get_reports", Group(None), Read),
+    ];
+
+    for (method, permission, action) in expected {
+        let guard = get_group_permission_guard(method).expect("Guard not found");
+        assert_eq!(
+            guard.permission.to_string(),
+            permission.to_string(),
+            "{method}"
+        );
+        assert_eq!(
+            std::mem::discriminant(&guard.action),
+            std::mem::discriminant(&action),
+            "{method}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_group_guard() {
+    let owner = principal(1);
+    let member = principal(2);
+
+    let group_id = seed_group(owner, PrivacyType::Public).await;
+    seed_member(group_id, member).await;
+
+    set_caller(member);
+    assert!(group_guard(&DELETE_GROUP, group_id).await.is_err());
+
+    set_caller(owner);
+    assert!(group_guard(&DELETE_GROUP, group_id).await.is_ok());
+    assert!(group_guard(&DELETE_GROUP, None).await.is_err());
+}
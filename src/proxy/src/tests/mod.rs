@@ -0,0 +1,7 @@
+mod admin;
+mod cache;
+mod config;
+mod gating;
+mod group;
+mod group_permission;
+mod utils;
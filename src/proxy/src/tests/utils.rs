@@ -0,0 +1,63 @@
+use candid::Principal;
+use catalyze_shared::{
+    group_with_members::GroupWithMembers, privacy::PrivacyType, profile_with_refs::ProfileWithRefs,
+    StorageClientInsertable, StorageClientInsertableByKey,
+};
+
+use crate::storage::{groups, profiles};
+
+pub fn principal(id: u8) -> Principal {
+    Principal::from_slice(&[id; 29])
+}
+
+pub async fn seed_profile(principal: Principal) {
+    profiles()
+        .insert(principal, ProfileWithRefs::default())
+        .await
+        .expect("Failed to seed profile");
+}
+
+/// Seeds a group owned by `owner` with the given privacy, the owner profile is seeded as well
+pub async fn seed_group(owner: Principal, privacy_type: PrivacyType) -> u64 {
+    seed_profile(owner).await;
+
+    let mut group = GroupWithMembers::default();
+    group.add_member(owner);
+    group.name = "test group".to_string();
+    group.owner = owner;
+    group.privacy.privacy_type = privacy_type;
+
+    if let Some(member) = group.members.members.get_mut(&owner) {
+        member.set_role("owner".to_string());
+    }
+
+    let (group_id, _) = groups().insert(group).await.expect("Failed to seed group");
+
+    let (_, mut profile) = profiles().get(owner).await.expect("Owner not found");
+    profile.add_group(group_id);
+    profiles()
+        .update(owner, profile)
+        .await
+        .expect("Failed to update owner");
+
+    group_id
+}
+
+/// Seeds a profile and adds it as a member of the group
+pub async fn seed_member(group_id: u64, member: Principal) {
+    seed_profile(member).await;
+
+    let (_, mut group) = groups().get(group_id).await.expect("Group not found");
+    group.add_member(member);
+    groups()
+        .update(group_id, group)
+        .await
+        .expect("Failed to add member");
+
+    let (_, mut profile) = profiles().get(member).await.expect("Member not found");
+    profile.add_group(group_id);
+    profiles()
+        .update(member, profile)
+        .await
+        .expect("Failed to update member");
+}
@@ -0,0 +1,202 @@
+//! Macros of the proxy canister.
+//!
+//! `#[group_permission]` declares the group permission an endpoint requires and checks it right
+//! after the `has_access` check of the endpoint, `group_permission_guards!` builds the
+//! `GROUP_PERMISSION_GUARDS` table from the list of endpoints it is given.
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, parse_quote,
+    punctuated::Punctuated,
+    visit_mut::{self, VisitMut},
+    Block, Expr, FnArg, Ident, ItemFn, Pat, Path, Stmt, Token,
+};
+
+struct GroupPermissionArgs {
+    permission: Ident,
+    action: Ident,
+    group_id: Option<Expr>,
+}
+
+impl Parse for GroupPermissionArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let permission = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let action = input.parse()?;
+
+        let mut group_id = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key: Ident = input.parse()?;
+            if key != "group_id" {
+                return Err(syn::Error::new(key.span(), "expected `group_id = <expr>`"));
+            }
+
+            input.parse::<Token![=]>()?;
+            group_id = Some(input.parse()?);
+        }
+
+        Ok(Self {
+            permission,
+            action,
+            group_id,
+        })
+    }
+}
+
+/// Checks the group permission of the endpoint right after its `has_access().await?` check,
+/// so the rejection is counted by `metered` and a blocked or banned caller is told so first.
+/// The group is read from the `group_id` argument, or from the expression given with `group_id = ..`
+/// that evaluates to a `u64` or an `Option<u64>`. The endpoint is added to `GROUP_PERMISSION_GUARDS`
+/// by listing it in `group_permission_guards!`.
+/// ```ignore
+/// #[group_permission(Group, Edit)]
+/// #[update(guard = "is_not_anonymous")]
+/// pub async fn edit_group(group_id: u64, update_group: UpdateGroup) -> CanisterResult<GroupResponse>
+/// ```
+#[proc_macro_attribute]
+pub fn group_permission(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let GroupPermissionArgs {
+        permission,
+        action,
+        group_id,
+    } = parse_macro_input!(attr as GroupPermissionArgs);
+    let mut endpoint = parse_macro_input!(item as ItemFn);
+
+    if endpoint.sig.asyncness.is_none() {
+        return syn::Error::new_spanned(
+            &endpoint.sig,
+            "group permission endpoints have to be async",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let group_id = match group_id {
+        Some(group_id) => quote!(#group_id),
+        None if has_group_id_argument(&endpoint) => quote!(group_id),
+        None => {
+            return syn::Error::new_spanned(
+                &endpoint.sig.ident,
+                "the endpoint has no `group_id` argument, pass it with `group_id = ..`",
+            )
+            .to_compile_error()
+            .into()
+        }
+    };
+
+    let guard_name = guard_ident(&endpoint.sig.ident);
+    let mut insert_guard = InsertGuard {
+        guard: parse_quote!(
+            crate::helpers::group_permission::group_guard(&#guard_name, #group_id).await?;
+        ),
+        is_inserted: false,
+    };
+    insert_guard.visit_block_mut(&mut endpoint.block);
+
+    if !insert_guard.is_inserted {
+        return syn::Error::new_spanned(
+            &endpoint.sig.ident,
+            "the endpoint has no `has_access().await?` to check the group permission after",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let guard = guard_tokens(&endpoint.sig.ident.to_string(), &permission, &action);
+    let visibility = &endpoint.vis;
+
+    quote!(
+        #[doc(hidden)]
+        #visibility const #guard_name: crate::helpers::group_permission::GroupPermissionGuard = #guard;
+
+        #endpoint
+    )
+    .into()
+}
+
+/// Builds `GROUP_PERMISSION_GUARDS` from the paths of the `#[group_permission]` endpoints
+/// ```ignore
+/// group_permission_guards![calls::group_calls::edit_group, calls::group_calls::delete_group];
+/// ```
+#[proc_macro]
+pub fn group_permission_guards(input: TokenStream) -> TokenStream {
+    let endpoints =
+        parse_macro_input!(input with Punctuated::<Path, Token![,]>::parse_terminated);
+
+    let guards = endpoints.into_iter().map(|mut endpoint| {
+        if let Some(segment) = endpoint.segments.last_mut() {
+            segment.ident = guard_ident(&segment.ident);
+        }
+        endpoint
+    });
+
+    quote!(
+        /// The group permission of every endpoint declared with `#[group_permission]`
+        pub const GROUP_PERMISSION_GUARDS: &[crate::helpers::group_permission::GroupPermissionGuard] =
+            &[#(#guards),*];
+    )
+    .into()
+}
+
+/// Adds the guard after the first `has_access().await?;` of the endpoint,
+/// which can be inside the `metered` block of the endpoint
+struct InsertGuard {
+    guard: Stmt,
+    is_inserted: bool,
+}
+
+impl VisitMut for InsertGuard {
+    fn visit_block_mut(&mut self, block: &mut Block) {
+        if self.is_inserted {
+            return;
+        }
+
+        if let Some(index) = block.stmts.iter().position(is_has_access) {
+            block.stmts.insert(index + 1, self.guard.clone());
+            self.is_inserted = true;
+            return;
+        }
+
+        visit_mut::visit_block_mut(self, block);
+    }
+}
+
+fn is_has_access(stmt: &Stmt) -> bool {
+    let Stmt::Expr(Expr::Try(try_expr), Some(_)) = stmt else {
+        return false;
+    };
+    let Expr::Await(await_expr) = &*try_expr.expr else {
+        return false;
+    };
+    let Expr::Call(call) = &*await_expr.base else {
+        return false;
+    };
+
+    matches!(&*call.func, Expr::Path(func) if func.path.is_ident("has_access"))
+}
+
+fn has_group_id_argument(endpoint: &ItemFn) -> bool {
+    endpoint.sig.inputs.iter().any(|input| match input {
+        FnArg::Typed(arg) => matches!(&*arg.pat, Pat::Ident(pat) if pat.ident == "group_id"),
+        FnArg::Receiver(_) => false,
+    })
+}
+
+/// The name of the constant that holds the permission of the endpoint
+fn guard_ident(endpoint: &Ident) -> Ident {
+    format_ident!(
+        "{}_GROUP_PERMISSION",
+        endpoint.to_string().to_uppercase(),
+        span = endpoint.span()
+    )
+}
+
+fn guard_tokens(method: &str, permission: &Ident, action: &Ident) -> TokenStream2 {
+    quote!(crate::helpers::group_permission::GroupPermissionGuard {
+        method: #method,
+        permission: catalyze_shared::permission::PermissionType::#permission(None),
+        action: catalyze_shared::permission::PermissionActionType::#action,
+    })
+}
@@ -0,0 +1,18 @@
+use catalyze_shared::boosted::{Boost, BoostedFilter, BoostedSort};
+
+stand_in_storage!(
+    u64,
+    Boost,
+    BoostedFilter,
+    BoostedSort,
+    |filter, _, boost| {
+        if let BoostedFilter::Subject(subject) = filter {
+            return &boost.subject == subject;
+        }
+        if let BoostedFilter::SubjectType(subject_type) = filter {
+            return &boost.subject.get_type() == subject_type;
+        }
+        true
+    }
+);
+stand_in_storage_insertable!(Boost);
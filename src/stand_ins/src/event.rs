@@ -0,0 +1,10 @@
+use catalyze_shared::event_with_attendees::{EventFilter, EventSort, EventWithAttendees};
+
+stand_in_storage!(
+    u64,
+    EventWithAttendees,
+    EventFilter,
+    EventSort,
+    |_, _, _| true
+);
+stand_in_storage_insertable!(EventWithAttendees);
@@ -0,0 +1,10 @@
+use catalyze_shared::friend_request::{FriendRequest, FriendRequestFilter, FriendRequestSort};
+
+stand_in_storage!(
+    u64,
+    FriendRequest,
+    FriendRequestFilter,
+    FriendRequestSort,
+    |_, _, _| true
+);
+stand_in_storage_insertable!(FriendRequest);
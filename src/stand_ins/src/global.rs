@@ -0,0 +1,46 @@
+//! The global canister only receives notifications from the proxy, the history point is
+//! counted so `next_history_point` keeps increasing
+use std::cell::Cell;
+
+use candid::Principal;
+use catalyze_shared::reward::RewardableActivityResponse;
+use ic_cdk::{query, update};
+
+thread_local! {
+    static HISTORY_POINT: Cell<u64> = const { Cell::new(0) };
+}
+
+#[update]
+fn notify_group_member_count_changed(_group_id: u64) {}
+
+#[update]
+fn notify_referral_accepted(_referrer: Principal) {}
+
+#[update]
+fn notify_active_user(_principal: Principal) {}
+
+#[update]
+fn notify_profile_filled(_principal: Principal) {}
+
+#[update]
+fn read_reward_buffer() -> Vec<RewardableActivityResponse> {
+    vec![]
+}
+
+#[query]
+fn reward_timer_next_trigger() -> u64 {
+    0
+}
+
+#[query]
+fn get_history_point() -> u64 {
+    HISTORY_POINT.with(|point| point.get())
+}
+
+#[update]
+fn next_history_point() -> u64 {
+    HISTORY_POINT.with(|point| {
+        point.set(point.get() + 1);
+        point.get()
+    })
+}
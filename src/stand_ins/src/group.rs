@@ -0,0 +1,15 @@
+use catalyze_shared::group_with_members::{GroupFilter, GroupSort, GroupWithMembers};
+
+stand_in_storage!(
+    u64,
+    GroupWithMembers,
+    GroupFilter,
+    GroupSort,
+    |filter, id, group| match filter {
+        GroupFilter::Name(name) => group.name.to_lowercase().contains(&name.to_lowercase()),
+        GroupFilter::Owner(owner) => &group.owner == owner,
+        GroupFilter::Ids(ids) => ids.contains(id),
+        _ => true,
+    }
+);
+stand_in_storage_insertable!(GroupWithMembers);
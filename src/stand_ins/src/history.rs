@@ -0,0 +1,8 @@
+use catalyze_shared::{history_event::HistoryEvent, CanisterResult};
+use ic_cdk::update;
+
+/// Accepts every history event without storing it
+#[update]
+fn add_event(_history_point: u64, _event: HistoryEvent) -> CanisterResult<()> {
+    Ok(())
+}
@@ -0,0 +1,14 @@
+//! The ICP ledger has no blocks, boost transactions can not be validated against it
+use ic_cdk::query;
+use ic_ledger_types::{GetBlocksArgs, QueryBlocksResponse};
+
+#[query]
+fn query_blocks(args: GetBlocksArgs) -> QueryBlocksResponse {
+    QueryBlocksResponse {
+        certificate: None,
+        blocks: vec![],
+        chain_length: 0,
+        first_block_index: args.start,
+        archived_blocks: vec![],
+    }
+}
@@ -0,0 +1,54 @@
+//! Stand-in canisters for the PocketIC harness in the `tests` crate. Every feature builds one
+//! canister, the storage stand-ins keep their entries in heap memory and expose the methods the
+//! `StorageClient` of the proxy calls, the other stand-ins answer the calls of the proxy without
+//! doing anything. Build them with `scripts/build_stand_ins.sh`.
+use ic_cdk::query;
+
+#[cfg(any(
+    feature = "profile",
+    feature = "group",
+    feature = "event",
+    feature = "boost",
+    feature = "notification",
+    feature = "report",
+    feature = "topic",
+    feature = "friend_request"
+))]
+#[macro_use]
+mod storage;
+
+#[cfg(feature = "boost")]
+mod boost;
+#[cfg(feature = "event")]
+mod event;
+#[cfg(feature = "friend_request")]
+mod friend_request;
+#[cfg(feature = "global")]
+mod global;
+#[cfg(feature = "group")]
+mod group;
+#[cfg(feature = "history")]
+mod history;
+#[cfg(feature = "ledger")]
+mod ledger;
+#[cfg(feature = "notification")]
+mod notification;
+#[cfg(feature = "profile")]
+mod profile;
+#[cfg(feature = "report")]
+mod report;
+#[cfg(feature = "topic")]
+mod topic;
+#[cfg(feature = "transaction_handler")]
+mod transaction_handler;
+
+/// Read by `_dev_health` of the proxy
+#[query]
+fn icts_name() -> String {
+    env!("CARGO_PKG_NAME").to_string()
+}
+
+#[query]
+fn icts_version() -> String {
+    env!("CARGO_PKG_VERSION").to_string()
+}
@@ -0,0 +1,10 @@
+use catalyze_shared::notification::{Notification, NotificationFilter, NotificationSort};
+
+stand_in_storage!(
+    u64,
+    Notification,
+    NotificationFilter,
+    NotificationSort,
+    |_, _, _| true
+);
+stand_in_storage_insertable!(Notification);
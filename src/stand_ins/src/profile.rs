@@ -0,0 +1,28 @@
+use candid::Principal;
+use catalyze_shared::profile_with_refs::{
+    ProfileEntry, ProfileFilter, ProfileSort, ProfileWithRefs,
+};
+
+stand_in_storage!(
+    Principal,
+    ProfileWithRefs,
+    ProfileFilter,
+    ProfileSort,
+    |filter, _, profile| match filter {
+        ProfileFilter::Username(username) => &profile.metadata.username == username,
+        _ => true,
+    }
+);
+
+/// Profiles are keyed by the principal of the user
+#[update]
+fn insert(key: Principal, value: ProfileWithRefs) -> CanisterResult<ProfileEntry> {
+    STORE.with(|store| {
+        let mut store = store.borrow_mut();
+        if store.contains_key(&key) {
+            return Err(ApiError::duplicate().add_message("profile already exists"));
+        }
+        store.insert(key, value.clone());
+        Ok((key, value))
+    })
+}
@@ -0,0 +1,4 @@
+use catalyze_shared::report::{Report, ReportFilter, ReportSort};
+
+stand_in_storage!(u64, Report, ReportFilter, ReportSort, |_, _, _| true);
+stand_in_storage_insertable!(Report);
@@ -0,0 +1,152 @@
+/// Generates the endpoints of a storage canister backed by a thread local `BTreeMap`
+/// # Arguments
+/// * `$key` - The key of the stored entries
+/// * `$value` - The stored value
+/// * `$filter` - The filter of the store
+/// * `$sort` - The sort of the store, sorting is not applied
+/// * `$matches` - Decides if an entry matches a single filter, filters that are
+/// not relevant for the tests can match everything
+macro_rules! stand_in_storage {
+    ($key:ty, $value:ty, $filter:ty, $sort:ty, $matches:expr) => {
+        use std::{cell::RefCell, collections::BTreeMap};
+
+        use catalyze_shared::{api_error::ApiError, paged_response::PagedResponse, CanisterResult};
+        use ic_cdk::{query, update};
+
+        thread_local! {
+            static STORE: RefCell<BTreeMap<$key, $value>> = RefCell::new(BTreeMap::new());
+        }
+
+        fn matches(filters: &[$filter], key: &$key, value: &$value) -> bool {
+            let matches: fn(&$filter, &$key, &$value) -> bool = $matches;
+            filters.iter().all(|filter| matches(filter, key, value))
+        }
+
+        fn not_found() -> ApiError {
+            ApiError::not_found().add_message("entry not found")
+        }
+
+        #[query]
+        fn get(key: $key) -> CanisterResult<($key, $value)> {
+            STORE
+                .with(|store| store.borrow().get(&key).cloned())
+                .map(|value| (key, value))
+                .ok_or_else(not_found)
+        }
+
+        #[query]
+        fn get_many(keys: Vec<$key>) -> CanisterResult<Vec<($key, $value)>> {
+            Ok(STORE.with(|store| {
+                let store = store.borrow();
+                keys.into_iter()
+                    .filter_map(|key| store.get(&key).cloned().map(|value| (key, value)))
+                    .collect()
+            }))
+        }
+
+        #[query]
+        fn get_all() -> CanisterResult<Vec<($key, $value)>> {
+            Ok(STORE.with(|store| {
+                store
+                    .borrow()
+                    .iter()
+                    .map(|(key, value)| (*key, value.clone()))
+                    .collect()
+            }))
+        }
+
+        #[query]
+        fn find(filters: Vec<$filter>) -> CanisterResult<Option<($key, $value)>> {
+            Ok(STORE.with(|store| {
+                store
+                    .borrow()
+                    .iter()
+                    .find(|(key, value)| matches(&filters, key, value))
+                    .map(|(key, value)| (*key, value.clone()))
+            }))
+        }
+
+        #[query]
+        fn filter(filters: Vec<$filter>) -> CanisterResult<Vec<($key, $value)>> {
+            Ok(STORE.with(|store| {
+                store
+                    .borrow()
+                    .iter()
+                    .filter(|(key, value)| matches(&filters, key, value))
+                    .map(|(key, value)| (*key, value.clone()))
+                    .collect()
+            }))
+        }
+
+        #[query]
+        fn filter_paginated(
+            limit: usize,
+            page: usize,
+            _sort: $sort,
+            filters: Vec<$filter>,
+        ) -> CanisterResult<PagedResponse<($key, $value)>> {
+            Ok(PagedResponse::new(page, limit, filter(filters)?))
+        }
+
+        #[update]
+        fn update(key: $key, value: $value) -> CanisterResult<($key, $value)> {
+            STORE.with(|store| {
+                let mut store = store.borrow_mut();
+                if !store.contains_key(&key) {
+                    return Err(not_found());
+                }
+                store.insert(key, value.clone());
+                Ok((key, value))
+            })
+        }
+
+        #[update]
+        fn update_many(list: Vec<($key, $value)>) -> CanisterResult<Vec<($key, $value)>> {
+            list.into_iter()
+                .map(|(key, value)| update(key, value))
+                .collect()
+        }
+
+        #[update]
+        fn remove(key: $key) -> CanisterResult<bool> {
+            Ok(STORE.with(|store| store.borrow_mut().remove(&key).is_some()))
+        }
+
+        #[update]
+        fn remove_many(keys: Vec<$key>) -> CanisterResult<()> {
+            STORE.with(|store| {
+                let mut store = store.borrow_mut();
+                keys.iter().for_each(|key| {
+                    store.remove(key);
+                });
+            });
+            Ok(())
+        }
+
+        #[query]
+        fn size() -> CanisterResult<u64> {
+            Ok(STORE.with(|store| store.borrow().len() as u64))
+        }
+    };
+}
+
+/// Generates `insert` and `insert_many` for a store generated with `stand_in_storage!`
+/// that is keyed by an id, the ids are incremented from the highest id in the store
+macro_rules! stand_in_storage_insertable {
+    ($value:ty) => {
+        #[update]
+        fn insert(value: $value) -> CanisterResult<(u64, $value)> {
+            STORE.with(|store| {
+                let mut store = store.borrow_mut();
+                let id = store.keys().last().map(|id| id + 1).unwrap_or_default();
+                store.insert(id, value.clone());
+                Ok((id, value))
+            })
+        }
+
+        #[update]
+        fn insert_many(values: Vec<$value>) -> CanisterResult<Vec<(u64, $value)>> {
+            values.into_iter().map(insert).collect()
+        }
+    };
+}
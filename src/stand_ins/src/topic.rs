@@ -0,0 +1,4 @@
+use catalyze_shared::topic::{Topic, TopicFilter, TopicSort};
+
+stand_in_storage!(u64, Topic, TopicFilter, TopicSort, |_, _, _| true);
+stand_in_storage_insertable!(Topic);
@@ -0,0 +1,2 @@
+//! The transaction handler only calls the proxy, the stand-in is installed so its
+//! principal can be wired and has no endpoints besides `icts_name` and `icts_version`
@@ -0,0 +1,45 @@
+use candid::Encode;
+use catalyze_shared::{
+    attendee::JoinedAttendeeResponse,
+    event_with_attendees::{EventResponse, PostEvent},
+};
+use ic_agent::Identity;
+
+use crate::{result::CanisterResult, utils::Context};
+
+use super::utils;
+
+pub async fn add_event<I: 'static + Identity>(
+    ctx: &Context,
+    identity: I,
+    input: PostEvent,
+) -> eyre::Result<EventResponse> {
+    let resp =
+        utils::update_with_identity(ctx, identity, &ctx.proxy, "add_event", Encode!(&input)?)
+            .await?;
+    CanisterResult::try_from(resp.as_slice())?.into_result()
+}
+
+pub async fn get_event(ctx: &Context, event_id: u64) -> eyre::Result<EventResponse> {
+    let resp = utils::query(ctx, &ctx.proxy, "get_event", Encode!(&event_id)?).await?;
+    CanisterResult::try_from(resp.as_slice())?.into_result()
+}
+
+pub async fn join_event<I: 'static + Identity>(
+    ctx: &Context,
+    identity: I,
+    event_id: u64,
+) -> eyre::Result<JoinedAttendeeResponse> {
+    let resp =
+        utils::update_with_identity(ctx, identity, &ctx.proxy, "join_event", Encode!(&event_id)?)
+            .await?;
+    CanisterResult::try_from(resp.as_slice())?.into_result()
+}
+
+pub async fn get_event_attendees(
+    ctx: &Context,
+    event_id: u64,
+) -> eyre::Result<Vec<JoinedAttendeeResponse>> {
+    let resp = utils::query(ctx, &ctx.proxy, "get_event_attendees", Encode!(&event_id)?).await?;
+    CanisterResult::try_from(resp.as_slice())?.into_result()
+}
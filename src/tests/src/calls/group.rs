@@ -0,0 +1,96 @@
+use candid::{CandidType, Encode, Principal};
+use catalyze_shared::{
+    group_with_members::{GroupResponse, PostGroup},
+    old_member::JoinedMemberResponse,
+};
+use ic_agent::Identity;
+use serde::Deserialize;
+
+use crate::{result::CanisterResult, utils::Context};
+
+use super::utils;
+
+#[derive(CandidType, Deserialize)]
+pub enum JoinGroupResult {
+    Joined(JoinedMemberResponse),
+    Requested,
+    Waitlisted,
+}
+
+pub async fn add_group<I: 'static + Identity>(
+    ctx: &Context,
+    identity: I,
+    input: PostGroup,
+) -> eyre::Result<GroupResponse> {
+    let resp = utils::update_with_identity(
+        ctx,
+        identity,
+        &ctx.proxy,
+        "add_group",
+        Encode!(&input, &None::<String>)?,
+    )
+    .await?;
+    CanisterResult::try_from(resp.as_slice())?.into_result()
+}
+
+pub async fn get_group(ctx: &Context, group_id: u64) -> eyre::Result<GroupResponse> {
+    let resp = utils::query(ctx, &ctx.proxy, "get_group", Encode!(&group_id)?).await?;
+    CanisterResult::try_from(resp.as_slice())?.into_result()
+}
+
+pub async fn join_group<I: 'static + Identity>(
+    ctx: &Context,
+    identity: I,
+    group_id: u64,
+) -> eyre::Result<JoinGroupResult> {
+    let resp = utils::update_with_identity(
+        ctx,
+        identity,
+        &ctx.proxy,
+        "join_group",
+        Encode!(&group_id, &None::<String>)?,
+    )
+    .await?;
+    CanisterResult::try_from(resp.as_slice())?.into_result()
+}
+
+pub async fn invite_to_group<I: 'static + Identity>(
+    ctx: &Context,
+    identity: I,
+    group_id: u64,
+    member: Principal,
+) -> eyre::Result<()> {
+    let resp = utils::update_with_identity(
+        ctx,
+        identity,
+        &ctx.proxy,
+        "invite_to_group",
+        Encode!(&group_id, &member)?,
+    )
+    .await?;
+    CanisterResult::try_from(resp.as_slice())?.into_result()
+}
+
+pub async fn accept_owner_request_group_invite<I: 'static + Identity>(
+    ctx: &Context,
+    identity: I,
+    group_id: u64,
+) -> eyre::Result<()> {
+    let resp = utils::update_with_identity(
+        ctx,
+        identity,
+        &ctx.proxy,
+        "accept_owner_request_group_invite",
+        Encode!(&group_id)?,
+    )
+    .await?;
+    CanisterResult::try_from(resp.as_slice())?.into_result()
+}
+
+pub async fn get_group_members(
+    ctx: &Context,
+    group_id: u64,
+) -> eyre::Result<Vec<JoinedMemberResponse>> {
+    let resp = utils::query(ctx, &ctx.proxy, "get_group_members", Encode!(&group_id)?).await?;
+    CanisterResult::try_from(resp.as_slice())?.into_result()
+}
@@ -1,3 +1,9 @@
+#[cfg(test)]
+pub mod event;
+
+#[cfg(test)]
+pub mod group;
+
 #[cfg(test)]
 pub mod profile;
 
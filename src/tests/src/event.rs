@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use catalyze_shared::privacy::PrivacyType;
+use ic_agent::Identity;
+
+use crate::{
+    calls::{event, group, profile},
+    fixtures::{post_event, post_group, post_profile},
+    utils::{context, random_identity},
+};
+
+#[tokio::test]
+async fn test_add_and_join_event() {
+    let ctx = context().await;
+
+    let owner = Arc::new(random_identity());
+    let attendee = Arc::new(random_identity());
+
+    profile::add_profile(&ctx, owner.clone(), post_profile("event_owner"))
+        .await
+        .expect("Failed to add owner profile");
+    profile::add_profile(&ctx, attendee.clone(), post_profile("event_attendee"))
+        .await
+        .expect("Failed to add attendee profile");
+
+    let added_group = group::add_group(
+        &ctx,
+        owner.clone(),
+        post_group("event group", PrivacyType::Public),
+    )
+    .await
+    .expect("Failed to add group");
+
+    group::join_group(&ctx, attendee.clone(), added_group.id)
+        .await
+        .expect("Failed to join group");
+
+    let added_event = event::add_event(
+        &ctx,
+        owner.clone(),
+        post_event("public event", added_group.id, PrivacyType::Public),
+    )
+    .await
+    .expect("Failed to add event");
+
+    let fetched = event::get_event(&ctx, added_event.id)
+        .await
+        .expect("Failed to get event");
+
+    assert_eq!(fetched.id, added_event.id);
+
+    let joined = event::join_event(&ctx, attendee.clone(), added_event.id)
+        .await
+        .expect("Failed to join event");
+
+    assert_eq!(joined.principal, attendee.sender().unwrap());
+
+    let attendees = event::get_event_attendees(&ctx, added_event.id)
+        .await
+        .expect("Failed to get event attendees");
+
+    assert!(attendees
+        .iter()
+        .any(|a| a.principal == attendee.sender().unwrap()));
+}
@@ -0,0 +1,50 @@
+use catalyze_shared::{
+    asset::Asset, date_range::DateRange, event_with_attendees::PostEvent,
+    group_with_members::PostGroup, location::Location, privacy::PrivacyType, profile::PostProfile,
+    profile_privacy::ProfilePrivacy,
+};
+
+pub fn post_profile(name: &str) -> PostProfile {
+    PostProfile {
+        username: name.to_owned(),
+        display_name: name.to_owned(),
+        first_name: name.to_owned(),
+        last_name: name.to_owned(),
+        privacy: ProfilePrivacy::Public,
+        extra: "".to_owned(),
+    }
+}
+
+pub fn post_group(name: &str, privacy: PrivacyType) -> PostGroup {
+    PostGroup {
+        name: name.to_owned(),
+        description: "".to_owned(),
+        website: "".to_owned(),
+        matrix_space_id: "".to_owned(),
+        location: Location::None,
+        privacy,
+        privacy_gated_type_amount: None,
+        image: Asset::None,
+        banner_image: Asset::None,
+        tags: vec![],
+    }
+}
+
+pub fn post_event(name: &str, group_id: u64, privacy: PrivacyType) -> PostEvent {
+    PostEvent {
+        name: name.to_owned(),
+        description: "".to_owned(),
+        date: DateRange {
+            start_date: 0,
+            end_date: 0,
+        },
+        privacy,
+        website: "".to_owned(),
+        location: Location::None,
+        image: Asset::None,
+        banner_image: Asset::None,
+        tags: vec![],
+        metadata: None,
+        group_id: Some(group_id),
+    }
+}
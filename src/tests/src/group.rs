@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use catalyze_shared::privacy::PrivacyType;
+use ic_agent::Identity;
+
+use crate::{
+    calls::{group, profile},
+    fixtures::{post_group, post_profile},
+    utils::{context, random_identity},
+};
+
+#[tokio::test]
+async fn test_join_public_group() {
+    let ctx = context().await;
+
+    let owner = Arc::new(random_identity());
+    let member = Arc::new(random_identity());
+
+    profile::add_profile(&ctx, owner.clone(), post_profile("group_owner"))
+        .await
+        .expect("Failed to add owner profile");
+    profile::add_profile(&ctx, member.clone(), post_profile("group_member"))
+        .await
+        .expect("Failed to add member profile");
+
+    let added = group::add_group(
+        &ctx,
+        owner.clone(),
+        post_group("public group", PrivacyType::Public),
+    )
+    .await
+    .expect("Failed to add group");
+
+    let group::JoinGroupResult::Joined(joined) = group::join_group(&ctx, member.clone(), added.id)
+        .await
+        .expect("Failed to join group")
+    else {
+        panic!("Expected to join the group");
+    };
+
+    assert_eq!(joined.group_id, added.id);
+    assert_eq!(joined.principal, member.sender().unwrap());
+
+    let members = group::get_group_members(&ctx, added.id)
+        .await
+        .expect("Failed to get group members");
+
+    assert_eq!(members.len(), 2);
+
+    let group = group::get_group(&ctx, added.id)
+        .await
+        .expect("Failed to get group");
+
+    assert_eq!(group.members_count, 2);
+}
+
+#[tokio::test]
+async fn test_invite_to_private_group() {
+    let ctx = context().await;
+
+    let owner = Arc::new(random_identity());
+    let invitee = Arc::new(random_identity());
+
+    profile::add_profile(&ctx, owner.clone(), post_profile("private_owner"))
+        .await
+        .expect("Failed to add owner profile");
+    profile::add_profile(&ctx, invitee.clone(), post_profile("private_invitee"))
+        .await
+        .expect("Failed to add invitee profile");
+
+    let added = group::add_group(
+        &ctx,
+        owner.clone(),
+        post_group("private group", PrivacyType::Private),
+    )
+    .await
+    .expect("Failed to add group");
+
+    group::invite_to_group(&ctx, owner.clone(), added.id, invitee.sender().unwrap())
+        .await
+        .expect("Failed to invite to group");
+
+    group::accept_owner_request_group_invite(&ctx, invitee.clone(), added.id)
+        .await
+        .expect("Failed to accept invite");
+
+    let members = group::get_group_members(&ctx, added.id)
+        .await
+        .expect("Failed to get group members");
+
+    assert!(members
+        .iter()
+        .any(|member| member.principal == invitee.sender().unwrap()));
+}
@@ -0,0 +1,216 @@
+//! Local PocketIC harness, installs the proxy next to stand-in storage, ledger and global
+//! canisters and wires them together through the `_dev_set_*` endpoints so the tests can run
+//! offline. Requires the `POCKET_IC_BIN` environment variable to point to a PocketIC server.
+//!
+//! The proxy wasm is read from `<WASM_DIR>/proxy.wasm.gz` as produced by `scripts/build.sh`,
+//! the stand-ins from `<WASM_DIR>/stand-ins/<name>.wasm.gz` as produced from the `stand_ins`
+//! crate by `scripts/build_stand_ins.sh`. Stand-ins are installed without an init argument.
+//! `WASM_DIR` defaults to the `wasm` directory in the root of the repository.
+use std::path::PathBuf;
+
+use candid::{types::reserved::Reserved, CandidType, Encode, Principal};
+use ic_agent::Identity;
+use pocket_ic::{nonblocking::PocketIc, PocketIcBuilder, WasmResult};
+
+use crate::{
+    result::CanisterResult,
+    utils::{random_identity, Context},
+};
+
+const INIT_CYCLES: u128 = 100_000_000_000_000;
+
+/// The ICP ledger the proxy validates boost transactions against
+const LEDGER_CANISTER_ID: &str = "ryjl3-tyaaa-aaaaa-aaaba-cai";
+
+/// The stand-in canisters and the proxy endpoint used to wire them
+const STAND_INS: [(&str, &str); 11] = [
+    ("profile", "_dev_set_profile_canister"),
+    ("group", "_dev_set_group_canister"),
+    ("event", "_dev_set_event_canister"),
+    ("boost", "_dev_set_boost_canister"),
+    ("notification", "_dev_set_notification_canister"),
+    ("report", "_dev_set_report_canister"),
+    ("topic", "_dev_set_topic_canister"),
+    ("friend_request", "_dev_set_friend_request_canister"),
+    ("global", "_dev_set_global_canister"),
+    ("history", "_dev_set_history_canister"),
+    (
+        "transaction_handler",
+        "_dev_set_transaction_handler_canister",
+    ),
+];
+
+/// The stand-in that is installed at the ledger id instead of being wired
+const LEDGER_STAND_IN: &str = "ledger";
+
+#[derive(CandidType)]
+enum AdminRole {
+    Developer,
+}
+
+fn wasm_dir() -> PathBuf {
+    std::env::var("WASM_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../wasm"))
+}
+
+fn proxy_wasm() -> PathBuf {
+    wasm_dir().join("proxy.wasm.gz")
+}
+
+fn stand_in_wasm(name: &str) -> PathBuf {
+    wasm_dir()
+        .join("stand-ins")
+        .join(format!("{}.wasm.gz", name))
+}
+
+/// Fails before PocketIC is started when a wasm is missing, listing every missing wasm
+/// and the script that builds it
+fn check_wasms() {
+    let mut missing = vec![];
+
+    if !proxy_wasm().exists() {
+        missing.push(format!("{} (scripts/build.sh)", proxy_wasm().display()));
+    }
+
+    for name in STAND_INS
+        .iter()
+        .map(|(name, _)| *name)
+        .chain([LEDGER_STAND_IN])
+    {
+        if !stand_in_wasm(name).exists() {
+            missing.push(format!(
+                "{} (scripts/build_stand_ins.sh)",
+                stand_in_wasm(name).display()
+            ));
+        }
+    }
+
+    if !missing.is_empty() {
+        panic!(
+            "Missing wasm files, build them or set WASM_DIR:\n{}",
+            missing.join("\n")
+        );
+    }
+}
+
+fn read_wasm(path: PathBuf) -> Vec<u8> {
+    std::fs::read(&path)
+        .unwrap_or_else(|err| panic!("Failed to read wasm at {}: {}", path.display(), err))
+}
+
+async fn create_canister(pic: &PocketIc, controller: Principal) -> Principal {
+    let subnet = pic.topology().await.get_app_subnets()[0];
+    let canister_id = pic
+        .create_canister_on_subnet(Some(controller), None, subnet)
+        .await;
+    pic.add_cycles(canister_id, INIT_CYCLES).await;
+    canister_id
+}
+
+async fn update<T>(
+    pic: &PocketIc,
+    canister_id: Principal,
+    sender: Principal,
+    method: &str,
+    args: Vec<u8>,
+) -> eyre::Result<T>
+where
+    T: CandidType + for<'de> serde::Deserialize<'de>,
+{
+    match pic.update_call(canister_id, sender, method, args).await {
+        Ok(WasmResult::Reply(resp)) => CanisterResult::try_from(resp.as_slice())?.into_result(),
+        Ok(WasmResult::Reject(err)) => Err(eyre::eyre!("\"{}\" rejected: {}", method, err)),
+        Err(err) => Err(eyre::eyre!("\"{}\" failed: {}", method, err)),
+    }
+}
+
+/// Starts a PocketIC instance with the proxy and its stand-ins installed and wired,
+/// the returned agent uses the developer identity that installed the canisters
+pub async fn local_context() -> Context {
+    check_wasms();
+
+    let mut pic = PocketIcBuilder::new()
+        .with_nns_subnet()
+        .with_application_subnet()
+        .build_async()
+        .await;
+
+    let developer = random_identity();
+    let developer_principal = developer
+        .sender()
+        .expect("Failed to get the developer principal");
+
+    let proxy = create_canister(&pic, developer_principal).await;
+    pic.install_canister(
+        proxy,
+        read_wasm(proxy_wasm()),
+        Encode!().expect("Failed to encode init argument"),
+        Some(developer_principal),
+    )
+    .await;
+
+    // the controller registers itself so it passes the developer guards
+    let _: Reserved = update(
+        &pic,
+        proxy,
+        developer_principal,
+        "_dev_add_admin_role",
+        Encode!(&developer_principal, &AdminRole::Developer).expect("Failed to encode args"),
+    )
+    .await
+    .expect("Failed to register the developer");
+
+    for (name, set_method) in STAND_INS {
+        let canister_id = create_canister(&pic, developer_principal).await;
+        pic.install_canister(
+            canister_id,
+            read_wasm(stand_in_wasm(name)),
+            Encode!().expect("Failed to encode init argument"),
+            Some(developer_principal),
+        )
+        .await;
+
+        let _: Principal = update(
+            &pic,
+            proxy,
+            developer_principal,
+            set_method,
+            Encode!(&canister_id).expect("Failed to encode args"),
+        )
+        .await
+        .unwrap_or_else(|err| panic!("Failed to wire the {} canister: {}", name, err));
+    }
+
+    let ledger = Principal::from_text(LEDGER_CANISTER_ID).expect("Invalid ledger principal");
+    pic.create_canister_with_id(Some(developer_principal), None, ledger)
+        .await
+        .expect("Failed to create the ledger canister");
+    pic.add_cycles(ledger, INIT_CYCLES).await;
+    pic.install_canister(
+        ledger,
+        read_wasm(stand_in_wasm(LEDGER_STAND_IN)),
+        Encode!().expect("Failed to encode init argument"),
+        Some(developer_principal),
+    )
+    .await;
+
+    let url = pic.make_live(None).await;
+
+    let agent = ic_agent::Agent::builder()
+        .with_url(url.to_string())
+        .with_identity(developer)
+        .build()
+        .expect("Failed to build agent");
+
+    agent
+        .fetch_root_key()
+        .await
+        .expect("Failed to fetch root key for the icp agent");
+
+    Context {
+        agent,
+        proxy,
+        pic: Some(pic),
+    }
+}
@@ -1,6 +1,18 @@
+#[cfg(test)]
+mod event;
+
+#[cfg(test)]
+mod group;
+
 #[cfg(test)]
 mod profile;
 
+#[cfg(test)]
+mod fixtures;
+
+#[cfg(test)]
+mod harness;
+
 #[cfg(test)]
 mod utils;
 
@@ -6,6 +6,9 @@ use ic_agent::{
     identity::{BasicIdentity, Secp256k1Identity},
     Identity,
 };
+use pocket_ic::nonblocking::PocketIc;
+
+use crate::harness::local_context;
 
 pub fn random_identity() -> impl Identity {
     let private_key = SecretKey::random(&mut rand::thread_rng());
@@ -15,6 +18,8 @@ pub fn random_identity() -> impl Identity {
 pub struct Context {
     pub agent: ic_agent::Agent,
     pub proxy: Principal,
+    /// The local instance the proxy runs on, kept alive for the duration of the test
+    pub pic: Option<PocketIc>,
 }
 
 fn default_pem_path() -> String {
@@ -26,9 +31,15 @@ fn default_pem_path() -> String {
     )
 }
 
+/// Runs against a local PocketIC instance, unless `IC_URL` is set to run against a live network
 pub async fn context() -> Context {
-    let ic_url = std::env::var("IC_URL").unwrap_or_else(|_| "https://icp0.io".to_string());
+    match std::env::var("IC_URL") {
+        Ok(ic_url) => live_context(ic_url).await,
+        Err(_) => local_context().await,
+    }
+}
 
+async fn live_context(ic_url: String) -> Context {
     let identity = std::env::var("IDENTITY_PATH").unwrap_or_else(|_| default_pem_path());
     let identity = Path::new(&identity);
     let identity = BasicIdentity::from_pem_file(identity).expect("Failed to get identity");
@@ -48,5 +59,9 @@ pub async fn context() -> Context {
         .await
         .expect("Failed to fetch root key for the icp agent");
 
-    Context { proxy, agent }
+    Context {
+        proxy,
+        agent,
+        pic: None,
+    }
 }